@@ -62,6 +62,7 @@ type NGWord struct {
 	UserID       int64  `json:"user_id" db:"user_id"`
 	LivestreamID int64  `json:"livestream_id" db:"livestream_id"`
 	Word         string `json:"word" db:"word"`
+	MatchMode    string `json:"match_mode" db:"match_mode"`
 	CreatedAt    int64  `json:"created_at" db:"created_at"`
 }
 
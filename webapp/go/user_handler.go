@@ -53,9 +53,13 @@ type Theme struct {
 }
 
 type ThemeModel struct {
-	ID       int64 `db:"id"`
-	UserID   int64 `db:"user_id"`
-	DarkMode bool  `db:"dark_mode"`
+	ID                 int64          `db:"id"`
+	UserID             int64          `db:"user_id"`
+	DarkMode           bool           `db:"dark_mode"`
+	AccentColor        string         `db:"accent_color"`
+	BackgroundImageURL sql.NullString `db:"background_image_url"`
+	Font               string         `db:"font"`
+	ChatColorScheme    string         `db:"chat_color_scheme"`
 }
 
 type PostUserRequest struct {
@@ -0,0 +1,119 @@
+use crate::models::livestream::LivestreamId;
+use crate::models::tag::{Tag, TagId};
+use crate::models::theme::Theme;
+use crate::models::user::{User, UserId};
+use crate::ng_word_matcher::NgWordMatcher;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+/// プロセス内でエンティティを使い回すためのキャッシュ
+/// 更新系の処理は対応するキーを remove して整合性を保つ
+pub trait Cache<K, V>: Send + Sync {
+    fn get(&self, key: &K) -> Option<V>;
+    fn set(&self, key: K, value: V);
+    fn remove(&self, key: &K);
+    fn clear(&self);
+}
+
+/// 何もキャッシュしない実装。キャッシュを無効にしたいときやテストで使う
+#[derive(Debug, Clone, Default)]
+pub struct NoopCache;
+
+impl<K, V> Cache<K, V> for NoopCache {
+    fn get(&self, _key: &K) -> Option<V> {
+        None
+    }
+
+    fn set(&self, _key: K, _value: V) {}
+
+    fn remove(&self, _key: &K) {}
+
+    fn clear(&self) {}
+}
+
+/// 期限も上限も無くプロセス内に保持する実装。テストで使う
+#[derive(Debug, Clone)]
+pub struct InMemoryCache<K, V> {
+    entries: Arc<Mutex<HashMap<K, V>>>,
+}
+
+impl<K, V> Default for InMemoryCache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<K, V> Cache<K, V> for InMemoryCache<K, V>
+where
+    K: Eq + Hash + Send,
+    V: Clone + Send,
+{
+    fn get(&self, key: &K) -> Option<V> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn set(&self, key: K, value: V) {
+        self.entries.lock().unwrap().insert(key, value);
+    }
+
+    fn remove(&self, key: &K) {
+        self.entries.lock().unwrap().remove(key);
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+pub trait HaveUserCache {
+    type Cache: Cache<UserId, User>;
+
+    fn user_cache(&self) -> &Self::Cache;
+}
+
+pub trait HaveThemeCache {
+    type Cache: Cache<UserId, Theme>;
+
+    fn theme_cache(&self) -> &Self::Cache;
+}
+
+pub trait HaveTagCache {
+    type Cache: Cache<TagId, Tag>;
+
+    fn tag_cache(&self) -> &Self::Cache;
+}
+
+/// アイコン画像のハッシュ。アイコン未設定のユーザーは None を保持する
+pub trait HaveIconHashCache {
+    type Cache: Cache<UserId, Option<String>>;
+
+    fn icon_hash_cache(&self) -> &Self::Cache;
+}
+
+/// ライブ配信ごとのNGワードの照合器。コメントの照合ではDBに問い合わせない
+/// NGワードを登録したら捨てて作り直す。他のホストでの登録は期限切れで反映する
+pub trait HaveNgWordMatcherCache {
+    type Cache: Cache<LivestreamId, Arc<NgWordMatcher>>;
+
+    fn ng_word_matcher_cache(&self) -> &Self::Cache;
+}
+
+/// キャッシュに載っているものとキャッシュに無いキーに分ける
+pub fn partition_cached<K: Clone, V, C: Cache<K, V>>(
+    cache: &C,
+    keys: &[K],
+) -> (Vec<(K, V)>, Vec<K>) {
+    let mut hits = Vec::with_capacity(keys.len());
+    let mut misses = Vec::new();
+    for key in keys {
+        match cache.get(key) {
+            Some(value) => hits.push((key.clone(), value)),
+            None => misses.push(key.clone()),
+        }
+    }
+
+    (hits, misses)
+}
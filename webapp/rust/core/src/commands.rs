@@ -8,6 +8,12 @@ pub mod pdnsutil_command;
 pub enum CommandError {
     #[error("IO error: {0}")]
     IoError(#[from] io::Error),
+    #[error("config error: {0}")]
+    ConfigError(String),
+    #[error("request error: {0}")]
+    RequestError(String),
+    #[error("invalid output: {0}")]
+    InvalidOutput(String),
     #[error("test error")]
     TestError,
 }
@@ -1,10 +1,192 @@
-use crate::commands::{CommandOutput, CommandResult};
+use crate::commands::{CommandError, CommandOutput, CommandResult};
 use async_trait::async_trait;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
 
+#[cfg(test)]
+mod subdomain_record_config;
+
+pub const DEFAULT_ZONE: &str = "u.isucon.dev";
+pub const DEFAULT_TTL: u32 = 0;
+/// RFC 2181 で TTL は 31 ビットの符号なし整数
+pub const MAX_TTL: u32 = 2_147_483_647;
+
+const ZONE_ENV_KEY: &str = "ISUCON13_PDNS_ZONE";
+const RECORD_TYPE_ENV_KEY: &str = "ISUCON13_PDNS_RECORD_TYPE";
+const TTL_ENV_KEY: &str = "ISUCON13_PDNS_RECORD_TTL";
+const TARGET_ENV_KEY: &str = "ISUCON13_POWERDNS_SUBDOMAIN_ADDRESS";
+
+/// ゾーンに登録されているレコード
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsRecord {
+    /// 末尾のドットを除いた完全修飾名。例: test001.u.isucon.dev
+    pub name: String,
+    pub record_type: String,
+    pub ttl: u32,
+    pub content: String,
+}
+
+/// ユーザーのサブドメインに登録するレコードの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordType {
+    A,
+    Aaaa,
+    Cname,
+}
+
+impl RecordType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::Aaaa => "AAAA",
+            Self::Cname => "CNAME",
+        }
+    }
+}
+
+impl FromStr for RecordType {
+    type Err = CommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "A" => Ok(Self::A),
+            "AAAA" => Ok(Self::Aaaa),
+            "CNAME" => Ok(Self::Cname),
+            _ => Err(CommandError::ConfigError(format!(
+                "record type must be one of A, AAAA, CNAME: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// ユーザーのサブドメインをどのゾーンにどんなレコードで登録するか
+/// 起動時に検証し、以降は正しい値だけを持つ
+#[derive(Debug, Clone)]
+pub struct SubdomainRecordConfig {
+    /// 末尾のドットを除いたゾーン名
+    zone: String,
+    record_type: RecordType,
+    ttl: u32,
+    /// レコードの内容。CNAME の場合は末尾にドットを付けた完全修飾名
+    content: String,
+}
+
+impl SubdomainRecordConfig {
+    /// target は A なら IPv4 アドレス、AAAA なら IPv6 アドレス、CNAME ならホスト名
+    pub fn new(zone: &str, record_type: RecordType, ttl: u32, target: &str) -> CommandResult<Self> {
+        let zone = zone.trim_end_matches('.');
+        if !is_hostname(zone) {
+            return Err(CommandError::ConfigError(format!("invalid zone: {}", zone)));
+        }
+        if ttl > MAX_TTL {
+            return Err(CommandError::ConfigError(format!(
+                "TTL must be at most {}: {}",
+                MAX_TTL, ttl
+            )));
+        }
+
+        let invalid_target = || {
+            CommandError::ConfigError(format!(
+                "invalid {} record target: {}",
+                record_type.as_str(),
+                target
+            ))
+        };
+        let content = match record_type {
+            RecordType::A => target
+                .parse::<Ipv4Addr>()
+                .map_err(|_| invalid_target())?
+                .to_string(),
+            RecordType::Aaaa => target
+                .parse::<Ipv6Addr>()
+                .map_err(|_| invalid_target())?
+                .to_string(),
+            RecordType::Cname => {
+                let host = target.trim_end_matches('.');
+                // ゾーン内を指すと、ユーザー名によっては自分自身を指す CNAME になる
+                let in_zone = host == zone || host.ends_with(&format!(".{}", zone));
+                if !is_hostname(host) || in_zone {
+                    return Err(invalid_target());
+                }
+                format!("{}.", host.to_ascii_lowercase())
+            }
+        };
+
+        Ok(Self {
+            zone: zone.to_ascii_lowercase(),
+            record_type,
+            ttl,
+            content,
+        })
+    }
+
+    /// ISUCON13_PDNS_ZONE, ISUCON13_PDNS_RECORD_TYPE, ISUCON13_PDNS_RECORD_TTL と
+    /// ISUCON13_POWERDNS_SUBDOMAIN_ADDRESS (必須) から組み立てる
+    pub fn from_env() -> CommandResult<Self> {
+        let zone = std::env::var(ZONE_ENV_KEY).unwrap_or_else(|_| DEFAULT_ZONE.to_owned());
+        let record_type = match std::env::var(RECORD_TYPE_ENV_KEY) {
+            Ok(record_type) => record_type.parse()?,
+            Err(_) => RecordType::A,
+        };
+        let ttl = match std::env::var(TTL_ENV_KEY) {
+            Ok(ttl) => ttl.parse().map_err(|_| {
+                CommandError::ConfigError(format!("{} must be a number: {}", TTL_ENV_KEY, ttl))
+            })?,
+            Err(_) => DEFAULT_TTL,
+        };
+        let target = std::env::var(TARGET_ENV_KEY).map_err(|_| {
+            CommandError::ConfigError(format!("environ {} must be provided", TARGET_ENV_KEY))
+        })?;
+
+        Self::new(&zone, record_type, ttl, &target)
+    }
+
+    pub fn zone(&self) -> &str {
+        &self.zone
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        self.record_type
+    }
+
+    pub fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// 末尾のドットを除いたホスト名として使えるか
+fn is_hostname(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+/// ユーザーのサブドメインを管理する
+/// name にはゾーンを除いたラベル、content にはレコードの内容を渡す
 #[cfg_attr(any(feature = "test", test), mockall::automock)]
 #[async_trait]
 pub trait PDNSUtilCommand {
-    async fn add_record(&self, name: &str, domain: &str) -> CommandResult<CommandOutput>;
+    async fn add_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput>;
+
+    async fn delete_record(&self, name: &str) -> CommandResult<CommandOutput>;
+
+    async fn list_records(&self) -> CommandResult<Vec<DnsRecord>>;
+
+    /// 既存のレコードを指定した内容だけに置き換える
+    async fn update_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput>;
 }
 
 pub trait HavePDNSUtilCommand {
@@ -12,3 +194,7 @@ pub trait HavePDNSUtilCommand {
 
     fn pdnsutil_command(&self) -> &Self::Command;
 }
+
+pub trait HaveSubdomainRecordConfig {
+    fn subdomain_record(&self) -> &SubdomainRecordConfig;
+}
@@ -0,0 +1,74 @@
+use crate::commands::pdnsutil_command::{RecordType, SubdomainRecordConfig, MAX_TTL};
+use crate::commands::CommandError;
+
+#[test]
+fn a_record() {
+    let config =
+        SubdomainRecordConfig::new("u.isucon.dev.", RecordType::A, 60, "127.0.0.1").unwrap();
+    assert_eq!(config.zone(), "u.isucon.dev");
+    assert_eq!(config.record_type(), RecordType::A);
+    assert_eq!(config.ttl(), 60);
+    assert_eq!(config.content(), "127.0.0.1");
+}
+
+#[test]
+fn aaaa_record() {
+    let config =
+        SubdomainRecordConfig::new("u.isucon.dev", RecordType::Aaaa, 0, "2001:DB8::0001").unwrap();
+    assert_eq!(config.content(), "2001:db8::1");
+}
+
+#[test]
+fn cname_record() {
+    let config =
+        SubdomainRecordConfig::new("u.isucon.dev", RecordType::Cname, 300, "LB.example.com")
+            .unwrap();
+    assert_eq!(config.content(), "lb.example.com.");
+}
+
+#[test]
+fn invalid_target() {
+    for (record_type, target) in [
+        (RecordType::A, "::1"),
+        (RecordType::A, "lb.example.com"),
+        (RecordType::Aaaa, "127.0.0.1"),
+        (RecordType::Cname, "127.0.0.1:80"),
+        (RecordType::Cname, "-lb.example.com"),
+        (RecordType::Cname, "lb..example.com"),
+        (RecordType::Cname, "lb.u.isucon.dev"),
+    ] {
+        let result = SubdomainRecordConfig::new("u.isucon.dev", record_type, 0, target);
+        assert!(
+            matches!(result, Err(CommandError::ConfigError(_))),
+            "{:?} {}",
+            record_type,
+            target
+        );
+    }
+}
+
+#[test]
+fn invalid_zone() {
+    for zone in ["", ".", "u_isucon.dev", "u..isucon.dev"] {
+        let result = SubdomainRecordConfig::new(zone, RecordType::A, 0, "127.0.0.1");
+        assert!(
+            matches!(result, Err(CommandError::ConfigError(_))),
+            "{}",
+            zone
+        );
+    }
+}
+
+#[test]
+fn invalid_ttl() {
+    let result =
+        SubdomainRecordConfig::new("u.isucon.dev", RecordType::A, MAX_TTL + 1, "127.0.0.1");
+    assert!(matches!(result, Err(CommandError::ConfigError(_))));
+}
+
+#[test]
+fn parse_record_type() {
+    assert_eq!("aaaa".parse::<RecordType>().unwrap(), RecordType::Aaaa);
+    assert_eq!("CNAME".parse::<RecordType>().unwrap(), RecordType::Cname);
+    assert!("MX".parse::<RecordType>().is_err());
+}
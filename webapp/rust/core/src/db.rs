@@ -26,6 +26,12 @@ pub async fn get_db_pool() -> Result<DBPool, sqlx::Error> {
     Ok(pool)
 }
 
+/// 使うまで接続しないプール。DBに触れない mock だけのテストで使う
+#[cfg(any(feature = "test", test))]
+pub fn get_lazy_db_pool() -> DBPool {
+    MySqlPoolOptions::new().connect_lazy_with(build_database_connection_options_for_test())
+}
+
 fn _build_database_connection_options(is_test_mode: bool) -> sqlx::mysql::MySqlConnectOptions {
     let mut options = sqlx::mysql::MySqlConnectOptions::new()
         .host("127.0.0.1")
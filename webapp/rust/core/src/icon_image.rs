@@ -0,0 +1,164 @@
+use image::imageops::FilterType;
+use image::io::{Limits, Reader};
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+
+#[cfg(test)]
+mod process_icon;
+#[cfg(test)]
+mod sniff;
+
+/// アップロードできるアイコン画像の最大バイト数
+pub const MAX_ICON_BYTES: usize = 5 * 1024 * 1024;
+/// アップロードできるアイコン画像の最大の幅・高さ
+pub const MAX_ICON_DIMENSION: u32 = 4096;
+/// アップロード時に生成するサムネイルの一辺のピクセル数
+pub const THUMBNAIL_SIZES: [u32; 2] = [64, 256];
+
+const THUMBNAIL_JPEG_QUALITY: u8 = 85;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IconImageError {
+    #[error("icon image is empty")]
+    Empty,
+    #[error("icon image is too large: {0} bytes (max {MAX_ICON_BYTES} bytes)")]
+    TooLarge(usize),
+    #[error("unsupported icon image format (supported: JPEG, PNG, WebP, GIF)")]
+    UnsupportedFormat,
+    #[error("icon image dimensions {0}x{1} exceed {MAX_ICON_DIMENSION}x{MAX_ICON_DIMENSION}")]
+    TooLargeDimensions(u32, u32),
+    #[error("invalid icon image: {0}")]
+    Decode(#[from] image::ImageError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconFormat {
+    Jpeg,
+    Png,
+    WebP,
+    Gif,
+}
+
+impl IconFormat {
+    /// 先頭のマジックナンバーから画像フォーマットを判定する
+    pub fn sniff(image: &[u8]) -> Option<Self> {
+        if image.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if image.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if image.starts_with(b"GIF87a") || image.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if image.len() >= 12 && &image[0..4] == b"RIFF" && &image[8..12] == b"WEBP" {
+            Some(Self::WebP)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::WebP => "image/webp",
+            Self::Gif => "image/gif",
+        }
+    }
+
+    fn image_format(&self) -> ImageFormat {
+        match self {
+            Self::Jpeg => ImageFormat::Jpeg,
+            Self::Png => ImageFormat::Png,
+            Self::WebP => ImageFormat::WebP,
+            Self::Gif => ImageFormat::Gif,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Thumbnail {
+    pub size: u32,
+    pub mime_type: &'static str,
+    pub image: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct ProcessedIcon {
+    pub format: IconFormat,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+/// アップロードされたアイコン画像を検証し、サムネイルを生成する
+pub fn process_icon(image: &[u8]) -> Result<ProcessedIcon, IconImageError> {
+    let (format, decoded) = decode(image)?;
+    let thumbnails = THUMBNAIL_SIZES
+        .iter()
+        .map(|&size| encode_thumbnail(&decoded, format, size))
+        .collect::<Result<_, _>>()?;
+
+    Ok(ProcessedIcon { format, thumbnails })
+}
+
+/// 指定サイズのサムネイルを生成する
+/// サムネイルが保存されていない画像に対して使う
+pub fn thumbnail(image: &[u8], size: u32) -> Result<Thumbnail, IconImageError> {
+    let (format, decoded) = decode(image)?;
+    encode_thumbnail(&decoded, format, size)
+}
+
+fn decode(image: &[u8]) -> Result<(IconFormat, DynamicImage), IconImageError> {
+    if image.is_empty() {
+        return Err(IconImageError::Empty);
+    }
+    if image.len() > MAX_ICON_BYTES {
+        return Err(IconImageError::TooLarge(image.len()));
+    }
+    let format = IconFormat::sniff(image).ok_or(IconImageError::UnsupportedFormat)?;
+
+    let reader = || Reader::with_format(Cursor::new(image), format.image_format());
+    let (width, height) = reader().into_dimensions()?;
+    if width > MAX_ICON_DIMENSION || height > MAX_ICON_DIMENSION {
+        return Err(IconImageError::TooLargeDimensions(width, height));
+    }
+
+    // ヘッダを偽装した画像で大量にメモリを確保しないよう、デコード時にも制限をかける
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_ICON_DIMENSION);
+    limits.max_image_height = Some(MAX_ICON_DIMENSION);
+    let mut reader = reader();
+    reader.limits(limits);
+    let decoded = reader.decode()?;
+
+    Ok((format, decoded))
+}
+
+/// 正方形に切り抜いて縮小する
+/// 透過を保てるよう、JPEG以外はPNGで出力する
+fn encode_thumbnail(
+    decoded: &DynamicImage,
+    format: IconFormat,
+    size: u32,
+) -> Result<Thumbnail, IconImageError> {
+    let resized = decoded.resize_to_fill(size, size, FilterType::Triangle);
+
+    let mut buf = Vec::new();
+    let mime_type = match format {
+        IconFormat::Jpeg => {
+            let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(
+                &mut buf,
+                THUMBNAIL_JPEG_QUALITY,
+            );
+            DynamicImage::ImageRgb8(resized.to_rgb8()).write_with_encoder(encoder)?;
+            IconFormat::Jpeg.mime_type()
+        }
+        IconFormat::Png | IconFormat::WebP | IconFormat::Gif => {
+            resized.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)?;
+            IconFormat::Png.mime_type()
+        }
+    };
+
+    Ok(Thumbnail {
+        size,
+        mime_type,
+        image: buf,
+    })
+}
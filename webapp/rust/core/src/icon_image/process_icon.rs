@@ -0,0 +1,82 @@
+use crate::icon_image::{
+    process_icon, IconFormat, IconImageError, MAX_ICON_BYTES, MAX_ICON_DIMENSION, THUMBNAIL_SIZES,
+};
+use image::{ImageFormat, RgbaImage};
+use std::io::Cursor;
+
+fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = image::DynamicImage::ImageRgba8(RgbaImage::new(width, height));
+    let image = match format {
+        ImageFormat::Jpeg => image::DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => image,
+    };
+    let mut buf = Vec::new();
+    image.write_to(&mut Cursor::new(&mut buf), format).unwrap();
+    buf
+}
+
+#[test]
+fn generates_thumbnails() {
+    let processed = process_icon(&encode(300, 200, ImageFormat::Png)).unwrap();
+
+    assert_eq!(processed.format, IconFormat::Png);
+    assert_eq!(processed.thumbnails.len(), THUMBNAIL_SIZES.len());
+    for (thumbnail, size) in processed.thumbnails.iter().zip(THUMBNAIL_SIZES) {
+        assert_eq!(thumbnail.size, size);
+        assert_eq!(thumbnail.mime_type, "image/png");
+        let decoded = image::load_from_memory(&thumbnail.image).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (size, size));
+    }
+}
+
+#[test]
+fn keeps_jpeg_thumbnails_as_jpeg() {
+    let processed = process_icon(&encode(100, 100, ImageFormat::Jpeg)).unwrap();
+
+    assert_eq!(processed.format, IconFormat::Jpeg);
+    for thumbnail in processed.thumbnails {
+        assert_eq!(thumbnail.mime_type, "image/jpeg");
+        assert_eq!(IconFormat::sniff(&thumbnail.image), Some(IconFormat::Jpeg));
+    }
+}
+
+#[test]
+fn rejects_empty() {
+    assert!(matches!(process_icon(b""), Err(IconImageError::Empty)));
+}
+
+#[test]
+fn rejects_too_large_bytes() {
+    let image = vec![0xFF; MAX_ICON_BYTES + 1];
+    assert!(matches!(
+        process_icon(&image),
+        Err(IconImageError::TooLarge(_))
+    ));
+}
+
+#[test]
+fn rejects_unsupported_format() {
+    assert!(matches!(
+        process_icon(b"not an image"),
+        Err(IconImageError::UnsupportedFormat)
+    ));
+}
+
+#[test]
+fn rejects_too_large_dimensions() {
+    let image = encode(MAX_ICON_DIMENSION + 1, 1, ImageFormat::Png);
+    assert!(matches!(
+        process_icon(&image),
+        Err(IconImageError::TooLargeDimensions(_, 1))
+    ));
+}
+
+#[test]
+fn rejects_broken_image() {
+    let mut image = encode(10, 10, ImageFormat::Png);
+    image.truncate(image.len() / 2);
+    assert!(matches!(
+        process_icon(&image),
+        Err(IconImageError::Decode(_))
+    ));
+}
@@ -0,0 +1,28 @@
+use crate::icon_image::IconFormat;
+
+#[test]
+fn sniff_supported_formats() {
+    let cases: [(&[u8], IconFormat); 5] = [
+        (&[0xFF, 0xD8, 0xFF, 0xE0, 0x00], IconFormat::Jpeg),
+        (b"\x89PNG\r\n\x1a\n\x00", IconFormat::Png),
+        (b"GIF87a\x00", IconFormat::Gif),
+        (b"GIF89a\x00", IconFormat::Gif),
+        (b"RIFF\x00\x00\x00\x00WEBPVP8 ", IconFormat::WebP),
+    ];
+    for (image, expected) in cases {
+        assert_eq!(IconFormat::sniff(image), Some(expected));
+    }
+}
+
+#[test]
+fn sniff_unsupported_formats() {
+    let cases: [&[u8]; 4] = [
+        b"",
+        b"BM\x00\x00",
+        b"RIFF\x00\x00\x00\x00WAVE",
+        b"<svg></svg>",
+    ];
+    for image in cases {
+        assert_eq!(IconFormat::sniff(image), None);
+    }
+}
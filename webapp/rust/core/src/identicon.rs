@@ -0,0 +1,55 @@
+use image::{ImageFormat, Rgb, RgbImage};
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+
+#[cfg(test)]
+mod generate;
+#[cfg(test)]
+mod hash;
+
+/// 生成する画像の見た目を変えたら上げる。ハッシュが変わり、クライアントのキャッシュが捨てられる
+pub const VERSION: u32 = 1;
+
+/// 縦横のマス数。左右対称に描くので、実際に決めるのは左半分と中央の列だけ
+const GRID: u32 = 5;
+const BACKGROUND: Rgb<u8> = Rgb([240, 240, 240]);
+
+/// ユーザー名から決まる左右対称のアイコンをPNGで生成する
+/// 同じユーザー名とサイズなら常に同じ画像になる
+pub fn generate(seed: &str, size: u32) -> Vec<u8> {
+    let digest = Sha256::digest(seed.as_bytes());
+    // 背景と区別できるよう、明るすぎない色にする
+    let color = Rgb([digest[0] / 2 + 32, digest[1] / 2 + 32, digest[2] / 2 + 32]);
+    let filled = |row: u32, col: u32| digest[(3 + row * 3 + col) as usize] & 1 == 1;
+
+    // 上下左右に半マスずつ余白をとる
+    let half_cells = GRID * 2 + 2;
+    let image = RgbImage::from_fn(size, size, |x, y| {
+        // 右半分は左半分を折り返して描く
+        let x = x.min(size - 1 - x);
+        let hx = x * half_cells / size;
+        let hy = y * half_cells / size;
+        if hx == 0 || hy == 0 || hx > GRID * 2 || hy > GRID * 2 {
+            return BACKGROUND;
+        }
+        if filled((hy - 1) / 2, (hx - 1) / 2) {
+            color
+        } else {
+            BACKGROUND
+        }
+    });
+
+    let mut buf = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+        .expect("encoding an in-memory PNG never fails");
+    buf
+}
+
+/// 生成したアイコンのハッシュ。画像を描かずに、ユーザー名と生成方法の版から決める
+pub fn hash(seed: &str) -> String {
+    format!(
+        "{:x}",
+        Sha256::digest(format!("identicon-v{}:{}", VERSION, seed).as_bytes())
+    )
+}
@@ -0,0 +1,25 @@
+use crate::identicon::generate;
+
+#[test]
+fn generate_is_deterministic() {
+    assert_eq!(generate("alice", 64), generate("alice", 64));
+    assert_ne!(generate("alice", 64), generate("bob", 64));
+}
+
+#[test]
+fn generate_png_of_given_size() {
+    for size in [64, 256] {
+        let image =
+            image::load_from_memory_with_format(&generate("alice", size), image::ImageFormat::Png)
+                .unwrap()
+                .to_rgb8();
+        assert_eq!(image.dimensions(), (size, size));
+
+        // 左右対称になっている
+        for y in 0..size {
+            for x in 0..size / 2 {
+                assert_eq!(image.get_pixel(x, y), image.get_pixel(size - 1 - x, y));
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use crate::identicon::hash;
+
+#[test]
+fn hash_is_deterministic() {
+    assert_eq!(hash("alice"), hash("alice"));
+    assert_ne!(hash("alice"), hash("bob"));
+    assert_eq!(hash("alice").len(), 64);
+}
@@ -1,6 +1,13 @@
+pub mod caches;
 pub mod commands;
 pub mod db;
+pub mod icon_image;
+pub mod identicon;
 pub mod models;
+pub mod ng_word_matcher;
+pub mod notifiers;
 pub mod repos;
 pub mod services;
+pub mod storages;
 pub mod utils;
+pub mod validation;
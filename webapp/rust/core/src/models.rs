@@ -1,3 +1,4 @@
+pub mod cursor;
 pub mod icon;
 pub mod livestream;
 pub mod livestream_comment;
@@ -6,10 +7,14 @@ pub mod livestream_ranking_entry;
 pub mod livestream_statistics;
 pub mod livestream_tag;
 pub mod livestream_viewers_history;
+pub mod login_attempt;
 // pub mod mysql_decimal;
 pub mod ng_word;
+pub mod ng_word_purge_job;
+pub mod password_reset_token;
 pub mod reaction;
 pub mod reservation_slot;
+pub mod session;
 pub mod tag;
 pub mod theme;
 pub mod user;
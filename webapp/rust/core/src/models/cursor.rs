@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod build;
+
+/// created_at と id の組で一覧上の位置を表すカーソル
+/// created_at が同じ行は id で順序を決める
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: i64,
+    pub id: i64,
+}
+
+/// ページングの起点
+/// 一覧は新しい順に並べ、Before はカーソルより古いもの、After はカーソルより新しいものを取得する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageCursor<C> {
+    Before(C),
+    After(C),
+}
+
+#[derive(Debug)]
+pub struct CursorPage<T, C> {
+    pub items: Vec<T>,
+    /// 同じ向きに続きを取得するためのカーソル。続きがなければ None
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> CursorPage<T, C> {
+    /// 新しい順に並んだ items からページを組み立てる
+    pub fn build(
+        items: Vec<T>,
+        cursor: Option<&PageCursor<C>>,
+        limit: i64,
+        cursor_of: impl Fn(&T) -> C,
+    ) -> Self {
+        let next_cursor = if (items.len() as i64) < limit {
+            None
+        } else {
+            match cursor {
+                Some(PageCursor::After(_)) => items.first().map(&cursor_of),
+                _ => items.last().map(&cursor_of),
+            }
+        };
+
+        Self { items, next_cursor }
+    }
+}
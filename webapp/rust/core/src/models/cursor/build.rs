@@ -0,0 +1,40 @@
+use crate::models::cursor::{CursorPage, PageCursor};
+
+#[test]
+fn first_page_case() {
+    let page = CursorPage::build(vec![5, 4, 3], None::<&PageCursor<i64>>, 3, |i| *i);
+    assert_eq!(page.items, vec![5, 4, 3]);
+    // 古い方へ続きを取得する
+    assert_eq!(page.next_cursor, Some(3));
+}
+
+#[test]
+fn before_case() {
+    let cursor = PageCursor::Before(6);
+    let page = CursorPage::build(vec![5, 4], Some(&cursor), 2, |i| *i);
+    assert_eq!(page.next_cursor, Some(4));
+}
+
+#[test]
+fn after_case() {
+    // 新しい方へ続きを取得する
+    let cursor = PageCursor::After(3);
+    let page = CursorPage::build(vec![5, 4], Some(&cursor), 2, |i| *i);
+    assert_eq!(page.next_cursor, Some(5));
+}
+
+#[test]
+fn last_page_case() {
+    for cursor in [
+        None,
+        Some(PageCursor::Before(6)),
+        Some(PageCursor::After(3)),
+    ] {
+        let page = CursorPage::build(vec![5, 4], cursor.as_ref(), 3, |i| *i);
+        assert_eq!(page.items, vec![5, 4]);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    let page = CursorPage::build(Vec::new(), None::<&PageCursor<i64>>, 3, |i: &i64| *i);
+    assert_eq!(page.next_cursor, None);
+}
@@ -1,6 +1,59 @@
 use crate::models::user::UserId;
+use kubetsu::Id;
 
-pub struct CreateIcon {
+#[derive(Debug, sqlx::FromRow)]
+pub struct Icon {
+    pub id: Id<Self, i64>,
+    pub user_id: UserId,
+    /// 外部ストレージに保存している場合は None
+    pub image: Option<Vec<u8>>,
+    /// imageのSHA-256。アップロード時に計算して保存する
+    pub hash: String,
+    pub mime_type: String,
+}
+
+pub type IconId = Id<Icon, i64>;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct IconHash {
     pub user_id: UserId,
+    pub hash: String,
+}
+
+/// 画像とそのMIMEタイプ
+#[derive(Debug, sqlx::FromRow)]
+pub struct IconImage {
+    pub mime_type: String,
     pub image: Vec<u8>,
 }
+
+/// DBに保存されている画像。image が None なら hash をキーに外部ストレージから取得する
+#[derive(Debug, sqlx::FromRow)]
+pub struct StoredIconImage {
+    pub mime_type: String,
+    pub hash: String,
+    pub image: Option<Vec<u8>>,
+}
+
+/// まだDBに保存されている画像。外部ストレージへの移行に使う
+#[derive(Debug, sqlx::FromRow)]
+pub struct IconBlob {
+    pub id: i64,
+    pub hash: String,
+    pub image: Vec<u8>,
+}
+
+pub struct CreateIcon {
+    pub user_id: UserId,
+    pub image: Option<Vec<u8>>,
+    pub hash: String,
+    pub mime_type: String,
+}
+
+pub struct CreateIconThumbnail {
+    pub user_id: UserId,
+    pub size: u32,
+    pub mime_type: String,
+    pub image: Option<Vec<u8>>,
+    pub hash: String,
+}
@@ -1,5 +1,6 @@
 use crate::models::livestream::LivestreamId;
 use crate::models::user::UserId;
+use fake::Dummy;
 use kubetsu::Id;
 
 #[derive(Debug, sqlx::FromRow)]
@@ -14,6 +15,7 @@ pub struct LivestreamComment {
 
 pub type LivestreamCommentId = Id<LivestreamComment, i64>;
 
+#[derive(Dummy)]
 pub struct CreateLivestreamComment {
     pub user_id: UserId,
     pub livestream_id: LivestreamId,
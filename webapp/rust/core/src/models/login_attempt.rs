@@ -0,0 +1,11 @@
+use fake::Dummy;
+
+/// ログイン失敗の記録。キーはユーザー名もしくは接続元IPごとに分ける
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow, Dummy)]
+pub struct LoginAttempt {
+    pub attempt_key: String,
+    pub failures: i64,
+    pub last_failed_at: i64,
+    /// この時刻まではログインを受け付けない
+    pub locked_until: i64,
+}
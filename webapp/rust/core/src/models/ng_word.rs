@@ -1,6 +1,8 @@
 use crate::models::livestream::LivestreamId;
 use crate::models::user::UserId;
 use kubetsu::Id;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, serde::Serialize, sqlx::FromRow)]
 pub struct NgWord {
@@ -8,6 +10,8 @@ pub struct NgWord {
     pub user_id: UserId,
     pub livestream_id: LivestreamId,
     pub word: String,
+    #[sqlx(try_from = "String")]
+    pub match_mode: NgWordMatchMode,
     #[sqlx(default)]
     pub created_at: i64,
 }
@@ -18,5 +22,69 @@ pub struct CreateNgWord {
     pub user_id: UserId,
     pub livestream_id: LivestreamId,
     pub word: String,
+    pub match_mode: NgWordMatchMode,
     pub created_at: i64,
 }
+
+/// NGワードをコメントとどう照合するか
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NgWordMatchMode {
+    /// 部分文字列としてバイト列で比較する。LIKE CONCAT('%', word, '%') と同じ
+    #[default]
+    Substring,
+    /// 前後が英数字などの単語の文字でない位置にだけ一致する
+    WholeWord,
+    /// 大文字と小文字を区別しない部分一致
+    CaseInsensitive,
+    /// NFKC 正規化した上で大文字と小文字を区別しない部分一致。全角と半角を同一視する
+    Nfkc,
+    /// 正規表現
+    Regex,
+}
+
+impl NgWordMatchMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Substring => "substring",
+            Self::WholeWord => "whole_word",
+            Self::CaseInsensitive => "case_insensitive",
+            Self::Nfkc => "nfkc",
+            Self::Regex => "regex",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNgWordMatchModeError(String);
+
+impl fmt::Display for ParseNgWordMatchModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown NG word match mode: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseNgWordMatchModeError {}
+
+impl FromStr for NgWordMatchMode {
+    type Err = ParseNgWordMatchModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "substring" => Ok(Self::Substring),
+            "whole_word" => Ok(Self::WholeWord),
+            "case_insensitive" => Ok(Self::CaseInsensitive),
+            "nfkc" => Ok(Self::Nfkc),
+            "regex" => Ok(Self::Regex),
+            _ => Err(ParseNgWordMatchModeError(s.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<String> for NgWordMatchMode {
+    type Error = ParseNgWordMatchModeError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
@@ -0,0 +1,36 @@
+use crate::models::livestream::LivestreamId;
+use crate::models::ng_word::NgWordId;
+use kubetsu::Id;
+
+/// NGワードの登録で、過去のコメントを消す処理の進み具合
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct NgWordPurgeJob {
+    pub id: Id<Self, i64>,
+    pub livestream_id: LivestreamId,
+    pub word_id: NgWordId,
+    pub status: NgWordPurgeJobStatus,
+    /// 調べ終えたコメントの件数
+    pub scanned: i64,
+    /// 削除したコメントの件数
+    pub deleted: i64,
+    /// 失敗した場合の理由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub type NgWordPurgeJobId = Id<NgWordPurgeJob, i64>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NgWordPurgeJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl NgWordPurgeJobStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::models::user::UserId;
+use fake::Dummy;
+use kubetsu::Id;
+
+/// パスワード再設定用のトークン。トークン自体は保存せず SHA-256 のハッシュだけを持つ
+#[derive(Debug, Clone, sqlx::FromRow, Dummy)]
+pub struct PasswordResetToken {
+    pub id: PasswordResetTokenId,
+    pub user_id: UserId,
+    pub token_hash: String,
+    pub expires_at: i64,
+    /// 使用済みなら使用した時刻
+    pub used_at: Option<i64>,
+    pub created_at: i64,
+}
+
+pub type PasswordResetTokenId = Id<PasswordResetToken, i64>;
+
+#[derive(Debug, Clone, PartialEq, Dummy)]
+pub struct CreatePasswordResetToken {
+    pub user_id: UserId,
+    pub token_hash: String,
+    pub expires_at: i64,
+    pub created_at: i64,
+}
@@ -0,0 +1,14 @@
+use crate::models::user::UserId;
+use fake::Dummy;
+use kubetsu::Id;
+
+/// サーバー側で管理するログインセッション
+/// 行が存在しないセッションはログアウト済みもしくは失効済みとして扱う
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow, Dummy)]
+pub struct Session {
+    pub id: SessionId,
+    pub user_id: UserId,
+    pub expires_at: i64,
+}
+
+pub type SessionId = Id<Session, String>;
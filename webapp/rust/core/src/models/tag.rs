@@ -1,7 +1,7 @@
 use fake::Dummy;
 use kubetsu::Id;
 
-#[derive(Debug, sqlx::FromRow, Dummy)]
+#[derive(Debug, Clone, sqlx::FromRow, Dummy)]
 pub struct Tag {
     pub id: Id<Self, i64>,
     pub name: TagName,
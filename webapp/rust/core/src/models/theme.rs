@@ -2,12 +2,37 @@ use crate::models::user::UserId;
 use fake::Dummy;
 use kubetsu::Id;
 
-#[derive(Debug, sqlx::FromRow, Dummy)]
+/// 既存行のデフォルト値 (10_schema.sql の DEFAULT と揃える)
+pub const DEFAULT_ACCENT_COLOR: &str = "#ff9900";
+pub const DEFAULT_FONT: &str = "default";
+pub const DEFAULT_CHAT_COLOR_SCHEME: &str = "default";
+
+/// 選択可能なフォント
+pub const FONTS: &[&str] = &["default", "sans-serif", "serif", "monospace", "rounded"];
+/// 選択可能なチャットの配色
+pub const CHAT_COLOR_SCHEMES: &[&str] = &["default", "light", "dark", "high-contrast"];
+
+#[derive(Debug, Clone, sqlx::FromRow, Dummy)]
 pub struct Theme {
     pub id: Id<Self, i64>,
     #[allow(unused)]
     pub user_id: UserId,
     pub dark_mode: bool,
+    pub accent_color: String,
+    pub background_image_url: Option<String>,
+    pub font: String,
+    pub chat_color_scheme: String,
 }
 
 pub type ThemeId = Id<Theme, i64>;
+
+/// テーマの部分更新。None の項目は変更しない
+/// background_image_url に空文字を指定すると背景画像を外す
+#[derive(Debug, Dummy, PartialEq, Clone, Default)]
+pub struct UpdateTheme {
+    pub dark_mode: Option<bool>,
+    pub accent_color: Option<String>,
+    pub background_image_url: Option<String>,
+    pub font: Option<String>,
+    pub chat_color_scheme: Option<String>,
+}
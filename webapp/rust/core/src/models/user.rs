@@ -1,7 +1,7 @@
 use fake::Dummy;
 use kubetsu::Id;
 
-#[derive(Debug, sqlx::FromRow, Dummy)]
+#[derive(Debug, Clone, sqlx::FromRow, Dummy)]
 pub struct User {
     pub id: Id<Self, i64>,
     pub name: UserName,
@@ -15,6 +15,13 @@ pub type UserId = Id<User, i64>;
 
 pub type UserName = Id<User, String>;
 
+/// プロフィールの部分更新。None の項目は変更しない
+#[derive(Debug, Dummy, PartialEq, Clone, Default)]
+pub struct UpdateUser {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+}
+
 #[derive(Debug, Dummy, PartialEq, Clone)]
 pub struct CreateUser {
     pub name: String,
@@ -0,0 +1,154 @@
+use crate::models::ng_word::{NgWord, NgWordMatchMode};
+use aho_corasick::AhoCorasick;
+use regex::{RegexSet, RegexSetBuilder};
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+#[cfg(test)]
+mod is_match;
+#[cfg(test)]
+mod validate;
+
+/// 配信者が登録する正規表現1つあたりのコンパイル後の大きさの上限
+pub const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, Error)]
+pub enum NgWordMatcherError {
+    #[error("automaton error: {0}")]
+    Automaton(#[from] aho_corasick::BuildError),
+    #[error("regex error: {0}")]
+    Regex(#[from] regex::Error),
+}
+
+/// ライブ配信ごとのNGワードをまとめて照合する
+/// 照合方法ごとに1つのオートマトンにまとめるので、登録されたNGワードの数によらず
+/// コメントの長さに比例する時間で判定できる
+#[derive(Debug, Clone, Default)]
+pub struct NgWordMatcher {
+    substring: Option<AhoCorasick>,
+    whole_word: Option<AhoCorasick>,
+    /// 小文字にしたNGワード
+    case_insensitive: Option<AhoCorasick>,
+    /// NFKC 正規化して小文字にしたNGワード
+    nfkc: Option<AhoCorasick>,
+    regex: Option<RegexSet>,
+}
+
+impl NgWordMatcher {
+    pub fn new<I, S>(words: I) -> Result<Self, NgWordMatcherError>
+    where
+        I: IntoIterator<Item = (NgWordMatchMode, S)>,
+        S: AsRef<str>,
+    {
+        let mut substring = Vec::new();
+        let mut whole_word = Vec::new();
+        let mut case_insensitive = Vec::new();
+        let mut nfkc = Vec::new();
+        let mut regex = Vec::new();
+        for (mode, word) in words {
+            let word = word.as_ref();
+            match mode {
+                NgWordMatchMode::Substring => substring.push(word.to_owned()),
+                NgWordMatchMode::WholeWord => whole_word.push(word.to_owned()),
+                NgWordMatchMode::CaseInsensitive => case_insensitive.push(word.to_lowercase()),
+                NgWordMatchMode::Nfkc => nfkc.push(normalize(word)),
+                NgWordMatchMode::Regex => regex.push(word.to_owned()),
+            }
+        }
+
+        Ok(Self {
+            substring: build_automaton(substring)?,
+            whole_word: build_automaton(whole_word)?,
+            case_insensitive: build_automaton(case_insensitive)?,
+            nfkc: build_automaton(nfkc)?,
+            regex: build_regex_set(regex)?,
+        })
+    }
+
+    pub fn from_ng_words(ng_words: &[NgWord]) -> Result<Self, NgWordMatcherError> {
+        Self::new(
+            ng_words
+                .iter()
+                .map(|ng_word| (ng_word.match_mode, &ng_word.word)),
+        )
+    }
+
+    /// 登録する前に、NGワードが照合に使えるか確かめる
+    pub fn validate(mode: NgWordMatchMode, word: &str) -> Result<(), NgWordMatcherError> {
+        Self::new([(mode, word)]).map(|_| ())
+    }
+
+    /// いずれかのNGワードに一致するか
+    /// LIKE CONCAT('%', word, '%') と同じく、空のNGワードはすべてのコメントに一致する
+    pub fn is_match(&self, text: &str) -> bool {
+        if let Some(automaton) = &self.substring {
+            if automaton.is_match(text) {
+                return true;
+            }
+        }
+        if let Some(automaton) = &self.whole_word {
+            let found = automaton
+                .find_overlapping_iter(text)
+                .any(|m| is_word_boundary(text, m.start(), m.end()));
+            if found {
+                return true;
+            }
+        }
+        if let Some(automaton) = &self.case_insensitive {
+            if automaton.is_match(&text.to_lowercase()) {
+                return true;
+            }
+        }
+        if let Some(automaton) = &self.nfkc {
+            if automaton.is_match(&normalize(text)) {
+                return true;
+            }
+        }
+        if let Some(regex) = &self.regex {
+            if regex.is_match(text) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+fn build_automaton(words: Vec<String>) -> Result<Option<AhoCorasick>, NgWordMatcherError> {
+    if words.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(AhoCorasick::new(words)?))
+}
+
+fn build_regex_set(patterns: Vec<String>) -> Result<Option<RegexSet>, NgWordMatcherError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    // 配信者が自由に登録できるので、コンパイル後の大きさを制限する
+    // regex クレートは入力長に対して線形時間で照合するので、照合時間は問題にならない
+    let set = RegexSetBuilder::new(&patterns)
+        .size_limit(REGEX_SIZE_LIMIT * patterns.len())
+        .build()?;
+
+    Ok(Some(set))
+}
+
+/// 全角と半角、合成済みと分解された文字などを同一視し、大文字と小文字も区別しない形にする
+fn normalize(text: &str) -> String {
+    text.nfkc().collect::<String>().to_lowercase()
+}
+
+/// 一致した範囲の前後が単語の文字でなければ単語として一致したとみなす
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
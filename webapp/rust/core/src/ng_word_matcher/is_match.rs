@@ -0,0 +1,172 @@
+use crate::models::ng_word::NgWordMatchMode;
+use crate::ng_word_matcher::NgWordMatcher;
+
+fn matcher(mode: NgWordMatchMode, words: &[&str]) -> NgWordMatcher {
+    NgWordMatcher::new(words.iter().map(|word| (mode, *word))).unwrap()
+}
+
+#[test]
+fn match_any_word() {
+    let matcher = matcher(NgWordMatchMode::Substring, &["spam", "ハム", "egg"]);
+
+    assert!(matcher.is_match("this is spam"));
+    assert!(matcher.is_match("生ハムメロン"));
+    assert!(matcher.is_match("eggs"));
+    assert!(!matcher.is_match("bacon"));
+}
+
+#[test]
+fn case_sensitive() {
+    // utf8mb4_bin と同じくバイト列で比較する
+    let matcher = matcher(NgWordMatchMode::Substring, &["spam"]);
+
+    assert!(!matcher.is_match("SPAM"));
+}
+
+#[test]
+fn no_words() {
+    let matcher = NgWordMatcher::new(Vec::<(NgWordMatchMode, String)>::new()).unwrap();
+
+    assert!(!matcher.is_match(""));
+    assert!(!matcher.is_match("spam"));
+}
+
+#[test]
+fn empty_word() {
+    let matcher = matcher(NgWordMatchMode::Substring, &[""]);
+
+    assert!(matcher.is_match(""));
+    assert!(matcher.is_match("anything"));
+}
+
+#[test]
+fn wildcard_is_literal() {
+    let matcher = matcher(NgWordMatchMode::Substring, &["a%b", "c_d"]);
+
+    assert!(matcher.is_match("xa%by"));
+    assert!(!matcher.is_match("axxb"));
+    assert!(!matcher.is_match("cxd"));
+}
+
+#[test]
+fn width_variants_matrix() {
+    use NgWordMatchMode::*;
+
+    // (照合方法, NGワード, コメント, 一致するか)
+    let cases = [
+        // 半角英字のNGワードに対する全角・大文字の表記揺れ
+        (Substring, "spam", "spam", true),
+        (Substring, "spam", "Spam", false),
+        (Substring, "spam", "ｓｐａｍ", false),
+        (CaseInsensitive, "spam", "Spam", true),
+        (CaseInsensitive, "spam", "ＳＰＡＭ", false),
+        (CaseInsensitive, "ｓｐａｍ", "ＳＰＡＭ", true),
+        (Nfkc, "spam", "Spam", true),
+        (Nfkc, "spam", "ｓｐａｍ", true),
+        (Nfkc, "spam", "ＳＰＡＭ", true),
+        (Nfkc, "ｓｐａｍ", "SPAM", true),
+        // 全角カタカナと半角カタカナ。半角の濁点・半濁点は別の文字になっている
+        (Substring, "スパム", "ｽﾊﾟﾑ", false),
+        (CaseInsensitive, "スパム", "ｽﾊﾟﾑ", false),
+        (Nfkc, "スパム", "ｽﾊﾟﾑ", true),
+        (Nfkc, "ｽﾊﾟﾑ", "スパム", true),
+        (Nfkc, "ガンダム", "ｶﾞﾝﾀﾞﾑ", true),
+        // ひらがなとカタカナは NFKC でも同一視しない
+        (Nfkc, "スパム", "すぱむ", false),
+        // 全角数字と記号、互換文字
+        (Substring, "123", "１２３", false),
+        (Nfkc, "123", "１２３", true),
+        (Nfkc, "!?", "！？", true),
+        (Nfkc, "平成", "㍻", true),
+        (Nfkc, "株式会社", "㈱", false),
+        (Nfkc, "(株)", "㈱", true),
+        // 全角スペースは半角スペースになる
+        (Substring, "no spam", "no　spam", false),
+        (Nfkc, "no spam", "no　spam", true),
+    ];
+
+    for (mode, word, comment, expected) in cases {
+        let matcher = matcher(mode, &[word]);
+        assert_eq!(
+            matcher.is_match(comment),
+            expected,
+            "mode={:?} word={:?} comment={:?}",
+            mode,
+            word,
+            comment
+        );
+    }
+}
+
+#[test]
+fn whole_word() {
+    let matcher = matcher(NgWordMatchMode::WholeWord, &["spam"]);
+
+    assert!(matcher.is_match("spam"));
+    assert!(matcher.is_match("I hate spam!"));
+    assert!(matcher.is_match("スパム spam スパム"));
+    assert!(!matcher.is_match("spammer"));
+    assert!(!matcher.is_match("antispam"));
+    assert!(!matcher.is_match("spam_bot"));
+    assert!(!matcher.is_match("SPAM"));
+}
+
+#[test]
+fn whole_word_japanese() {
+    // かなや漢字も単語の文字なので、区切りが無い日本語の文中では一致しない
+    let matcher = matcher(NgWordMatchMode::WholeWord, &["スパム"]);
+
+    assert!(matcher.is_match("スパム、やめて"));
+    assert!(matcher.is_match("「スパム」"));
+    assert!(!matcher.is_match("スパムです"));
+}
+
+#[test]
+fn whole_word_later_occurrence() {
+    // 先に見つかった位置が単語の途中でも、後ろの位置で一致すればよい
+    let matcher = matcher(NgWordMatchMode::WholeWord, &["ab", "abab"]);
+
+    assert!(matcher.is_match("ababx ab"));
+    assert!(matcher.is_match("abab"));
+    assert!(!matcher.is_match("ababab"));
+}
+
+#[test]
+fn regex() {
+    let matcher = matcher(NgWordMatchMode::Regex, &["^buy\\s+now", "(?i)s[p5]am"]);
+
+    assert!(matcher.is_match("buy   now!"));
+    assert!(!matcher.is_match("don't buy now"));
+    assert!(matcher.is_match("S5AM"));
+    assert!(!matcher.is_match("scam"));
+}
+
+#[test]
+fn regex_japanese() {
+    let matcher = matcher(NgWordMatchMode::Regex, &["[ｦ-ﾟ]{3,}", "w{3,}|ｗ{3,}"]);
+
+    assert!(matcher.is_match("ｽﾊﾟﾑ"));
+    assert!(matcher.is_match("草ｗｗｗ"));
+    assert!(!matcher.is_match("スパム"));
+    assert!(!matcher.is_match("ww"));
+}
+
+#[test]
+fn mixed_modes() {
+    let matcher = NgWordMatcher::new([
+        (NgWordMatchMode::Substring, "egg"),
+        (NgWordMatchMode::WholeWord, "ham"),
+        (NgWordMatchMode::CaseInsensitive, "bacon"),
+        (NgWordMatchMode::Nfkc, "スパム"),
+        (NgWordMatchMode::Regex, "^[0-9]+$"),
+    ])
+    .unwrap();
+
+    assert!(matcher.is_match("eggs"));
+    assert!(matcher.is_match("ham and cheese"));
+    assert!(!matcher.is_match("hamburger"));
+    assert!(matcher.is_match("BACON"));
+    assert!(matcher.is_match("ｽﾊﾟﾑ"));
+    assert!(matcher.is_match("12345"));
+    assert!(!matcher.is_match("12345 yen"));
+}
@@ -0,0 +1,31 @@
+use crate::models::ng_word::NgWordMatchMode;
+use crate::ng_word_matcher::{NgWordMatcher, NgWordMatcherError};
+
+#[test]
+fn literal_modes_accept_any_word() {
+    for mode in [
+        NgWordMatchMode::Substring,
+        NgWordMatchMode::WholeWord,
+        NgWordMatchMode::CaseInsensitive,
+        NgWordMatchMode::Nfkc,
+    ] {
+        assert!(NgWordMatcher::validate(mode, "(unclosed").is_ok());
+    }
+}
+
+#[test]
+fn valid_regex() {
+    assert!(NgWordMatcher::validate(NgWordMatchMode::Regex, "^s[p5]am$").is_ok());
+}
+
+#[test]
+fn invalid_regex() {
+    let result = NgWordMatcher::validate(NgWordMatchMode::Regex, "(unclosed");
+    assert!(matches!(result, Err(NgWordMatcherError::Regex(_))));
+}
+
+#[test]
+fn too_large_regex() {
+    let result = NgWordMatcher::validate(NgWordMatchMode::Regex, "\\w{1000}{1000}");
+    assert!(matches!(result, Err(NgWordMatcherError::Regex(_))));
+}
@@ -0,0 +1,16 @@
+use std::io;
+use thiserror::Error;
+
+pub mod password_reset_notifier;
+
+#[derive(Debug, Error)]
+pub enum NotifierError {
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("notifier config error: {0}")]
+    ConfigError(String),
+    #[error("test error")]
+    TestError,
+}
+
+pub type NotifierResult<T> = Result<T, NotifierError>;
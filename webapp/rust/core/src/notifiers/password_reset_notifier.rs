@@ -0,0 +1,16 @@
+use crate::models::user::User;
+use crate::notifiers::NotifierResult;
+use async_trait::async_trait;
+
+/// パスワード再設定トークンをユーザーに届ける
+#[cfg_attr(any(feature = "test", test), mockall::automock)]
+#[async_trait]
+pub trait PasswordResetNotifier {
+    async fn notify(&self, user: &User, token: &str, expires_at: i64) -> NotifierResult<()>;
+}
+
+pub trait HavePasswordResetNotifier {
+    type Notifier: Sync + PasswordResetNotifier;
+
+    fn password_reset_notifier(&self) -> &Self::Notifier;
+}
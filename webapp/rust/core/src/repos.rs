@@ -1,6 +1,7 @@
 use bcrypt::BcryptError;
 use thiserror::Error;
 
+pub mod account_repository;
 pub mod icon_repository;
 pub mod livestream_comment_report_repository;
 pub mod livestream_comment_repository;
@@ -9,6 +10,7 @@ pub mod livestream_tag_repository;
 pub mod livestream_viewers_history_repository;
 pub mod manager;
 pub mod ng_word_repository;
+pub mod password_reset_token_repository;
 pub mod reaction_repository;
 pub mod reservation_slot_repository;
 pub mod tag_repository;
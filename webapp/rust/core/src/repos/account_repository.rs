@@ -0,0 +1,19 @@
+use crate::db::DBConn;
+use crate::models::user::UserId;
+use crate::repos::Result;
+use async_trait::async_trait;
+
+/// 退会処理のためのリポジトリ
+/// 外部キーが無いので、ユーザーに紐づく行をテーブルごとに削除する
+#[cfg_attr(any(feature = "test", test), mockall::automock)]
+#[async_trait]
+pub trait AccountRepository {
+    /// ユーザー本人と、そのユーザーが作った・そのユーザーの配信に付いたデータを全て削除する
+    async fn delete_all_by_user_id(&self, conn: &mut DBConn, user_id: &UserId) -> Result<()>;
+}
+
+pub trait HaveAccountRepository {
+    type Repo: AccountRepository;
+
+    fn account_repo(&self) -> &Self::Repo;
+}
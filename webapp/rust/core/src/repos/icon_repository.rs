@@ -1,5 +1,7 @@
 use crate::db::DBConn;
-use crate::models::icon::CreateIcon;
+use crate::models::icon::{
+    CreateIcon, CreateIconThumbnail, Icon, IconBlob, IconHash, IconId, StoredIconImage,
+};
 use crate::models::user::UserId;
 use crate::repos::Result;
 use async_trait::async_trait;
@@ -10,11 +12,58 @@ pub trait IconRepository {
         &self,
         conn: &mut DBConn,
         user_id: &UserId,
-    ) -> Result<Option<Vec<u8>>>;
+    ) -> Result<Option<StoredIconImage>>;
+
+    async fn find_thumbnail_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+        size: u32,
+    ) -> Result<Option<StoredIconImage>>;
+
+    /// 画像をDBに保存しているアイコンを取得する
+    async fn find_blobs_limit(&self, conn: &mut DBConn, limit: i64) -> Result<Vec<IconBlob>>;
+
+    /// 画像をDBに保存しているサムネイルを取得する
+    async fn find_thumbnail_blobs_limit(
+        &self,
+        conn: &mut DBConn,
+        limit: i64,
+    ) -> Result<Vec<IconBlob>>;
+
+    /// 外部ストレージに移した画像をDBから消す
+    async fn clear_blob(&self, conn: &mut DBConn, id: i64, hash: &str) -> Result<()>;
+
+    async fn clear_thumbnail_blob(&self, conn: &mut DBConn, id: i64, hash: &str) -> Result<()>;
+
+    async fn find_many_by_user_ids(
+        &self,
+        conn: &mut DBConn,
+        user_ids: &[UserId],
+    ) -> Result<Vec<Icon>>;
+
+    async fn find_many_hashes_by_user_ids(
+        &self,
+        conn: &mut DBConn,
+        user_ids: &[UserId],
+    ) -> Result<Vec<IconHash>>;
+
+    /// ハッシュが空のまま保存されたアイコンにハッシュを設定する
+    /// 先に別のリクエストが設定していれば何もしない
+    async fn update_hash_if_empty(&self, conn: &mut DBConn, id: &IconId, hash: &str) -> Result<()>;
 
     async fn create(&self, conn: &mut DBConn, icon: &CreateIcon) -> Result<i64>;
 
+    async fn create_thumbnail(
+        &self,
+        conn: &mut DBConn,
+        thumbnail: &CreateIconThumbnail,
+    ) -> Result<()>;
+
     async fn delete_by_user_id(&self, conn: &mut DBConn, user_id: &UserId) -> Result<()>;
+
+    async fn delete_thumbnails_by_user_id(&self, conn: &mut DBConn, user_id: &UserId)
+        -> Result<()>;
 }
 
 pub trait HaveIconRepository {
@@ -1,4 +1,5 @@
 use crate::db::DBConn;
+use crate::models::cursor::Cursor;
 use crate::models::livestream::LivestreamId;
 use crate::models::livestream_comment::{
     CreateLivestreamComment, LivestreamComment, LivestreamCommentId,
@@ -16,12 +17,13 @@ pub trait LivestreamCommentRepository {
         comment: &CreateLivestreamComment,
     ) -> Result<LivestreamCommentId>;
 
-    async fn remove_if_match_ng_word(
+    /// 指定したライブ配信のコメントだけを削除し、削除した件数を返す
+    async fn delete_many_by_livestream_id(
         &self,
         conn: &mut DBConn,
-        comment: &LivestreamComment,
-        ng_word: &str,
-    ) -> Result<()>;
+        livestream_id: &LivestreamId,
+        comment_ids: &[LivestreamCommentId],
+    ) -> Result<u64>;
 
     async fn find(
         &self,
@@ -29,7 +31,11 @@ pub trait LivestreamCommentRepository {
         comment_id: &LivestreamCommentId,
     ) -> Result<Option<LivestreamComment>>;
 
-    async fn find_all(&self, conn: &mut DBConn) -> Result<Vec<LivestreamComment>>;
+    async fn find_many(
+        &self,
+        conn: &mut DBConn,
+        comment_ids: &[LivestreamCommentId],
+    ) -> Result<Vec<LivestreamComment>>;
 
     async fn find_all_by_livestream_id(
         &self,
@@ -50,6 +56,31 @@ pub trait LivestreamCommentRepository {
         limit: i64,
     ) -> Result<Vec<LivestreamComment>>;
 
+    /// id の昇順に limit 件まで返す。少しずつ読み進めるときに使う
+    async fn find_all_by_livestream_id_after_id_limit(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        comment_id: &LivestreamCommentId,
+        limit: i64,
+    ) -> Result<Vec<LivestreamComment>>;
+
+    async fn find_all_by_livestream_id_before_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> Result<Vec<LivestreamComment>>;
+
+    async fn find_all_by_livestream_id_after_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> Result<Vec<LivestreamComment>>;
+
     async fn get_sum_tip(&self, conn: &mut DBConn) -> Result<i64>;
 
     async fn get_sum_tip_of_livestream_id(
@@ -16,6 +16,18 @@ pub trait LivestreamRepository {
         conn: &mut DBConn,
         limit: i64,
     ) -> Result<Vec<Livestream>>;
+    async fn find_all_by_id_lt_order_by_id_desc_limit(
+        &self,
+        conn: &mut DBConn,
+        id: &LivestreamId,
+        limit: i64,
+    ) -> Result<Vec<Livestream>>;
+    async fn find_all_by_id_gt_order_by_id_asc_limit(
+        &self,
+        conn: &mut DBConn,
+        id: &LivestreamId,
+        limit: i64,
+    ) -> Result<Vec<Livestream>>;
 
     async fn find_all_by_user_id(
         &self,
@@ -23,6 +35,7 @@ pub trait LivestreamRepository {
         user_id: &UserId,
     ) -> Result<Vec<Livestream>>;
     async fn find(&self, conn: &mut DBConn, id: &LivestreamId) -> Result<Option<Livestream>>;
+    async fn find_many(&self, conn: &mut DBConn, ids: &[LivestreamId]) -> Result<Vec<Livestream>>;
 
     async fn exist_by_id_and_user_id(
         &self,
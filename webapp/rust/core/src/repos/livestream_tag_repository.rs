@@ -1,4 +1,5 @@
 use crate::db::DBConn;
+use crate::models::cursor::PageCursor;
 use crate::models::livestream::LivestreamId;
 use crate::models::livestream_tag::LivestreamTag;
 use crate::models::tag::TagId;
@@ -21,11 +22,26 @@ pub trait LivestreamTagRepository {
         livestream_id: &LivestreamId,
     ) -> Result<Vec<LivestreamTag>>;
 
+    async fn find_many_by_livestream_ids(
+        &self,
+        conn: &mut DBConn,
+        livestream_ids: &[LivestreamId],
+    ) -> Result<Vec<LivestreamTag>>;
+
     async fn find_all_by_tag_ids(
         &self,
         conn: &mut DBConn,
         tag_ids: &[TagId],
     ) -> Result<Vec<LivestreamTag>>;
+
+    /// タグが付いたライブ配信のIDを新しい順に limit 件返す。同じ配信は1度だけ数える
+    async fn find_livestream_ids_page_by_tag_ids(
+        &self,
+        conn: &mut DBConn,
+        tag_ids: &[TagId],
+        cursor: Option<PageCursor<LivestreamId>>,
+        limit: i64,
+    ) -> Result<Vec<LivestreamId>>;
 }
 
 pub trait HaveLivestreamTagRepository {
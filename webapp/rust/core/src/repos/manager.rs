@@ -1,11 +1,13 @@
 use crate::commands::pdnsutil_command::HavePDNSUtilCommand;
 use crate::db::HaveDBPool;
+use crate::repos::account_repository::HaveAccountRepository;
 use crate::repos::livestream_comment_report_repository::HaveLivestreamCommentReportRepository;
 use crate::repos::livestream_comment_repository::HaveLivestreamCommentRepository;
 use crate::repos::livestream_repository::HaveLivestreamRepository;
 use crate::repos::livestream_tag_repository::HaveLivestreamTagRepository;
 use crate::repos::livestream_viewers_history_repository::HaveLivestreamViewersHistoryRepository;
 use crate::repos::ng_word_repository::HaveNgWordRepository;
+use crate::repos::password_reset_token_repository::HavePasswordResetTokenRepository;
 use crate::repos::reaction_repository::HaveReactionRepository;
 use crate::repos::reservation_slot_repository::HaveReservationSlotRepository;
 use crate::repos::tag_repository::HaveTagRepository;
@@ -15,12 +17,14 @@ use crate::repos::user_repository::HaveUserRepository;
 pub trait RepositoryManager:
     Sync
     + HaveDBPool
+    + HaveAccountRepository
     + HaveLivestreamCommentReportRepository
     + HaveLivestreamCommentRepository
     + HaveLivestreamRepository
     + HaveLivestreamTagRepository
     + HaveLivestreamViewersHistoryRepository
     + HaveNgWordRepository
+    + HavePasswordResetTokenRepository
     + HaveReactionRepository
     + HaveReservationSlotRepository
     + HaveTagRepository
@@ -32,8 +36,21 @@ pub trait RepositoryManager:
 
 #[cfg(test)]
 pub mod tests {
-    use crate::commands::pdnsutil_command::{HavePDNSUtilCommand, MockPDNSUtilCommand};
+    use crate::caches::{
+        HaveIconHashCache, HaveNgWordMatcherCache, HaveThemeCache, HaveUserCache, InMemoryCache,
+        NoopCache,
+    };
+    use crate::commands::pdnsutil_command::{
+        HavePDNSUtilCommand, HaveSubdomainRecordConfig, MockPDNSUtilCommand, RecordType,
+        SubdomainRecordConfig,
+    };
     use crate::db::{DBPool, HaveDBPool};
+    use crate::models::livestream::LivestreamId;
+    use crate::ng_word_matcher::NgWordMatcher;
+    use crate::notifiers::password_reset_notifier::{
+        HavePasswordResetNotifier, MockPasswordResetNotifier,
+    };
+    use crate::repos::account_repository::{HaveAccountRepository, MockAccountRepository};
     use crate::repos::livestream_comment_report_repository::{
         HaveLivestreamCommentReportRepository, MockLivestreamCommentReportRepository,
     };
@@ -49,6 +66,9 @@ pub mod tests {
     };
     use crate::repos::manager::RepositoryManager;
     use crate::repos::ng_word_repository::{HaveNgWordRepository, MockNgWordRepository};
+    use crate::repos::password_reset_token_repository::{
+        HavePasswordResetTokenRepository, MockPasswordResetTokenRepository,
+    };
     use crate::repos::reaction_repository::{HaveReactionRepository, MockReactionRepository};
     use crate::repos::reservation_slot_repository::{
         HaveReservationSlotRepository, MockReservationSlotRepository,
@@ -56,40 +76,74 @@ pub mod tests {
     use crate::repos::tag_repository::{HaveTagRepository, MockTagRepository};
     use crate::repos::theme_repository::{HaveThemeRepository, MockThemeRepository};
     use crate::repos::user_repository::{HaveUserRepository, MockUserRepository};
+    use crate::services::account_service::AccountServiceImpl;
+    use crate::services::dns_record_service::DnsRecordServiceImpl;
+    use crate::services::livestream_comment_service::LivestreamCommentServiceImpl;
+    use crate::services::password_service::PasswordServiceImpl;
     use crate::services::user_service::UserServiceImpl;
+    use crate::storages::ng_word_purge_job_store::{
+        HaveNgWordPurgeJobStore, InMemoryNgWordPurgeJobStore,
+    };
+    use crate::storages::session_store::{HaveSessionStore, InMemorySessionStore};
+    use crate::validation::username::{HaveUsernamePolicy, UsernamePolicy};
+    use std::sync::Arc;
 
     pub struct MockRepositoryManager {
         db_pool: DBPool,
+        pub mock_account_repo: MockAccountRepository,
         pub mock_livestream_comment_report_repo: MockLivestreamCommentReportRepository,
         pub mock_livestream_comment_repo: MockLivestreamCommentRepository,
         pub mock_livestream_repo: MockLivestreamRepository,
         pub mock_livestream_tag_repo: MockLivestreamTagRepository,
         pub mock_livestream_viewers_history_repo: MockLivestreamViewersHistoryRepository,
         pub mock_ng_word_repo: MockNgWordRepository,
+        pub mock_password_reset_token_repo: MockPasswordResetTokenRepository,
         pub mock_reaction_repo: MockReactionRepository,
         pub mock_reservation_slot_repo: MockReservationSlotRepository,
         pub mock_tag_repo: MockTagRepository,
         pub mock_theme_repo: MockThemeRepository,
         pub mock_user_repo: MockUserRepository,
         pub mock_pdns_util_command: MockPDNSUtilCommand,
+        pub mock_password_reset_notifier: MockPasswordResetNotifier,
+        pub session_store: InMemorySessionStore,
+        pub ng_word_purge_job_store: InMemoryNgWordPurgeJobStore,
+        pub username_policy: UsernamePolicy,
+        pub subdomain_record: SubdomainRecordConfig,
+        pub ng_word_matcher_cache: InMemoryCache<LivestreamId, Arc<NgWordMatcher>>,
+        cache: NoopCache,
     }
 
     impl MockRepositoryManager {
         pub fn new(db_pool: DBPool) -> Self {
             Self {
                 db_pool,
+                mock_account_repo: Default::default(),
                 mock_livestream_comment_report_repo: Default::default(),
                 mock_livestream_comment_repo: Default::default(),
                 mock_livestream_repo: Default::default(),
                 mock_livestream_tag_repo: Default::default(),
                 mock_livestream_viewers_history_repo: Default::default(),
                 mock_ng_word_repo: Default::default(),
+                mock_password_reset_token_repo: Default::default(),
                 mock_reaction_repo: Default::default(),
                 mock_reservation_slot_repo: Default::default(),
                 mock_tag_repo: Default::default(),
                 mock_theme_repo: Default::default(),
                 mock_user_repo: Default::default(),
                 mock_pdns_util_command: Default::default(),
+                mock_password_reset_notifier: Default::default(),
+                session_store: Default::default(),
+                ng_word_purge_job_store: Default::default(),
+                username_policy: Default::default(),
+                subdomain_record: SubdomainRecordConfig::new(
+                    "u.isucon.dev",
+                    RecordType::A,
+                    0,
+                    "127.0.0.1",
+                )
+                .unwrap(),
+                ng_word_matcher_cache: Default::default(),
+                cache: NoopCache,
             }
         }
     }
@@ -100,6 +154,14 @@ pub mod tests {
         }
     }
 
+    impl HaveAccountRepository for MockRepositoryManager {
+        type Repo = MockAccountRepository;
+
+        fn account_repo(&self) -> &Self::Repo {
+            &self.mock_account_repo
+        }
+    }
+
     impl HaveLivestreamCommentReportRepository for MockRepositoryManager {
         type Repo = MockLivestreamCommentReportRepository;
 
@@ -196,6 +258,86 @@ pub mod tests {
         }
     }
 
+    impl HavePasswordResetTokenRepository for MockRepositoryManager {
+        type Repo = MockPasswordResetTokenRepository;
+
+        fn password_reset_token_repo(&self) -> &Self::Repo {
+            &self.mock_password_reset_token_repo
+        }
+    }
+
+    impl HavePasswordResetNotifier for MockRepositoryManager {
+        type Notifier = MockPasswordResetNotifier;
+
+        fn password_reset_notifier(&self) -> &Self::Notifier {
+            &self.mock_password_reset_notifier
+        }
+    }
+
+    impl HaveSessionStore for MockRepositoryManager {
+        type Store = InMemorySessionStore;
+
+        fn session_store(&self) -> &Self::Store {
+            &self.session_store
+        }
+    }
+
+    impl HaveNgWordPurgeJobStore for MockRepositoryManager {
+        type Store = InMemoryNgWordPurgeJobStore;
+
+        fn ng_word_purge_job_store(&self) -> &Self::Store {
+            &self.ng_word_purge_job_store
+        }
+    }
+
+    impl HaveUsernamePolicy for MockRepositoryManager {
+        fn username_policy(&self) -> &UsernamePolicy {
+            &self.username_policy
+        }
+    }
+
+    impl HaveSubdomainRecordConfig for MockRepositoryManager {
+        fn subdomain_record(&self) -> &SubdomainRecordConfig {
+            &self.subdomain_record
+        }
+    }
+
+    impl HaveUserCache for MockRepositoryManager {
+        type Cache = NoopCache;
+
+        fn user_cache(&self) -> &Self::Cache {
+            &self.cache
+        }
+    }
+
+    impl HaveThemeCache for MockRepositoryManager {
+        type Cache = NoopCache;
+
+        fn theme_cache(&self) -> &Self::Cache {
+            &self.cache
+        }
+    }
+
+    impl HaveIconHashCache for MockRepositoryManager {
+        type Cache = NoopCache;
+
+        fn icon_hash_cache(&self) -> &Self::Cache {
+            &self.cache
+        }
+    }
+
+    impl HaveNgWordMatcherCache for MockRepositoryManager {
+        type Cache = InMemoryCache<LivestreamId, Arc<NgWordMatcher>>;
+
+        fn ng_word_matcher_cache(&self) -> &Self::Cache {
+            &self.ng_word_matcher_cache
+        }
+    }
+
     impl RepositoryManager for MockRepositoryManager {}
     impl UserServiceImpl for MockRepositoryManager {}
+    impl AccountServiceImpl for MockRepositoryManager {}
+    impl PasswordServiceImpl for MockRepositoryManager {}
+    impl DnsRecordServiceImpl for MockRepositoryManager {}
+    impl LivestreamCommentServiceImpl for MockRepositoryManager {}
 }
@@ -0,0 +1,46 @@
+use crate::db::DBConn;
+use crate::models::password_reset_token::{
+    CreatePasswordResetToken, PasswordResetToken, PasswordResetTokenId,
+};
+use crate::models::user::UserId;
+use crate::repos::Result;
+use async_trait::async_trait;
+
+#[cfg_attr(any(feature = "test", test), mockall::automock)]
+#[async_trait]
+pub trait PasswordResetTokenRepository {
+    async fn create(
+        &self,
+        conn: &mut DBConn,
+        token: &CreatePasswordResetToken,
+    ) -> Result<PasswordResetTokenId>;
+
+    async fn find_by_token_hash(
+        &self,
+        conn: &mut DBConn,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>>;
+
+    /// 未使用のトークンに限り使用済みにする。使用済みにできたら true を返す
+    async fn mark_used(
+        &self,
+        conn: &mut DBConn,
+        id: &PasswordResetTokenId,
+        used_at: i64,
+    ) -> Result<bool>;
+
+    /// 最後に発行したトークン。使用済みのものも含む
+    async fn find_latest_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+    ) -> Result<Option<PasswordResetToken>>;
+
+    async fn delete_unused_by_user_id(&self, conn: &mut DBConn, user_id: &UserId) -> Result<()>;
+}
+
+pub trait HavePasswordResetTokenRepository {
+    type Repo: PasswordResetTokenRepository;
+
+    fn password_reset_token_repo(&self) -> &Self::Repo;
+}
@@ -1,4 +1,5 @@
 use crate::db::DBConn;
+use crate::models::cursor::Cursor;
 use crate::models::livestream::LivestreamId;
 use crate::models::reaction::{CreateReaction, Reaction, ReactionId};
 use crate::models::user::{UserId, UserName};
@@ -45,6 +46,22 @@ pub trait ReactionRepository {
         livestream_id: &LivestreamId,
         limit: i64,
     ) -> Result<Vec<Reaction>>;
+
+    async fn find_all_by_livestream_id_before_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> Result<Vec<Reaction>>;
+
+    async fn find_all_by_livestream_id_after_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> Result<Vec<Reaction>>;
 }
 
 pub trait HaveReactionRepository {
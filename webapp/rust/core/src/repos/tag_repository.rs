@@ -8,6 +8,7 @@ use async_trait::async_trait;
 pub trait TagRepository {
     async fn find(&self, conn: &mut DBConn, id: &TagId) -> Result<Tag>;
     async fn find_all(&self, conn: &mut DBConn) -> Result<Vec<Tag>>;
+    async fn find_many(&self, conn: &mut DBConn, ids: &[TagId]) -> Result<Vec<Tag>>;
 
     async fn find_ids_by_name(&self, conn: &mut DBConn, name: &TagName) -> Result<Vec<TagId>>;
 }
@@ -1,5 +1,5 @@
 use crate::db::DBConn;
-use crate::models::theme::Theme;
+use crate::models::theme::{Theme, UpdateTheme};
 use crate::models::user::UserId;
 use crate::repos::Result;
 use async_trait::async_trait;
@@ -8,7 +8,13 @@ use async_trait::async_trait;
 #[async_trait]
 pub trait ThemeRepository {
     async fn create(&self, conn: &mut DBConn, user_id: &UserId, dark_mode: bool) -> Result<()>;
+    async fn update(&self, conn: &mut DBConn, user_id: &UserId, theme: &UpdateTheme) -> Result<()>;
     async fn find_by_user_id(&self, conn: &mut DBConn, user_id: &UserId) -> Result<Theme>;
+    async fn find_many_by_user_ids(
+        &self,
+        conn: &mut DBConn,
+        user_ids: &[UserId],
+    ) -> Result<Vec<Theme>>;
 }
 
 pub trait HaveThemeRepository {
@@ -1,12 +1,18 @@
 use crate::db::DBConn;
-use crate::models::user::{CreateUser, User, UserId};
+use crate::models::user::{CreateUser, UpdateUser, User, UserId};
 use crate::repos::Result;
 use async_trait::async_trait;
 
 #[cfg_attr(any(feature = "test", test), mockall::automock)]
 #[async_trait]
 pub trait UserRepository {
-    async fn create(&self, conn: &mut DBConn, user: &CreateUser) -> Result<UserId>;
+    /// パスワードは hash_password でハッシュ化したものを渡す
+    async fn create(
+        &self,
+        conn: &mut DBConn,
+        user: &CreateUser,
+        hashed_password: &str,
+    ) -> Result<UserId>;
 
     fn hash_password(&self, password: &str) -> Result<String> {
         const BCRYPT_DEFAULT_COST: u32 = 4;
@@ -14,10 +20,22 @@ pub trait UserRepository {
         Ok(hashed_password)
     }
 
+    async fn update(&self, conn: &mut DBConn, id: &UserId, user: &UpdateUser) -> Result<()>;
+
+    async fn update_password(
+        &self,
+        conn: &mut DBConn,
+        id: &UserId,
+        hashed_password: &str,
+    ) -> Result<()>;
+
     async fn find(&self, conn: &mut DBConn, id: &UserId) -> Result<Option<User>>;
     async fn find_all(&self, conn: &mut DBConn) -> Result<Vec<User>>;
+    async fn find_many(&self, conn: &mut DBConn, ids: &[UserId]) -> Result<Vec<User>>;
     async fn find_id_by_name(&self, conn: &mut DBConn, name: &str) -> Result<Option<UserId>>;
     async fn find_by_name(&self, conn: &mut DBConn, name: &str) -> Result<Option<User>>;
+    /// 大文字小文字を区別せずに同じ名前のユーザーがいるか
+    async fn exists_by_name_ignore_case(&self, conn: &mut DBConn, name: &str) -> Result<bool>;
 }
 
 pub trait HaveUserRepository {
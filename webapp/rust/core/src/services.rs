@@ -1,7 +1,14 @@
 use crate::commands::CommandError;
+use crate::icon_image::IconImageError;
+use crate::ng_word_matcher::NgWordMatcherError;
+use crate::notifiers::NotifierError;
 use crate::repos::ReposError;
+use crate::storages::StorageError;
+use crate::validation::ValidationErrors;
 use thiserror::Error;
 
+pub mod account_service;
+pub mod dns_record_service;
 pub mod icon_service;
 pub mod initialize_service;
 pub mod livestream_comment_report_service;
@@ -10,9 +17,12 @@ pub mod livestream_service;
 pub mod livestream_statistics_service;
 pub mod livestream_tag_service;
 pub mod livestream_viewers_history_service;
+pub mod login_throttle_service;
 pub mod manager;
 pub mod ng_word_service;
+pub mod password_service;
 pub mod reaction_service;
+pub mod session_service;
 pub mod tag_service;
 pub mod theme_service;
 pub mod user_service;
@@ -24,16 +34,50 @@ pub enum ServiceError {
     NotFoundLivestream,
     #[error("livecomment not found")]
     NotFoundLivestreamComment,
+    #[error("user not found")]
+    NotFoundUser,
     #[error("this comment matched spam")]
     CommentMatchSpam,
     #[error("invalid reservation range")]
     InvalidReservationRange,
+    #[error("{0}")]
+    Validation(#[from] ValidationErrors),
+    #[error("username already taken")]
+    UsernameTaken,
+    #[error("{0}")]
+    InvalidUserProfile(&'static str),
+    #[error("{0}")]
+    InvalidTheme(&'static str),
+    #[error("{0}")]
+    InvalidPassword(&'static str),
+    #[error("current password is incorrect")]
+    IncorrectPassword,
+    #[error("invalid or expired password reset token")]
+    InvalidPasswordResetToken,
+    #[error("too many login attempts")]
+    TooManyLoginAttempts { retry_after: i64 },
+    #[error("failed to provision DNS record after {attempts} attempts: {detail}")]
+    DnsProvisioningFailed { attempts: u32, detail: String },
+    #[error("pdnsutil failed: {0}")]
+    PDNSUtilFailed(String),
+    #[error("{0}")]
+    InvalidIconImage(#[from] IconImageError),
     #[error("repos error: #{0}")]
     ReposError(#[from] ReposError),
     #[error("sqlx error: #{0}")]
     SqlxError(#[from] sqlx::Error),
+    #[error("bcrypt error: #{0}")]
+    Bcrypt(#[from] bcrypt::BcryptError),
+    #[error("notifier error: #{0}")]
+    NotifierError(#[from] NotifierError),
     #[error("command error: #{0}")]
     CommandError(#[from] CommandError),
+    #[error("ng word matcher error: #{0}")]
+    NgWordMatcherError(#[from] NgWordMatcherError),
+    #[error("storage error: #{0}")]
+    StorageError(#[from] StorageError),
+    #[error("join error: #{0}")]
+    JoinError(#[from] tokio::task::JoinError),
 }
 
 pub type ServiceResult<T> = Result<T, ServiceError>;
@@ -0,0 +1,81 @@
+use crate::caches::{Cache, HaveIconHashCache, HaveThemeCache, HaveUserCache};
+use crate::commands::pdnsutil_command::{HavePDNSUtilCommand, PDNSUtilCommand};
+use crate::db::HaveDBPool;
+use crate::models::user::UserId;
+use crate::repos::account_repository::{AccountRepository, HaveAccountRepository};
+use crate::repos::user_repository::{HaveUserRepository, UserRepository};
+use crate::services::{ServiceError, ServiceResult};
+use crate::validation::username::record_label;
+use async_trait::async_trait;
+
+#[cfg(test)]
+mod delete;
+
+#[async_trait]
+pub trait AccountService {
+    /// 退会。ユーザーのデータと全端末のセッションを消してから、DNSレコードを消す
+    async fn delete(&self, user_id: &UserId) -> ServiceResult<()>;
+}
+
+pub trait HaveAccountService {
+    type Service: AccountService;
+
+    fn account_service(&self) -> &Self::Service;
+}
+
+pub trait AccountServiceImpl:
+    Sync
+    + HaveDBPool
+    + HaveUserRepository
+    + HaveAccountRepository
+    + HavePDNSUtilCommand
+    + HaveUserCache
+    + HaveThemeCache
+    + HaveIconHashCache
+{
+}
+
+#[async_trait]
+impl<T: AccountServiceImpl> AccountService for T {
+    async fn delete(&self, user_id: &UserId) -> ServiceResult<()> {
+        let mut tx = self.get_db_pool().begin().await?;
+
+        let user = self
+            .user_repo()
+            .find(&mut tx, user_id)
+            .await?
+            .ok_or(ServiceError::NotFoundUser)?;
+
+        // セッションやログイン試行の記録も同じトランザクションで消す
+        self.account_repo()
+            .delete_all_by_user_id(&mut tx, user_id)
+            .await?;
+        tx.commit().await?;
+
+        self.user_cache().remove(user_id);
+        self.theme_cache().remove(user_id);
+        self.icon_hash_cache().remove(user_id);
+
+        // reconcile と同じレコード名を消す
+        remove_record(self, &record_label(user.name.inner())).await;
+
+        Ok(())
+    }
+}
+
+/// 退会はコミット済みなので取り消さない。消し損ねたレコードは reconcile で消せるようログに残す
+async fn remove_record<T: AccountServiceImpl>(service: &T, dns_label: &str) {
+    match service.pdnsutil_command().delete_record(dns_label).await {
+        Ok(output) if output.success => {}
+        Ok(output) => tracing::error!(
+            "failed to remove DNS record {} of deleted account: {}",
+            dns_label,
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(e) => tracing::error!(
+            "failed to remove DNS record {} of deleted account: {}",
+            dns_label,
+            e
+        ),
+    }
+}
@@ -0,0 +1,114 @@
+use crate::commands::CommandOutput;
+use crate::db::get_db_pool;
+use crate::models::user::{User, UserName};
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::account_service::AccountService;
+use crate::services::ServiceError;
+use fake::{Fake, Faker};
+
+fn command_output(success: bool) -> CommandOutput {
+    CommandOutput {
+        success,
+        stdout: Vec::new(),
+        stderr: Vec::new(),
+    }
+}
+
+async fn service() -> MockRepositoryManager {
+    let db_pool = get_db_pool().await.unwrap();
+    MockRepositoryManager::new(db_pool)
+}
+
+#[tokio::test]
+async fn not_found_user_case() {
+    let user: User = Faker.fake();
+    let mut service = service().await;
+    service
+        .mock_user_repo
+        .expect_find()
+        .returning(|_, _| Ok(None));
+    service
+        .mock_account_repo
+        .expect_delete_all_by_user_id()
+        .never();
+
+    let result = service.delete(&user.id).await;
+    assert!(matches!(result, Err(ServiceError::NotFoundUser)));
+}
+
+#[tokio::test]
+async fn pdnsutil_fail_case() {
+    let user: User = Faker.fake();
+    let mut service = service().await;
+    let got_user = user.clone();
+    service
+        .mock_user_repo
+        .expect_find()
+        .returning(move |_, _| Ok(Some(got_user.clone())));
+    service
+        .mock_account_repo
+        .expect_delete_all_by_user_id()
+        .times(1)
+        .returning(|_, _| Ok(()));
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .times(1)
+        .returning(|_| Ok(command_output(false)));
+
+    // 退会はコミット済みなので、DNSレコードを消せなくても成功にする
+    let result = service.delete(&user.id).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn deleted_case() {
+    let mut user: User = Faker.fake();
+    user.name = UserName::new("alice".to_owned());
+    let mut service = service().await;
+    let got_user = user.clone();
+    service
+        .mock_user_repo
+        .expect_find()
+        .returning(move |_, _| Ok(Some(got_user.clone())));
+    let uid = user.id.clone();
+    service
+        .mock_account_repo
+        .expect_delete_all_by_user_id()
+        .withf(move |_, id| id == &uid)
+        .times(1)
+        .returning(|_, _| Ok(()));
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .withf(|name| name == "alice")
+        .times(1)
+        .returning(|_| Ok(command_output(true)));
+
+    service.delete(&user.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn legacy_name_case() {
+    let mut user: User = Faker.fake();
+    user.name = UserName::new("Legacy_Name".to_owned());
+    let mut service = service().await;
+    let got_user = user.clone();
+    service
+        .mock_user_repo
+        .expect_find()
+        .returning(move |_, _| Ok(Some(got_user.clone())));
+    service
+        .mock_account_repo
+        .expect_delete_all_by_user_id()
+        .returning(|_, _| Ok(()));
+    // 検証導入前の名前は、reconcile と同じく小文字にしたレコード名を消す
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .withf(|name| name == "legacy_name")
+        .times(1)
+        .returning(|_| Ok(command_output(true)));
+
+    service.delete(&user.id).await.unwrap();
+}
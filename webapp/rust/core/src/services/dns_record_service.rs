@@ -0,0 +1,172 @@
+use crate::commands::pdnsutil_command::{
+    DnsRecord, HavePDNSUtilCommand, HaveSubdomainRecordConfig, PDNSUtilCommand,
+    SubdomainRecordConfig,
+};
+use crate::db::HaveDBPool;
+use crate::repos::user_repository::{HaveUserRepository, UserRepository};
+use crate::services::{ServiceError, ServiceResult};
+use crate::validation::username::{record_label, HaveUsernamePolicy, UsernamePolicy};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(test)]
+mod diff_records;
+#[cfg(test)]
+mod reconcile;
+
+/// users テーブルとゾーンのずれ。どちらもゾーンを除いたラベルで、昇順に並ぶ
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DnsRecordDiff {
+    /// ユーザーはいるがレコードがない
+    pub missing: Vec<String>,
+    /// レコードはあるがユーザーがいない
+    pub orphaned: Vec<String>,
+    /// ユーザーもレコードもあるが、レコードの内容が設定と違う
+    pub changed: Vec<String>,
+}
+
+impl DnsRecordDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.orphaned.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[async_trait]
+pub trait DnsRecordService {
+    /// users テーブルに合わせてサブドメインのレコードを追加・更新・削除し、差分を返す
+    /// dry_run の場合は差分を返すだけで何も変更しない
+    async fn reconcile(&self, dry_run: bool) -> ServiceResult<DnsRecordDiff>;
+}
+
+pub trait HaveDnsRecordService {
+    type Service: DnsRecordService;
+
+    fn dns_record_service(&self) -> &Self::Service;
+}
+
+pub trait DnsRecordServiceImpl:
+    Sync
+    + HaveDBPool
+    + HaveUserRepository
+    + HavePDNSUtilCommand
+    + HaveSubdomainRecordConfig
+    + HaveUsernamePolicy
+{
+}
+
+#[async_trait]
+impl<T: DnsRecordServiceImpl> DnsRecordService for T {
+    async fn reconcile(&self, dry_run: bool) -> ServiceResult<DnsRecordDiff> {
+        let users = {
+            let mut conn = self.get_db_pool().acquire().await?;
+            self.user_repo().find_all(&mut conn).await?
+        };
+        let labels = users.iter().map(|user| record_label(user.name.inner()));
+        let records = self.pdnsutil_command().list_records().await?;
+
+        let diff = diff_records(
+            labels,
+            &records,
+            self.subdomain_record(),
+            self.username_policy(),
+        );
+        if dry_run {
+            return Ok(diff);
+        }
+
+        // 途中で失敗しても、再実行すれば残りの差分だけを処理する
+        let content = self.subdomain_record().content();
+        for label in &diff.missing {
+            let output = self.pdnsutil_command().add_record(label, content).await?;
+            if !output.success {
+                return Err(ServiceError::PDNSUtilFailed(format!(
+                    "{}: {}",
+                    label,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+        for label in &diff.changed {
+            let output = self
+                .pdnsutil_command()
+                .update_record(label, content)
+                .await?;
+            if !output.success {
+                return Err(ServiceError::PDNSUtilFailed(format!(
+                    "{}: {}",
+                    label,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+        for label in &diff.orphaned {
+            let output = self.pdnsutil_command().delete_record(label).await?;
+            if !output.success {
+                return Err(ServiceError::PDNSUtilFailed(format!(
+                    "{}: {}",
+                    label,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+
+        Ok(diff)
+    }
+}
+
+/// ユーザーのラベルとゾーンのレコードを突き合わせる
+/// 設定した種類のレコードで、ゾーン直下の名前だけを対象にする
+/// 予約名 (pipe や ns1 など) はアプリ自身が使うので、ユーザーがいなくても消さない
+/// 同じ名前のレコードが設定の内容ちょうど1件でなければ、内容が違うとみなす
+pub fn diff_records<I>(
+    labels: I,
+    records: &[DnsRecord],
+    config: &SubdomainRecordConfig,
+    policy: &UsernamePolicy,
+) -> DnsRecordDiff
+where
+    I: IntoIterator<Item = String>,
+{
+    let suffix = format!(".{}", config.zone());
+    let record_type = config.record_type().as_str();
+    let mut existing: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for record in records
+        .iter()
+        .filter(|record| record.record_type.eq_ignore_ascii_case(record_type))
+    {
+        let name = record.name.trim_end_matches('.').to_ascii_lowercase();
+        let Some(label) = name.strip_suffix(&suffix) else {
+            continue;
+        };
+        if label.is_empty() || label.contains('.') {
+            continue;
+        }
+        existing
+            .entry(label.to_owned())
+            .or_default()
+            .push(&record.content);
+    }
+    let expected: BTreeSet<String> = labels.into_iter().collect();
+
+    // CNAME の内容は大文字小文字と末尾のドットの有無を区別しない
+    let content = config.content().trim_end_matches('.');
+    let is_expected = |contents: &[&str]| matches!(contents, [c] if c.trim_end_matches('.').eq_ignore_ascii_case(content));
+
+    DnsRecordDiff {
+        missing: expected
+            .iter()
+            .filter(|label| !existing.contains_key(*label))
+            .cloned()
+            .collect(),
+        orphaned: existing
+            .keys()
+            .filter(|label| !expected.contains(*label) && !policy.is_reserved(label))
+            .cloned()
+            .collect(),
+        changed: existing
+            .iter()
+            .filter(|(label, contents)| expected.contains(*label) && !is_expected(contents))
+            .map(|(label, _)| label.clone())
+            .collect(),
+    }
+}
@@ -0,0 +1,122 @@
+use crate::commands::pdnsutil_command::{DnsRecord, RecordType, SubdomainRecordConfig};
+use crate::services::dns_record_service::{diff_records, DnsRecordDiff};
+use crate::validation::username::UsernamePolicy;
+
+fn record(name: &str, record_type: &str) -> DnsRecord {
+    record_with_content(name, record_type, "127.0.0.1")
+}
+
+fn record_with_content(name: &str, record_type: &str, content: &str) -> DnsRecord {
+    DnsRecord {
+        name: name.to_owned(),
+        record_type: record_type.to_owned(),
+        ttl: 0,
+        content: content.to_owned(),
+    }
+}
+
+fn config() -> SubdomainRecordConfig {
+    SubdomainRecordConfig::new("u.isucon.dev", RecordType::A, 0, "127.0.0.1").unwrap()
+}
+
+#[test]
+fn diff() {
+    let records = vec![
+        record("u.isucon.dev", "A"),
+        record("u.isucon.dev", "SOA"),
+        record("ns1.u.isucon.dev", "A"),
+        record("pipe.u.isucon.dev", "A"),
+        record("alice.u.isucon.dev", "A"),
+        record("Carol.u.isucon.dev.", "A"),
+        record("dave.u.isucon.dev", "A"),
+    ];
+    let labels = ["alice", "bob", "carol"].map(str::to_owned);
+
+    let diff = diff_records(labels, &records, &config(), &UsernamePolicy::default());
+    assert_eq!(
+        diff,
+        DnsRecordDiff {
+            missing: vec!["bob".to_owned()],
+            orphaned: vec!["dave".to_owned()],
+            changed: Vec::new(),
+        }
+    );
+}
+
+#[test]
+fn ignore_other_types_and_zones() {
+    let records = vec![
+        record("alice.u.isucon.dev", "AAAA"),
+        record("bob.example.com", "A"),
+        record("www.bob.u.isucon.dev", "A"),
+    ];
+
+    let diff = diff_records(
+        ["alice".to_owned()],
+        &records,
+        &config(),
+        &UsernamePolicy::default(),
+    );
+    assert_eq!(
+        diff,
+        DnsRecordDiff {
+            missing: vec!["alice".to_owned()],
+            orphaned: Vec::new(),
+            changed: Vec::new(),
+        }
+    );
+}
+
+#[test]
+fn in_sync() {
+    let records = vec![record("alice.u.isucon.dev", "A")];
+
+    let diff = diff_records(
+        ["alice".to_owned()],
+        &records,
+        &config(),
+        &UsernamePolicy::default(),
+    );
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn changed_content() {
+    let records = vec![
+        record_with_content("alice.u.isucon.dev", "A", "127.0.0.2"),
+        record("bob.u.isucon.dev", "A"),
+        record_with_content("bob.u.isucon.dev", "A", "127.0.0.3"),
+        record("carol.u.isucon.dev", "A"),
+        record_with_content("dave.u.isucon.dev", "A", "127.0.0.2"),
+    ];
+    let labels = ["alice", "bob", "carol"].map(str::to_owned);
+
+    let diff = diff_records(labels, &records, &config(), &UsernamePolicy::default());
+    assert_eq!(
+        diff,
+        DnsRecordDiff {
+            missing: Vec::new(),
+            orphaned: vec!["dave".to_owned()],
+            changed: vec!["alice".to_owned(), "bob".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn cname_content_is_case_insensitive() {
+    let config =
+        SubdomainRecordConfig::new("u.isucon.dev", RecordType::Cname, 0, "pipe.example.com")
+            .unwrap();
+    let records = vec![
+        record_with_content("alice.u.isucon.dev", "CNAME", "Pipe.Example.com."),
+        record_with_content("bob.u.isucon.dev", "CNAME", "pipe.example.com"),
+    ];
+
+    let diff = diff_records(
+        ["alice".to_owned(), "bob".to_owned()],
+        &records,
+        &config,
+        &UsernamePolicy::default(),
+    );
+    assert!(diff.is_empty());
+}
@@ -0,0 +1,144 @@
+use crate::commands::pdnsutil_command::DnsRecord;
+use crate::commands::CommandOutput;
+use crate::db::get_db_pool;
+use crate::models::user::{User, UserName};
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::dns_record_service::{DnsRecordDiff, DnsRecordService};
+use crate::services::ServiceError;
+use fake::{Fake, Faker};
+
+fn command_output(success: bool) -> CommandOutput {
+    CommandOutput {
+        success,
+        stdout: Vec::new(),
+        stderr: Vec::new(),
+    }
+}
+
+async fn service_with_drift() -> MockRepositoryManager {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut service = MockRepositoryManager::new(db_pool);
+    service.mock_user_repo.expect_find_all().returning(|_| {
+        Ok(["Alice", "bücher", "carol"]
+            .into_iter()
+            .map(|name| {
+                let mut user: User = Faker.fake();
+                user.name = UserName::new(name.to_owned());
+                user
+            })
+            .collect())
+    });
+    service
+        .mock_pdns_util_command
+        .expect_list_records()
+        .returning(|| {
+            // carol のレコードは古いアドレスを指している
+            Ok([
+                ("pipe", "127.0.0.1"),
+                ("alice", "127.0.0.1"),
+                ("carol", "127.0.0.2"),
+                ("dave", "127.0.0.1"),
+            ]
+            .into_iter()
+            .map(|(label, content)| DnsRecord {
+                name: format!("{}.u.isucon.dev", label),
+                record_type: "A".to_owned(),
+                ttl: 0,
+                content: content.to_owned(),
+            })
+            .collect())
+        });
+
+    service
+}
+
+fn expected_diff() -> DnsRecordDiff {
+    DnsRecordDiff {
+        missing: vec!["xn--bcher-kva".to_owned()],
+        orphaned: vec!["dave".to_owned()],
+        changed: vec!["carol".to_owned()],
+    }
+}
+
+#[tokio::test]
+async fn dry_run_case() {
+    let mut service = service_with_drift().await;
+    service.mock_pdns_util_command.expect_add_record().never();
+    service
+        .mock_pdns_util_command
+        .expect_update_record()
+        .never();
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .never();
+
+    let diff = service.reconcile(true).await.unwrap();
+    assert_eq!(diff, expected_diff());
+}
+
+#[tokio::test]
+async fn reconcile_case() {
+    let mut service = service_with_drift().await;
+    service
+        .mock_pdns_util_command
+        .expect_add_record()
+        .withf(|name, content| name == "xn--bcher-kva" && content == "127.0.0.1")
+        .times(1)
+        .returning(|_, _| Ok(command_output(true)));
+    service
+        .mock_pdns_util_command
+        .expect_update_record()
+        .withf(|name, content| name == "carol" && content == "127.0.0.1")
+        .times(1)
+        .returning(|_, _| Ok(command_output(true)));
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .withf(|name| name == "dave")
+        .times(1)
+        .returning(|_| Ok(command_output(true)));
+
+    let diff = service.reconcile(false).await.unwrap();
+    assert_eq!(diff, expected_diff());
+}
+
+#[tokio::test]
+async fn add_record_fail_case() {
+    let mut service = service_with_drift().await;
+    service
+        .mock_pdns_util_command
+        .expect_add_record()
+        .returning(|_, _| Ok(command_output(false)));
+    service
+        .mock_pdns_util_command
+        .expect_update_record()
+        .never();
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .never();
+
+    let result = service.reconcile(false).await;
+    assert!(matches!(result, Err(ServiceError::PDNSUtilFailed(_))));
+}
+
+#[tokio::test]
+async fn update_record_fail_case() {
+    let mut service = service_with_drift().await;
+    service
+        .mock_pdns_util_command
+        .expect_add_record()
+        .returning(|_, _| Ok(command_output(true)));
+    service
+        .mock_pdns_util_command
+        .expect_update_record()
+        .returning(|_, _| Ok(command_output(false)));
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .never();
+
+    let result = service.reconcile(false).await;
+    assert!(matches!(result, Err(ServiceError::PDNSUtilFailed(_))));
+}
@@ -1,16 +1,38 @@
+use crate::caches::{partition_cached, Cache, HaveIconHashCache};
 use crate::db::HaveDBPool;
-use crate::models::icon::CreateIcon;
+use crate::icon_image::{process_icon, thumbnail};
+use crate::models::icon::{
+    CreateIcon, CreateIconThumbnail, Icon, IconBlob, IconImage, StoredIconImage,
+};
 use crate::models::user::UserId;
 use crate::repos::icon_repository::{HaveIconRepository, IconRepository};
 use crate::repos::user_repository::{HaveUserRepository, UserRepository};
 use crate::services::ServiceResult;
+use crate::storages::icon_storage::{HaveIconStorage, IconStorage};
+use crate::storages::StorageError;
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 #[async_trait]
 pub trait IconService {
-    async fn find_image_by_user_id(&self, user_id: &UserId) -> ServiceResult<Option<Vec<u8>>>;
-    async fn find_image_by_user_name(&self, user_name: &str) -> ServiceResult<Option<Vec<u8>>>;
+    async fn find_image_by_user_id(&self, user_id: &UserId) -> ServiceResult<Option<IconImage>>;
+    async fn find_image_by_user_name(&self, user_name: &str) -> ServiceResult<Option<IconImage>>;
+    /// 指定サイズのサムネイルを返す。保存されていなければ元画像から生成する
+    async fn find_thumbnail_by_user_id(
+        &self,
+        user_id: &UserId,
+        size: u32,
+    ) -> ServiceResult<Option<IconImage>>;
+    async fn find_many_by_user_ids(&self, user_ids: &[UserId]) -> ServiceResult<Vec<Icon>>;
+    /// アイコンを設定しているユーザーのアイコン画像のハッシュを返す
+    async fn find_icon_hashes_by_user_ids(
+        &self,
+        user_ids: &[UserId],
+    ) -> ServiceResult<HashMap<UserId, String>>;
+    /// 画像を検証してアイコンを置き換え、サムネイルを生成する
     async fn replace_new_image(&self, user_id: &UserId, image: &[u8]) -> ServiceResult<i64>;
+    /// DBに保存されている画像を外部ストレージに移し、移した件数を返す
+    async fn move_blobs_to_storage(&self, batch_size: i64) -> ServiceResult<u64>;
 }
 
 pub trait HaveIconService {
@@ -19,22 +41,25 @@ pub trait HaveIconService {
     fn icon_service(&self) -> &Self::Service;
 }
 
-pub trait IconServiceImpl: Sync + HaveDBPool + HaveIconRepository + HaveUserRepository {}
+pub trait IconServiceImpl:
+    Sync + HaveDBPool + HaveIconRepository + HaveUserRepository + HaveIconHashCache + HaveIconStorage
+{
+}
 
 #[async_trait]
 impl<T: IconServiceImpl> IconService for T {
-    async fn find_image_by_user_id(&self, user_id: &UserId) -> ServiceResult<Option<Vec<u8>>> {
+    async fn find_image_by_user_id(&self, user_id: &UserId) -> ServiceResult<Option<IconImage>> {
         let mut conn = self.get_db_pool().begin().await?;
 
-        let image = self
+        let stored = self
             .icon_repo()
             .find_image_by_user_id(&mut conn, user_id)
             .await?;
 
-        Ok(image)
+        load_image(self, stored).await
     }
 
-    async fn find_image_by_user_name(&self, user_name: &str) -> ServiceResult<Option<Vec<u8>>> {
+    async fn find_image_by_user_name(&self, user_name: &str) -> ServiceResult<Option<IconImage>> {
         let mut conn = self.get_db_pool().begin().await?;
 
         let user = self
@@ -42,18 +67,147 @@ impl<T: IconServiceImpl> IconService for T {
             .find_by_name(&mut conn, user_name)
             .await?
             .unwrap();
-        let image = self
+        let stored = self
             .icon_repo()
             .find_image_by_user_id(&mut conn, &user.id)
             .await?;
 
-        Ok(image)
+        load_image(self, stored).await
+    }
+
+    async fn find_thumbnail_by_user_id(
+        &self,
+        user_id: &UserId,
+        size: u32,
+    ) -> ServiceResult<Option<IconImage>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+
+        let stored = self
+            .icon_repo()
+            .find_thumbnail_by_user_id(&mut conn, user_id, size)
+            .await?;
+        if let Some(image) = load_image(self, stored).await? {
+            return Ok(Some(image));
+        }
+
+        let stored = self
+            .icon_repo()
+            .find_image_by_user_id(&mut conn, user_id)
+            .await?;
+        let Some(original) = load_image(self, stored).await? else {
+            return Ok(None);
+        };
+        let image = tokio::task::spawn_blocking(move || match thumbnail(&original.image, size) {
+            Ok(thumbnail) => IconImage {
+                mime_type: thumbnail.mime_type.to_owned(),
+                image: thumbnail.image,
+            },
+            // 検証前に保存された画像はサムネイルを作れないことがあるので、元画像をそのまま返す
+            Err(e) => {
+                tracing::warn!("failed to generate icon thumbnail: {}", e);
+                original
+            }
+        })
+        .await?;
+
+        Ok(Some(image))
+    }
+
+    async fn find_many_by_user_ids(&self, user_ids: &[UserId]) -> ServiceResult<Vec<Icon>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let icons = self
+            .icon_repo()
+            .find_many_by_user_ids(&mut conn, user_ids)
+            .await?;
+
+        Ok(icons)
+    }
+
+    async fn find_icon_hashes_by_user_ids(
+        &self,
+        user_ids: &[UserId],
+    ) -> ServiceResult<HashMap<UserId, String>> {
+        let (hits, misses) = partition_cached(self.icon_hash_cache(), user_ids);
+        let mut icon_hashes: HashMap<UserId, String> = hits
+            .into_iter()
+            .filter_map(|(user_id, icon_hash)| icon_hash.map(|h| (user_id, h)))
+            .collect();
+        if misses.is_empty() {
+            return Ok(icon_hashes);
+        }
+
+        let mut conn = self.get_db_pool().acquire().await?;
+        let mut fetched: HashMap<UserId, String> = self
+            .icon_repo()
+            .find_many_hashes_by_user_ids(&mut conn, &misses)
+            .await?
+            .into_iter()
+            .map(|icon| (icon.user_id, icon.hash))
+            .collect();
+        // 他の実装で保存された行はハッシュが空なので、ここで計算して保存する
+        let unhashed: Vec<UserId> = fetched
+            .iter()
+            .filter(|(_, hash)| hash.is_empty())
+            .map(|(user_id, _)| user_id.clone())
+            .collect();
+        if !unhashed.is_empty() {
+            let icons = self
+                .icon_repo()
+                .find_many_by_user_ids(&mut conn, &unhashed)
+                .await?;
+            for icon in icons {
+                let Some(image) = icon.image.filter(|_| icon.hash.is_empty()) else {
+                    continue;
+                };
+                let hash = icon_hash(&image);
+                self.icon_repo()
+                    .update_hash_if_empty(&mut conn, &icon.id, &hash)
+                    .await?;
+                fetched.insert(icon.user_id, hash);
+            }
+        }
+        for user_id in misses {
+            let icon_hash = fetched.get(&user_id).cloned();
+            self.icon_hash_cache()
+                .set(user_id.clone(), icon_hash.clone());
+            if let Some(icon_hash) = icon_hash {
+                icon_hashes.insert(user_id, icon_hash);
+            }
+        }
+
+        Ok(icon_hashes)
     }
 
     async fn replace_new_image(&self, user_id: &UserId, image: &[u8]) -> ServiceResult<i64> {
+        let hash = icon_hash(image);
+        // デコードとリサイズは重いのでブロッキングスレッドで行う
+        let processed = {
+            let image = image.to_vec();
+            tokio::task::spawn_blocking(move || process_icon(&image)).await??
+        };
+
+        // 外部ストレージへの保存はトランザクションの外で先に済ませる
+        // 内容でキーが決まるので、DBの更新に失敗して残った画像は再アップロード時に再利用される
+        let stored_image = store_image(self, &hash, image.to_vec()).await?;
+        let mut thumbnails = Vec::with_capacity(processed.thumbnails.len());
+        for thumbnail in processed.thumbnails {
+            let thumbnail_hash = icon_hash(&thumbnail.image);
+            let stored_thumbnail = store_image(self, &thumbnail_hash, thumbnail.image).await?;
+            thumbnails.push(CreateIconThumbnail {
+                user_id: user_id.clone(),
+                size: thumbnail.size,
+                mime_type: thumbnail.mime_type.to_owned(),
+                image: stored_thumbnail,
+                hash: thumbnail_hash,
+            });
+        }
+
         let mut tx = self.get_db_pool().begin().await?;
 
         self.icon_repo().delete_by_user_id(&mut tx, user_id).await?;
+        self.icon_repo()
+            .delete_thumbnails_by_user_id(&mut tx, user_id)
+            .await?;
 
         let icon_id = self
             .icon_repo()
@@ -61,13 +215,127 @@ impl<T: IconServiceImpl> IconService for T {
                 &mut tx,
                 &CreateIcon {
                     user_id: user_id.clone(),
-                    image: image.to_vec(),
+                    image: stored_image,
+                    hash: hash.clone(),
+                    mime_type: processed.format.mime_type().to_owned(),
                 },
             )
             .await?;
+        for thumbnail in &thumbnails {
+            self.icon_repo()
+                .create_thumbnail(&mut tx, thumbnail)
+                .await?;
+        }
 
         tx.commit().await?;
+        self.icon_hash_cache().set(user_id.clone(), Some(hash));
 
         Ok(icon_id)
     }
+
+    async fn move_blobs_to_storage(&self, batch_size: i64) -> ServiceResult<u64> {
+        let storage = self.icon_storage().ok_or_else(|| {
+            StorageError::ConfigError("icon storage is not configured".to_owned())
+        })?;
+        let mut conn = self.get_db_pool().acquire().await?;
+        let mut moved = 0;
+
+        // 移した行は image が NULL になるので、毎回先頭から取り直せばよい
+        loop {
+            let blobs = self
+                .icon_repo()
+                .find_blobs_limit(&mut conn, batch_size)
+                .await?;
+            if blobs.is_empty() {
+                break;
+            }
+            for blob in blobs {
+                let (id, hash) = put_blob(storage, blob).await?;
+                self.icon_repo().clear_blob(&mut conn, id, &hash).await?;
+                moved += 1;
+            }
+        }
+
+        loop {
+            let blobs = self
+                .icon_repo()
+                .find_thumbnail_blobs_limit(&mut conn, batch_size)
+                .await?;
+            if blobs.is_empty() {
+                break;
+            }
+            for blob in blobs {
+                let (id, hash) = put_blob(storage, blob).await?;
+                self.icon_repo()
+                    .clear_thumbnail_blob(&mut conn, id, &hash)
+                    .await?;
+                moved += 1;
+            }
+        }
+
+        Ok(moved)
+    }
+}
+
+/// 外部ストレージが設定されていれば画像を保存し、DBに保存する画像を返す
+async fn store_image<T: IconServiceImpl>(
+    service: &T,
+    hash: &str,
+    image: Vec<u8>,
+) -> ServiceResult<Option<Vec<u8>>> {
+    match service.icon_storage() {
+        Some(storage) => {
+            storage.put(hash, &image).await?;
+            Ok(None)
+        }
+        None => Ok(Some(image)),
+    }
+}
+
+/// DBの行から画像を取り出す。外部ストレージに移した画像はストレージから取得する
+async fn load_image<T: IconServiceImpl>(
+    service: &T,
+    stored: Option<StoredIconImage>,
+) -> ServiceResult<Option<IconImage>> {
+    let Some(stored) = stored else {
+        return Ok(None);
+    };
+    if let Some(image) = stored.image {
+        return Ok(Some(IconImage {
+            mime_type: stored.mime_type,
+            image,
+        }));
+    }
+
+    let storage = service.icon_storage().ok_or_else(|| {
+        StorageError::ConfigError(
+            "icon is stored outside MySQL but no storage is configured".to_owned(),
+        )
+    })?;
+    let image = storage.get(&stored.hash).await?;
+    if image.is_none() {
+        tracing::warn!("icon {} is missing in storage", stored.hash);
+    }
+
+    Ok(image.map(|image| IconImage {
+        mime_type: stored.mime_type,
+        image,
+    }))
+}
+
+/// 他の実装で保存された行はハッシュが空なので、ここで計算する
+async fn put_blob<S: IconStorage>(storage: &S, blob: IconBlob) -> ServiceResult<(i64, String)> {
+    let hash = if blob.hash.is_empty() {
+        icon_hash(&blob.image)
+    } else {
+        blob.hash
+    };
+    storage.put(&hash, &blob.image).await?;
+
+    Ok((blob.id, hash))
+}
+
+pub fn icon_hash(image: &[u8]) -> String {
+    use sha2::digest::Digest as _;
+    format!("{:x}", sha2::Sha256::digest(image))
 }
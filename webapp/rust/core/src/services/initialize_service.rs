@@ -1,3 +1,6 @@
+use crate::caches::{
+    Cache, HaveIconHashCache, HaveNgWordMatcherCache, HaveTagCache, HaveThemeCache, HaveUserCache,
+};
 use crate::commands::initialize_command::{HaveInitializeCommand, InitializeCommand};
 use crate::commands::CommandOutput;
 use crate::services::ServiceResult;
@@ -14,12 +17,29 @@ pub trait HaveInitializeService {
     fn initialize_service(&self) -> &Self::Service;
 }
 
-pub trait InitializeServiceImpl: Sync + HaveInitializeCommand {}
+pub trait InitializeServiceImpl:
+    Sync
+    + HaveInitializeCommand
+    + HaveUserCache
+    + HaveThemeCache
+    + HaveTagCache
+    + HaveIconHashCache
+    + HaveNgWordMatcherCache
+{
+}
 
 #[async_trait]
 impl<T: InitializeServiceImpl> InitializeService for T {
     async fn execute_command(&self) -> ServiceResult<CommandOutput> {
         let output = self.initialize_command().execute().await?;
+
+        // データが作り直されるのでキャッシュも捨てる
+        self.user_cache().clear();
+        self.theme_cache().clear();
+        self.tag_cache().clear();
+        self.icon_hash_cache().clear();
+        self.ng_word_matcher_cache().clear();
+
         Ok(output)
     }
 }
@@ -1,8 +1,11 @@
+use crate::caches::{Cache, HaveNgWordMatcherCache};
 use crate::db::HaveDBPool;
+use crate::models::cursor::{Cursor, CursorPage, PageCursor};
 use crate::models::livestream::LivestreamId;
 use crate::models::livestream_comment::{
     CreateLivestreamComment, LivestreamComment, LivestreamCommentId,
 };
+use crate::ng_word_matcher::NgWordMatcher;
 use crate::repos::livestream_comment_repository::{
     HaveLivestreamCommentRepository, LivestreamCommentRepository,
 };
@@ -10,6 +13,10 @@ use crate::repos::ng_word_repository::{HaveNgWordRepository, NgWordRepository};
 use crate::services::ServiceError::CommentMatchSpam;
 use crate::services::ServiceResult;
 use async_trait::async_trait;
+use std::sync::Arc;
+
+#[cfg(test)]
+mod create;
 
 #[async_trait]
 pub trait LivestreamCommentService {
@@ -18,11 +25,28 @@ pub trait LivestreamCommentService {
         livestream_comment_id: &LivestreamCommentId,
     ) -> ServiceResult<Option<LivestreamComment>>;
 
+    async fn find_many(
+        &self,
+        livestream_comment_ids: &[LivestreamCommentId],
+    ) -> ServiceResult<Vec<LivestreamComment>>;
     async fn find_all_by_livestream_id(
         &self,
         livestream_id: &LivestreamId,
         limit: Option<i64>,
     ) -> ServiceResult<Vec<LivestreamComment>>;
+    /// last_comment_id より後のコメントを id の昇順に limit 件まで返す
+    async fn find_all_by_livestream_id_after(
+        &self,
+        livestream_id: &LivestreamId,
+        last_comment_id: &LivestreamCommentId,
+        limit: i64,
+    ) -> ServiceResult<Vec<LivestreamComment>>;
+    async fn find_page_by_livestream_id(
+        &self,
+        livestream_id: &LivestreamId,
+        cursor: Option<&PageCursor<Cursor>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<LivestreamComment, Cursor>>;
     async fn get_sum_tip(&self) -> ServiceResult<i64>;
 
     async fn create(&self, comment: &CreateLivestreamComment) -> ServiceResult<LivestreamComment>;
@@ -34,7 +58,7 @@ pub trait HaveLivestreamCommentService {
 }
 
 pub trait LivestreamCommentServiceImpl:
-    Sync + HaveDBPool + HaveLivestreamCommentRepository + HaveNgWordRepository
+    Sync + HaveDBPool + HaveLivestreamCommentRepository + HaveNgWordRepository + HaveNgWordMatcherCache
 {
 }
 
@@ -52,6 +76,19 @@ impl<T: LivestreamCommentServiceImpl> LivestreamCommentService for T {
         Ok(comment)
     }
 
+    async fn find_many(
+        &self,
+        livestream_comment_ids: &[LivestreamCommentId],
+    ) -> ServiceResult<Vec<LivestreamComment>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let comments = self
+            .livestream_comment_repo()
+            .find_many(&mut conn, livestream_comment_ids)
+            .await?;
+
+        Ok(comments)
+    }
+
     async fn find_all_by_livestream_id(
         &self,
         livestream_id: &LivestreamId,
@@ -79,6 +116,63 @@ impl<T: LivestreamCommentServiceImpl> LivestreamCommentService for T {
         Ok(comments)
     }
 
+    async fn find_all_by_livestream_id_after(
+        &self,
+        livestream_id: &LivestreamId,
+        last_comment_id: &LivestreamCommentId,
+        limit: i64,
+    ) -> ServiceResult<Vec<LivestreamComment>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let comments = self
+            .livestream_comment_repo()
+            .find_all_by_livestream_id_after_id_limit(
+                &mut conn,
+                livestream_id,
+                last_comment_id,
+                limit,
+            )
+            .await?;
+
+        Ok(comments)
+    }
+
+    async fn find_page_by_livestream_id(
+        &self,
+        livestream_id: &LivestreamId,
+        cursor: Option<&PageCursor<Cursor>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<LivestreamComment, Cursor>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let repo = self.livestream_comment_repo();
+
+        let comments = match cursor {
+            None => {
+                repo.find_all_by_livestream_id_order_by_created_at_limit(
+                    &mut conn,
+                    livestream_id,
+                    limit,
+                )
+                .await?
+            }
+            Some(PageCursor::Before(c)) => {
+                repo.find_all_by_livestream_id_before_cursor(&mut conn, livestream_id, c, limit)
+                    .await?
+            }
+            Some(PageCursor::After(c)) => {
+                let mut comments = repo
+                    .find_all_by_livestream_id_after_cursor(&mut conn, livestream_id, c, limit)
+                    .await?;
+                comments.reverse();
+                comments
+            }
+        };
+
+        Ok(CursorPage::build(comments, cursor, limit, |c| Cursor {
+            created_at: c.created_at,
+            id: *c.id.inner(),
+        }))
+    }
+
     async fn get_sum_tip(&self) -> ServiceResult<i64> {
         let mut conn = self.get_db_pool().acquire().await?;
         let sum_tip = self
@@ -89,34 +183,18 @@ impl<T: LivestreamCommentServiceImpl> LivestreamCommentService for T {
     }
 
     async fn create(&self, comment: &CreateLivestreamComment) -> ServiceResult<LivestreamComment> {
-        let mut tx = self.get_db_pool().begin().await?;
-
-        let ng_word_repo = self.ng_word_repo();
-        let ng_words = ng_word_repo
-            .find_all_by_livestream_id_and_user_id(
-                &mut tx,
-                &comment.livestream_id,
-                &comment.user_id,
-            )
-            .await?;
-
-        for ngword in &ng_words {
-            let hit_spam = ng_word_repo
-                .count_by_ng_word_in_comment(&mut tx, &ngword.word, &comment.comment)
-                .await?;
-            tracing::info!("[hit_spam={}] comment = {}", hit_spam, &comment.comment);
-            if hit_spam >= 1 {
-                return Err(CommentMatchSpam);
-            }
+        let matcher = find_ng_word_matcher(self, &comment.livestream_id).await?;
+        if matcher.is_match(&comment.comment) {
+            tracing::info!("[hit_spam] comment = {}", &comment.comment);
+            return Err(CommentMatchSpam);
         }
 
+        let mut conn = self.get_db_pool().acquire().await?;
         let comment_id = self
             .livestream_comment_repo()
-            .create(&mut tx, comment)
+            .create(&mut conn, comment)
             .await?;
 
-        tx.commit().await?;
-
         Ok(LivestreamComment {
             id: comment_id,
             user_id: comment.user_id.clone(),
@@ -127,3 +205,25 @@ impl<T: LivestreamCommentServiceImpl> LivestreamCommentService for T {
         })
     }
 }
+
+/// キャッシュに無ければ、ライブ配信のNGワードを読み込んで照合器を作る
+async fn find_ng_word_matcher<T: LivestreamCommentServiceImpl>(
+    service: &T,
+    livestream_id: &LivestreamId,
+) -> ServiceResult<Arc<NgWordMatcher>> {
+    if let Some(matcher) = service.ng_word_matcher_cache().get(livestream_id) {
+        return Ok(matcher);
+    }
+
+    let mut conn = service.get_db_pool().acquire().await?;
+    let ng_words = service
+        .ng_word_repo()
+        .find_all_by_livestream_id(&mut conn, livestream_id)
+        .await?;
+    let matcher = Arc::new(NgWordMatcher::from_ng_words(&ng_words)?);
+    service
+        .ng_word_matcher_cache()
+        .set(livestream_id.clone(), matcher.clone());
+
+    Ok(matcher)
+}
@@ -0,0 +1,95 @@
+use crate::caches::Cache;
+use crate::db::{get_db_pool, get_lazy_db_pool};
+use crate::models::livestream_comment::{CreateLivestreamComment, LivestreamCommentId};
+use crate::models::ng_word::{NgWord, NgWordMatchMode};
+use crate::ng_word_matcher::NgWordMatcher;
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::livestream_comment_service::LivestreamCommentService;
+use crate::services::ServiceError;
+use fake::{Fake, Faker};
+use kubetsu::Id;
+use std::sync::Arc;
+
+fn ng_word(comment: &CreateLivestreamComment, word: &str) -> NgWord {
+    NgWord {
+        id: Id::new(1),
+        user_id: Faker.fake(),
+        livestream_id: comment.livestream_id.clone(),
+        word: word.to_owned(),
+        match_mode: NgWordMatchMode::Substring,
+        created_at: 0,
+    }
+}
+
+async fn service_with_ng_words(
+    comment: &CreateLivestreamComment,
+    words: &'static [&'static str],
+) -> MockRepositoryManager {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut service = MockRepositoryManager::new(db_pool);
+    let ng_words: Vec<NgWord> = words.iter().map(|word| ng_word(comment, word)).collect();
+    let livestream_id = comment.livestream_id.clone();
+    service
+        .mock_ng_word_repo
+        .expect_find_all_by_livestream_id()
+        .withf(move |_, id| id == &livestream_id)
+        .times(1)
+        .return_once(move |_, _| Ok(ng_words));
+    // NGワードごとにDBへ問い合わせない
+    service
+        .mock_ng_word_repo
+        .expect_count_by_ng_word_in_comment()
+        .never();
+
+    service
+}
+
+#[tokio::test]
+async fn match_spam_case() {
+    let mut comment: CreateLivestreamComment = Faker.fake();
+    comment.comment = "この配信はspamです".to_owned();
+    let mut service = service_with_ng_words(&comment, &["ham", "spam", "egg"]).await;
+    service.mock_livestream_comment_repo.expect_create().never();
+
+    let result = service.create(&comment).await;
+    assert!(matches!(result, Err(ServiceError::CommentMatchSpam)));
+}
+
+#[tokio::test]
+async fn success_case() {
+    let mut comment: CreateLivestreamComment = Faker.fake();
+    comment.comment = "いい配信ですね".to_owned();
+    let mut service = service_with_ng_words(&comment, &["ham", "spam", "egg"]).await;
+    service
+        .mock_livestream_comment_repo
+        .expect_create()
+        .times(1)
+        .returning(|_, _| Ok(LivestreamCommentId::new(1)));
+
+    let created = service.create(&comment).await.unwrap();
+    assert_eq!(created.comment, comment.comment);
+}
+
+#[tokio::test]
+async fn cache_hit_case() {
+    let mut comment: CreateLivestreamComment = Faker.fake();
+    comment.comment = "この配信はspamです".to_owned();
+    let mut service = MockRepositoryManager::new(get_lazy_db_pool());
+    let matcher = NgWordMatcher::from_ng_words(&[ng_word(&comment, "spam")]).unwrap();
+    service
+        .ng_word_matcher_cache
+        .set(comment.livestream_id.clone(), Arc::new(matcher));
+    // キャッシュに載っていれば照合にDBを使わない
+    service
+        .mock_ng_word_repo
+        .expect_find_all_by_livestream_id()
+        .never();
+    service
+        .mock_ng_word_repo
+        .expect_count_by_ng_word_in_comment()
+        .never();
+    service.mock_livestream_comment_repo.expect_create().never();
+
+    let result = service.create(&comment).await;
+    assert!(matches!(result, Err(ServiceError::CommentMatchSpam)));
+}
@@ -1,4 +1,5 @@
 use crate::db::HaveDBPool;
+use crate::models::cursor::{CursorPage, PageCursor};
 use crate::models::livestream::{CreateLivestream, Livestream, LivestreamId};
 use crate::models::tag::{TagId, TagName};
 use crate::models::user::UserId;
@@ -13,6 +14,7 @@ use crate::repos::tag_repository::{HaveTagRepository, TagRepository};
 use crate::services::ServiceError::InvalidReservationRange;
 use crate::services::ServiceResult;
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 #[async_trait]
 pub trait LivestreamService {
@@ -22,9 +24,21 @@ pub trait LivestreamService {
         tag_ids: &[TagId],
     ) -> ServiceResult<Livestream>;
     async fn find(&self, livestream_id: &LivestreamId) -> ServiceResult<Option<Livestream>>;
+    async fn find_many(&self, livestream_ids: &[LivestreamId]) -> ServiceResult<Vec<Livestream>>;
 
     async fn find_recent_livestreams(&self, limit: Option<i64>) -> ServiceResult<Vec<Livestream>>;
+    async fn find_recent_livestreams_page(
+        &self,
+        cursor: Option<&PageCursor<LivestreamId>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<Livestream, LivestreamId>>;
     async fn find_recent_by_tag_name(&self, tag_name: &TagName) -> ServiceResult<Vec<Livestream>>;
+    async fn find_recent_by_tag_name_page(
+        &self,
+        tag_name: &TagName,
+        cursor: Option<&PageCursor<LivestreamId>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<Livestream, LivestreamId>>;
     async fn find_all_by_user_id(&self, user_id: &UserId) -> ServiceResult<Vec<Livestream>>;
 
     async fn exist_by_id_and_user_id(
@@ -118,6 +132,16 @@ impl<T: LivestreamServiceImpl> LivestreamService for T {
         Ok(result)
     }
 
+    async fn find_many(&self, livestream_ids: &[LivestreamId]) -> ServiceResult<Vec<Livestream>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let livestreams = self
+            .livestream_repo()
+            .find_many(&mut conn, livestream_ids)
+            .await?;
+
+        Ok(livestreams)
+    }
+
     async fn find_recent_livestreams(&self, limit: Option<i64>) -> ServiceResult<Vec<Livestream>> {
         let mut conn = self.get_db_pool().acquire().await?;
         let livestreams = match limit {
@@ -136,6 +160,37 @@ impl<T: LivestreamServiceImpl> LivestreamService for T {
         Ok(livestreams)
     }
 
+    async fn find_recent_livestreams_page(
+        &self,
+        cursor: Option<&PageCursor<LivestreamId>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<Livestream, LivestreamId>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let repo = self.livestream_repo();
+
+        let livestreams = match cursor {
+            None => {
+                repo.find_all_order_by_id_desc_limit(&mut conn, limit)
+                    .await?
+            }
+            Some(PageCursor::Before(id)) => {
+                repo.find_all_by_id_lt_order_by_id_desc_limit(&mut conn, id, limit)
+                    .await?
+            }
+            Some(PageCursor::After(id)) => {
+                let mut livestreams = repo
+                    .find_all_by_id_gt_order_by_id_asc_limit(&mut conn, id, limit)
+                    .await?;
+                livestreams.reverse();
+                livestreams
+            }
+        };
+
+        Ok(CursorPage::build(livestreams, cursor, limit, |l| {
+            l.id.clone()
+        }))
+    }
+
     async fn find_recent_by_tag_name(&self, tag_name: &TagName) -> ServiceResult<Vec<Livestream>> {
         let mut tx = self.get_db_pool().acquire().await?;
         let tag_id_list = self.tag_repo().find_ids_by_name(&mut tx, tag_name).await?;
@@ -159,6 +214,40 @@ impl<T: LivestreamServiceImpl> LivestreamService for T {
         Ok(livestream_models)
     }
 
+    async fn find_recent_by_tag_name_page(
+        &self,
+        tag_name: &TagName,
+        cursor: Option<&PageCursor<LivestreamId>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<Livestream, LivestreamId>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let tag_id_list = self
+            .tag_repo()
+            .find_ids_by_name(&mut conn, tag_name)
+            .await?;
+        let livestream_ids = self
+            .livestream_tag_repo()
+            .find_livestream_ids_page_by_tag_ids(&mut conn, &tag_id_list, cursor.cloned(), limit)
+            .await?;
+
+        // find_many は順序を保証しないので、IDの並びに合わせる
+        let mut livestreams: HashMap<LivestreamId, Livestream> = self
+            .livestream_repo()
+            .find_many(&mut conn, &livestream_ids)
+            .await?
+            .into_iter()
+            .map(|livestream| (livestream.id.clone(), livestream))
+            .collect();
+        let livestreams = livestream_ids
+            .iter()
+            .filter_map(|id| livestreams.remove(id))
+            .collect();
+
+        Ok(CursorPage::build(livestreams, cursor, limit, |l| {
+            l.id.clone()
+        }))
+    }
+
     async fn find_all_by_user_id(&self, user_id: &UserId) -> ServiceResult<Vec<Livestream>> {
         let mut conn = self.get_db_pool().acquire().await?;
         let livestreams = self
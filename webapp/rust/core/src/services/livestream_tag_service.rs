@@ -13,6 +13,11 @@ pub trait LivestreamTagService {
         &self,
         livestream_id: &LivestreamId,
     ) -> ServiceResult<Vec<LivestreamTag>>;
+
+    async fn find_many_by_livestream_ids(
+        &self,
+        livestream_ids: &[LivestreamId],
+    ) -> ServiceResult<Vec<LivestreamTag>>;
 }
 
 pub trait HaveLivestreamTagService {
@@ -36,4 +41,17 @@ impl<T: LivestreamTagServiceImpl> LivestreamTagService for T {
             .await?;
         Ok(livestream_tags)
     }
+
+    async fn find_many_by_livestream_ids(
+        &self,
+        livestream_ids: &[LivestreamId],
+    ) -> ServiceResult<Vec<LivestreamTag>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let livestream_tags = self
+            .livestream_tag_repo()
+            .find_many_by_livestream_ids(&mut conn, livestream_ids)
+            .await?;
+
+        Ok(livestream_tags)
+    }
 }
@@ -0,0 +1,177 @@
+use crate::services::{ServiceError, ServiceResult};
+use crate::storages::login_attempt_store::{HaveLoginAttemptStore, LoginAttemptStore};
+use async_trait::async_trait;
+
+#[cfg(test)]
+mod ensure_allowed;
+#[cfg(test)]
+mod lockout_secs;
+#[cfg(test)]
+mod prune_expired;
+#[cfg(test)]
+mod record_failure;
+
+/// 失敗回数に応じたロックの掛け方
+#[derive(Debug, Clone, Copy)]
+pub struct LoginThrottlePolicy {
+    /// この回数まではロックしない
+    pub free_attempts: i64,
+    /// 最初のロック時間。以降は失敗のたびに倍にする
+    pub base_lockout_secs: i64,
+    pub max_lockout_secs: i64,
+    /// 最後の失敗からこの時間が経てば失敗回数を数え直す
+    pub window_secs: i64,
+}
+
+/// ユーザー名ごとの制限。特定のアカウントへの総当たりを防ぐ
+pub const USERNAME_POLICY: LoginThrottlePolicy = LoginThrottlePolicy {
+    free_attempts: 5,
+    base_lockout_secs: 1,
+    max_lockout_secs: 15 * 60,
+    window_secs: 60 * 60,
+};
+
+/// 接続元IPごとの制限。多数のアカウントを順に試す攻撃を防ぐ
+pub const IP_POLICY: LoginThrottlePolicy = LoginThrottlePolicy {
+    free_attempts: 50,
+    base_lockout_secs: 1,
+    max_lockout_secs: 15 * 60,
+    window_secs: 60 * 60,
+};
+
+impl LoginThrottlePolicy {
+    /// failures 回失敗した後のロック時間。ロックしない場合は None
+    pub fn lockout_secs(&self, failures: i64) -> Option<i64> {
+        let exceeded = failures - self.free_attempts;
+        if exceeded <= 0 {
+            return None;
+        }
+
+        let lockout = u32::try_from(exceeded - 1)
+            .ok()
+            .and_then(|exp| 2i64.checked_pow(exp))
+            .and_then(|factor| self.base_lockout_secs.checked_mul(factor))
+            .unwrap_or(self.max_lockout_secs);
+        Some(lockout.min(self.max_lockout_secs))
+    }
+}
+
+#[async_trait]
+pub trait LoginThrottleService {
+    /// ユーザー名か接続元がロック中なら ServiceError::TooManyLoginAttempts を返す
+    async fn ensure_allowed(&self, username: &str, ip: Option<&str>, now: i64)
+        -> ServiceResult<()>;
+
+    async fn record_failure(&self, username: &str, ip: Option<&str>, now: i64)
+        -> ServiceResult<()>;
+
+    /// ログインに成功したユーザー名の失敗回数を消す
+    /// 接続元の失敗回数は自分のアカウントへのログインで消せないよう残す
+    async fn record_success(&self, username: &str) -> ServiceResult<()>;
+
+    /// 数え直しになった記録を消す。存在しないユーザー名でも記録は作られるので、定期的に呼ぶ
+    async fn prune_expired(&self, now: i64) -> ServiceResult<u64>;
+}
+
+pub trait HaveLoginThrottleService {
+    type Service: LoginThrottleService;
+
+    fn login_throttle_service(&self) -> &Self::Service;
+}
+
+pub trait LoginThrottleServiceImpl: Sync + HaveLoginAttemptStore {}
+
+#[async_trait]
+impl<T: LoginThrottleServiceImpl> LoginThrottleService for T {
+    async fn ensure_allowed(
+        &self,
+        username: &str,
+        ip: Option<&str>,
+        now: i64,
+    ) -> ServiceResult<()> {
+        let mut locked_until = 0;
+        for (key, _) in attempt_keys(username, ip) {
+            if let Some(attempt) = self.login_attempt_store().find(&key).await? {
+                locked_until = locked_until.max(attempt.locked_until);
+            }
+        }
+
+        if locked_until > now {
+            return Err(ServiceError::TooManyLoginAttempts {
+                retry_after: locked_until - now,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        username: &str,
+        ip: Option<&str>,
+        now: i64,
+    ) -> ServiceResult<()> {
+        for (key, policy) in attempt_keys(username, ip) {
+            let attempt = self
+                .login_attempt_store()
+                .record_failure(&key, now, now - policy.window_secs)
+                .await?;
+            if let Some(lockout) = policy.lockout_secs(attempt.failures) {
+                self.login_attempt_store().lock(&key, now + lockout).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn record_success(&self, username: &str) -> ServiceResult<()> {
+        self.login_attempt_store()
+            .delete(&username_key(username))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn prune_expired(&self, now: i64) -> ServiceResult<u64> {
+        let window_secs = USERNAME_POLICY.window_secs.max(IP_POLICY.window_secs);
+        let deleted = self
+            .login_attempt_store()
+            .delete_expired(now - window_secs, now)
+            .await?;
+
+        Ok(deleted)
+    }
+}
+
+fn username_key(username: &str) -> String {
+    format!("user:{}", username)
+}
+
+fn attempt_keys(username: &str, ip: Option<&str>) -> Vec<(String, LoginThrottlePolicy)> {
+    let mut keys = vec![(username_key(username), USERNAME_POLICY)];
+    if let Some(ip) = ip {
+        keys.push((format!("ip:{}", ip), IP_POLICY));
+    }
+    keys
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::services::login_throttle_service::LoginThrottleServiceImpl;
+    use crate::storages::login_attempt_store::{HaveLoginAttemptStore, InMemoryLoginAttemptStore};
+
+    #[derive(Default)]
+    pub struct InMemoryLoginThrottleService {
+        pub store: InMemoryLoginAttemptStore,
+    }
+
+    impl HaveLoginAttemptStore for InMemoryLoginThrottleService {
+        type Store = InMemoryLoginAttemptStore;
+
+        fn login_attempt_store(&self) -> &Self::Store {
+            &self.store
+        }
+    }
+
+    impl LoginThrottleServiceImpl for InMemoryLoginThrottleService {}
+}
@@ -0,0 +1,79 @@
+use crate::services::login_throttle_service::tests::InMemoryLoginThrottleService;
+use crate::services::login_throttle_service::{LoginThrottleService, IP_POLICY, USERNAME_POLICY};
+use crate::services::ServiceError;
+
+#[tokio::test]
+async fn allowed_case() {
+    let service = InMemoryLoginThrottleService::default();
+
+    let result = service.ensure_allowed("alice", Some("192.0.2.1"), 0).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn username_locked_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    for _ in 0..=USERNAME_POLICY.free_attempts {
+        service.record_failure("alice", None, now).await.unwrap();
+    }
+
+    let result = service
+        .ensure_allowed("alice", Some("192.0.2.1"), now)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ServiceError::TooManyLoginAttempts { retry_after })
+            if retry_after == USERNAME_POLICY.base_lockout_secs
+    ));
+
+    // 別のユーザー名には影響しない
+    let result = service.ensure_allowed("bob", Some("192.0.2.1"), now).await;
+    assert!(result.is_ok());
+
+    // ロックが明ければ再び試せる
+    let result = service
+        .ensure_allowed("alice", None, now + USERNAME_POLICY.base_lockout_secs)
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn ip_locked_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    for i in 0..=IP_POLICY.free_attempts {
+        let username = format!("user{}", i);
+        service
+            .record_failure(&username, Some("192.0.2.1"), now)
+            .await
+            .unwrap();
+    }
+
+    let result = service
+        .ensure_allowed("carol", Some("192.0.2.1"), now)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ServiceError::TooManyLoginAttempts { .. })
+    ));
+
+    let result = service
+        .ensure_allowed("carol", Some("192.0.2.2"), now)
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn success_resets_username_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    for _ in 0..=USERNAME_POLICY.free_attempts {
+        service.record_failure("alice", None, now).await.unwrap();
+    }
+
+    service.record_success("alice").await.unwrap();
+
+    let result = service.ensure_allowed("alice", None, now).await;
+    assert!(result.is_ok());
+}
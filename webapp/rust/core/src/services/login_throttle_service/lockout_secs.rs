@@ -0,0 +1,33 @@
+use crate::services::login_throttle_service::{LoginThrottlePolicy, USERNAME_POLICY};
+
+const POLICY: LoginThrottlePolicy = LoginThrottlePolicy {
+    free_attempts: 3,
+    base_lockout_secs: 2,
+    max_lockout_secs: 60,
+    window_secs: 3600,
+};
+
+#[test]
+fn free_attempts_case() {
+    for failures in 0..=POLICY.free_attempts {
+        assert_eq!(POLICY.lockout_secs(failures), None);
+    }
+}
+
+#[test]
+fn exponential_backoff_case() {
+    assert_eq!(POLICY.lockout_secs(4), Some(2));
+    assert_eq!(POLICY.lockout_secs(5), Some(4));
+    assert_eq!(POLICY.lockout_secs(6), Some(8));
+    assert_eq!(POLICY.lockout_secs(7), Some(16));
+}
+
+#[test]
+fn capped_case() {
+    assert_eq!(POLICY.lockout_secs(9), Some(60));
+    assert_eq!(POLICY.lockout_secs(1000), Some(60));
+    assert_eq!(
+        USERNAME_POLICY.lockout_secs(i64::MAX),
+        Some(USERNAME_POLICY.max_lockout_secs)
+    );
+}
@@ -0,0 +1,45 @@
+use crate::services::login_throttle_service::tests::InMemoryLoginThrottleService;
+use crate::services::login_throttle_service::{LoginThrottleService, USERNAME_POLICY};
+use crate::storages::login_attempt_store::LoginAttemptStore;
+
+#[tokio::test]
+async fn expired_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    service
+        .record_failure("nobody", Some("192.0.2.1"), now)
+        .await
+        .unwrap();
+
+    let later = now + USERNAME_POLICY.window_secs + 1;
+    let deleted = service.prune_expired(later).await.unwrap();
+    assert_eq!(deleted, 2);
+    assert!(service.store.find("user:nobody").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn within_window_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    service.record_failure("alice", None, now).await.unwrap();
+
+    let deleted = service
+        .prune_expired(now + USERNAME_POLICY.window_secs - 1)
+        .await
+        .unwrap();
+    assert_eq!(deleted, 0);
+    assert!(service.store.find("user:alice").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn still_locked_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    service.record_failure("alice", None, now).await.unwrap();
+    let later = now + USERNAME_POLICY.window_secs + 1;
+    service.store.lock("user:alice", later + 60).await.unwrap();
+
+    // ロック中の記録は消さない
+    let deleted = service.prune_expired(later).await.unwrap();
+    assert_eq!(deleted, 0);
+}
@@ -0,0 +1,46 @@
+use crate::services::login_throttle_service::tests::InMemoryLoginThrottleService;
+use crate::services::login_throttle_service::{LoginThrottleService, USERNAME_POLICY};
+use crate::storages::login_attempt_store::LoginAttemptStore;
+
+#[tokio::test]
+async fn backoff_doubles_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    for _ in 0..USERNAME_POLICY.free_attempts + 3 {
+        service.record_failure("alice", None, now).await.unwrap();
+    }
+
+    let attempt = service.store.find("user:alice").await.unwrap().unwrap();
+    assert_eq!(attempt.failures, USERNAME_POLICY.free_attempts + 3);
+    assert_eq!(
+        attempt.locked_until,
+        now + USERNAME_POLICY.base_lockout_secs * 4
+    );
+}
+
+#[tokio::test]
+async fn window_expired_case() {
+    let service = InMemoryLoginThrottleService::default();
+    let now = 1_000;
+    for _ in 0..USERNAME_POLICY.free_attempts {
+        service.record_failure("alice", None, now).await.unwrap();
+    }
+
+    // 最後の失敗から十分経っていれば数え直す
+    let later = now + USERNAME_POLICY.window_secs + 1;
+    service.record_failure("alice", None, later).await.unwrap();
+
+    let attempt = service.store.find("user:alice").await.unwrap().unwrap();
+    assert_eq!(attempt.failures, 1);
+    assert_eq!(attempt.locked_until, 0);
+}
+
+#[tokio::test]
+async fn without_ip_case() {
+    let service = InMemoryLoginThrottleService::default();
+
+    service.record_failure("alice", None, 1_000).await.unwrap();
+
+    assert!(service.store.find("user:alice").await.unwrap().is_some());
+    assert_eq!(service.store.find("ip:").await.unwrap(), None);
+}
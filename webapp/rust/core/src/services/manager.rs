@@ -1,3 +1,4 @@
+use crate::services::account_service::HaveAccountService;
 use crate::services::icon_service::HaveIconService;
 use crate::services::initialize_service::HaveInitializeService;
 use crate::services::livestream_comment_report_service::HaveLivestreamCommentReportService;
@@ -6,8 +7,11 @@ use crate::services::livestream_service::HaveLivestreamService;
 use crate::services::livestream_statistics_service::HaveLivestreamStatisticsService;
 use crate::services::livestream_tag_service::HaveLivestreamTagService;
 use crate::services::livestream_viewers_history_service::HaveLivestreamViewersHistoryService;
+use crate::services::login_throttle_service::HaveLoginThrottleService;
 use crate::services::ng_word_service::HaveNgWordService;
+use crate::services::password_service::HavePasswordService;
 use crate::services::reaction_service::HaveReactionService;
+use crate::services::session_service::HaveSessionService;
 use crate::services::tag_service::HaveTagService;
 use crate::services::theme_service::HaveThemeService;
 use crate::services::user_service::HaveUserService;
@@ -31,5 +35,9 @@ pub trait ServiceManager:
     + HaveLivestreamViewersHistoryService
     + HaveLivestreamTagService
     + HaveLivestreamStatisticsService
+    + HaveSessionService
+    + HaveLoginThrottleService
+    + HavePasswordService
+    + HaveAccountService
 {
 }
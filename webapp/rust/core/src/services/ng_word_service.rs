@@ -1,22 +1,40 @@
+use crate::caches::{Cache, HaveNgWordMatcherCache};
 use crate::db::HaveDBPool;
 use crate::models::livestream::LivestreamId;
+use crate::models::livestream_comment::LivestreamCommentId;
 use crate::models::ng_word::{CreateNgWord, NgWord, NgWordId};
+use crate::models::ng_word_purge_job::{NgWordPurgeJob, NgWordPurgeJobId, NgWordPurgeJobStatus};
 use crate::models::user::UserId;
+use crate::ng_word_matcher::NgWordMatcher;
 use crate::repos::livestream_comment_repository::{
     HaveLivestreamCommentRepository, LivestreamCommentRepository,
 };
 use crate::repos::ng_word_repository::{HaveNgWordRepository, NgWordRepository};
 use crate::services::ServiceResult;
+use crate::storages::ng_word_purge_job_store::{HaveNgWordPurgeJobStore, NgWordPurgeJobStore};
+use crate::validation::ValidationErrors;
 use async_trait::async_trait;
 
+#[cfg(test)]
+mod purge_comments;
+
+/// 過去のコメントを消すときに一度に読み込む件数
+pub const PURGE_BATCH_SIZE: i64 = 500;
+
 #[async_trait]
 pub trait NgWordService {
-    async fn create(&self, ng_word: &CreateNgWord) -> ServiceResult<NgWordId>;
+    /// NGワードを登録し、一致する過去のコメントを消すジョブをバックグラウンドで始める
+    async fn create(&self, ng_word: &CreateNgWord) -> ServiceResult<(NgWordId, NgWordPurgeJob)>;
     async fn find_all_by_livestream_id_and_user_id(
         &self,
         livestream_id: &LivestreamId,
         user_id: &UserId,
     ) -> ServiceResult<Vec<NgWord>>;
+    async fn find_purge_job(
+        &self,
+        livestream_id: &LivestreamId,
+        job_id: &NgWordPurgeJobId,
+    ) -> ServiceResult<Option<NgWordPurgeJob>>;
 }
 
 pub trait HaveNgWordService {
@@ -25,37 +43,52 @@ pub trait HaveNgWordService {
     fn ng_word_service(&self) -> &Self::Service;
 }
 
+/// ジョブを別タスクで動かすため、Clone して持ち出せる必要がある
 pub trait NgWordServiceImpl:
-    Sync + HaveDBPool + HaveNgWordRepository + HaveLivestreamCommentRepository
+    Clone
+    + Send
+    + Sync
+    + 'static
+    + HaveDBPool
+    + HaveNgWordRepository
+    + HaveLivestreamCommentRepository
+    + HaveNgWordMatcherCache
+    + HaveNgWordPurgeJobStore
 {
 }
 
 #[async_trait]
 impl<T: NgWordServiceImpl> NgWordService for T {
-    async fn create(&self, ng_word: &CreateNgWord) -> ServiceResult<NgWordId> {
-        let mut tx = self.get_db_pool().begin().await?;
+    async fn create(&self, ng_word: &CreateNgWord) -> ServiceResult<(NgWordId, NgWordPurgeJob)> {
+        // 照合に使えないNGワードを登録すると、以後そのライブ配信にコメントできなくなる
+        if let Err(e) = NgWordMatcher::validate(ng_word.match_mode, &ng_word.word) {
+            let mut errors = ValidationErrors::default();
+            errors.push("ng_word", "invalid_pattern", e.to_string());
+            return Err(errors.into());
+        }
 
-        let word_id = self.ng_word_repo().create(&mut tx, ng_word).await?;
+        let word_id = {
+            let mut conn = self.get_db_pool().acquire().await?;
+            self.ng_word_repo().create(&mut conn, ng_word).await?
+        };
 
-        let ng_words = self
-            .ng_word_repo()
-            .find_all_by_livestream_id(&mut tx, &ng_word.livestream_id)
-            .await?;
+        // 登録が見えるようになってから捨てる。次のコメント投稿時に作り直される
+        self.ng_word_matcher_cache().remove(&ng_word.livestream_id);
 
-        // NGワードにヒットする過去の投稿も全削除する
-        let comment_repo = self.livestream_comment_repo();
-        let comments = comment_repo.find_all(&mut tx).await?;
-        for ngword in &ng_words {
-            for comment in &comments {
-                comment_repo
-                    .remove_if_match_ng_word(&mut tx, comment, &ngword.word)
-                    .await?;
+        // NGワードにヒットする過去の投稿は、テーブルを長くロックしないよう少しずつ消す
+        let job = self
+            .ng_word_purge_job_store()
+            .create(&ng_word.livestream_id, &word_id)
+            .await?;
+        let service = self.clone();
+        let queued = job.clone();
+        tokio::spawn(async move {
+            if let Err(e) = purge_comments(&service, queued).await {
+                tracing::error!("failed to purge comments matching NG words: {}", e);
             }
-        }
-
-        tx.commit().await?;
+        });
 
-        Ok(word_id)
+        Ok((word_id, job))
     }
 
     async fn find_all_by_livestream_id_and_user_id(
@@ -75,4 +108,97 @@ impl<T: NgWordServiceImpl> NgWordService for T {
 
         Ok(ng_words)
     }
+
+    async fn find_purge_job(
+        &self,
+        livestream_id: &LivestreamId,
+        job_id: &NgWordPurgeJobId,
+    ) -> ServiceResult<Option<NgWordPurgeJob>> {
+        let job = self.ng_word_purge_job_store().find(job_id).await?;
+        Ok(job.filter(|job| &job.livestream_id == livestream_id))
+    }
+}
+
+/// ライブ配信のNGワードのいずれかに一致するコメントを、id 順に少しずつ消す
+/// バッチごとに自動コミットし、進み具合をジョブに記録する
+pub async fn purge_comments<T>(
+    service: &T,
+    mut job: NgWordPurgeJob,
+) -> ServiceResult<NgWordPurgeJob>
+where
+    T: HaveDBPool
+        + HaveNgWordRepository
+        + HaveLivestreamCommentRepository
+        + HaveNgWordPurgeJobStore
+        + Sync,
+{
+    job.status = NgWordPurgeJobStatus::Running;
+    service.ng_word_purge_job_store().update(&job).await?;
+
+    match purge_in_batches(service, &mut job).await {
+        Ok(()) => {
+            job.status = NgWordPurgeJobStatus::Completed;
+            service.ng_word_purge_job_store().update(&job).await?;
+            Ok(job)
+        }
+        Err(e) => {
+            job.status = NgWordPurgeJobStatus::Failed;
+            job.error = Some(e.to_string());
+            service.ng_word_purge_job_store().update(&job).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn purge_in_batches<T>(service: &T, job: &mut NgWordPurgeJob) -> ServiceResult<()>
+where
+    T: HaveDBPool
+        + HaveNgWordRepository
+        + HaveLivestreamCommentRepository
+        + HaveNgWordPurgeJobStore
+        + Sync,
+{
+    let mut conn = service.get_db_pool().acquire().await?;
+    // 開始までに登録された他のNGワードもまとめて照合する
+    let ng_words = service
+        .ng_word_repo()
+        .find_all_by_livestream_id(&mut conn, &job.livestream_id)
+        .await?;
+    let matcher = NgWordMatcher::from_ng_words(&ng_words)?;
+
+    let comment_repo = service.livestream_comment_repo();
+    let mut last_id = LivestreamCommentId::new(0);
+    loop {
+        let comments = comment_repo
+            .find_all_by_livestream_id_after_id_limit(
+                &mut conn,
+                &job.livestream_id,
+                &last_id,
+                PURGE_BATCH_SIZE,
+            )
+            .await?;
+        let Some(last) = comments.last() else {
+            break;
+        };
+        last_id = last.id.clone();
+
+        let matched: Vec<LivestreamCommentId> = comments
+            .iter()
+            .filter(|comment| matcher.is_match(&comment.comment))
+            .map(|comment| comment.id.clone())
+            .collect();
+        let deleted = comment_repo
+            .delete_many_by_livestream_id(&mut conn, &job.livestream_id, &matched)
+            .await?;
+
+        job.scanned += comments.len() as i64;
+        job.deleted += deleted as i64;
+        service.ng_word_purge_job_store().update(job).await?;
+
+        if (comments.len() as i64) < PURGE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,147 @@
+use crate::db::get_lazy_db_pool;
+use crate::models::livestream::LivestreamId;
+use crate::models::livestream_comment::{LivestreamComment, LivestreamCommentId};
+use crate::models::ng_word::{NgWord, NgWordId, NgWordMatchMode};
+use crate::models::ng_word_purge_job::{NgWordPurgeJob, NgWordPurgeJobStatus};
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::repos::ReposError;
+use crate::services::ng_word_service::{purge_comments, PURGE_BATCH_SIZE};
+use crate::storages::ng_word_purge_job_store::NgWordPurgeJobStore;
+use fake::{Fake, Faker};
+use kubetsu::Id;
+
+fn livestream_comment(livestream_id: &LivestreamId, id: i64, comment: &str) -> LivestreamComment {
+    LivestreamComment {
+        id: LivestreamCommentId::new(id),
+        user_id: Faker.fake(),
+        livestream_id: livestream_id.clone(),
+        comment: comment.to_owned(),
+        tip: 0,
+        created_at: 0,
+    }
+}
+
+async fn service_with_ng_words(
+    livestream_id: &LivestreamId,
+    words: &'static [&'static str],
+) -> (MockRepositoryManager, NgWordPurgeJob) {
+    let mut service = MockRepositoryManager::new(get_lazy_db_pool());
+    let ng_words: Vec<NgWord> = words
+        .iter()
+        .map(|word| NgWord {
+            id: Id::new(1),
+            user_id: Faker.fake(),
+            livestream_id: livestream_id.clone(),
+            word: word.to_string(),
+            match_mode: NgWordMatchMode::Substring,
+            created_at: 0,
+        })
+        .collect();
+    service
+        .mock_ng_word_repo
+        .expect_find_all_by_livestream_id()
+        .return_once(move |_, _| Ok(ng_words));
+    let job = service
+        .ng_word_purge_job_store
+        .create(livestream_id, &NgWordId::new(1))
+        .await
+        .unwrap();
+
+    (service, job)
+}
+
+#[tokio::test]
+async fn purge_in_batches_case() {
+    let livestream_id: LivestreamId = Faker.fake();
+    let (mut service, job) = service_with_ng_words(&livestream_id, &["spam"]).await;
+
+    // 1バッチ目は上限まで返し、2バッチ目で読み終える
+    let first_batch: Vec<LivestreamComment> = (1..=PURGE_BATCH_SIZE)
+        .map(|id| {
+            let comment = if id % 100 == 0 { "spam!" } else { "hello" };
+            livestream_comment(&livestream_id, id, comment)
+        })
+        .collect();
+    let second_batch = vec![livestream_comment(
+        &livestream_id,
+        PURGE_BATCH_SIZE + 1,
+        "more spam",
+    )];
+    service
+        .mock_livestream_comment_repo
+        .expect_find_all_by_livestream_id_after_id_limit()
+        .withf(|_, _, last_id, _| last_id == &LivestreamCommentId::new(0))
+        .times(1)
+        .return_once(move |_, _, _, _| Ok(first_batch));
+    service
+        .mock_livestream_comment_repo
+        .expect_find_all_by_livestream_id_after_id_limit()
+        .withf(|_, _, last_id, _| last_id == &LivestreamCommentId::new(PURGE_BATCH_SIZE))
+        .times(1)
+        .return_once(move |_, _, _, _| Ok(second_batch));
+    service
+        .mock_livestream_comment_repo
+        .expect_delete_many_by_livestream_id()
+        .times(2)
+        .returning(|_, _, ids| {
+            let expected: Vec<LivestreamCommentId> = if ids.len() == 1 {
+                vec![LivestreamCommentId::new(PURGE_BATCH_SIZE + 1)]
+            } else {
+                (1..=PURGE_BATCH_SIZE / 100)
+                    .map(|n| LivestreamCommentId::new(n * 100))
+                    .collect()
+            };
+            assert_eq!(ids, expected.as_slice());
+            Ok(ids.len() as u64)
+        });
+
+    let finished = purge_comments(&service, job.clone()).await.unwrap();
+    assert_eq!(finished.status, NgWordPurgeJobStatus::Completed);
+    assert_eq!(finished.scanned, PURGE_BATCH_SIZE + 1);
+    assert_eq!(finished.deleted, PURGE_BATCH_SIZE / 100 + 1);
+
+    let stored = service.ng_word_purge_job_store.find(&job.id).await.unwrap();
+    assert_eq!(stored, Some(finished));
+}
+
+#[tokio::test]
+async fn no_comments_case() {
+    let livestream_id: LivestreamId = Faker.fake();
+    let (mut service, job) = service_with_ng_words(&livestream_id, &["spam"]).await;
+    service
+        .mock_livestream_comment_repo
+        .expect_find_all_by_livestream_id_after_id_limit()
+        .times(1)
+        .returning(|_, _, _, _| Ok(Vec::new()));
+    service
+        .mock_livestream_comment_repo
+        .expect_delete_many_by_livestream_id()
+        .never();
+
+    let finished = purge_comments(&service, job).await.unwrap();
+    assert_eq!(finished.status, NgWordPurgeJobStatus::Completed);
+    assert_eq!(finished.scanned, 0);
+    assert_eq!(finished.deleted, 0);
+}
+
+#[tokio::test]
+async fn failed_case() {
+    let livestream_id: LivestreamId = Faker.fake();
+    let (mut service, job) = service_with_ng_words(&livestream_id, &["spam"]).await;
+    service
+        .mock_livestream_comment_repo
+        .expect_find_all_by_livestream_id_after_id_limit()
+        .returning(|_, _, _, _| Err(ReposError::TestError));
+
+    let result = purge_comments(&service, job.clone()).await;
+    assert!(result.is_err());
+
+    let stored = service
+        .ng_word_purge_job_store
+        .find(&job.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status, NgWordPurgeJobStatus::Failed);
+    assert!(stored.error.is_some());
+}
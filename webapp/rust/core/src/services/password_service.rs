@@ -0,0 +1,215 @@
+use crate::caches::{Cache, HaveUserCache};
+use crate::db::HaveDBPool;
+use crate::models::password_reset_token::CreatePasswordResetToken;
+use crate::models::session::SessionId;
+use crate::models::user::UserId;
+use crate::notifiers::password_reset_notifier::{HavePasswordResetNotifier, PasswordResetNotifier};
+use crate::repos::password_reset_token_repository::{
+    HavePasswordResetTokenRepository, PasswordResetTokenRepository,
+};
+use crate::repos::user_repository::{HaveUserRepository, UserRepository};
+use crate::services::{ServiceError, ServiceResult};
+use crate::storages::session_store::{HaveSessionStore, SessionStore};
+use async_trait::async_trait;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+#[cfg(test)]
+mod change_password;
+#[cfg(test)]
+mod confirm_reset;
+#[cfg(test)]
+mod request_reset;
+#[cfg(test)]
+mod validate_password;
+
+/// 再設定トークンの有効期間
+pub const PASSWORD_RESET_TOKEN_TTL_SECS: i64 = 30 * 60;
+/// 同じユーザーに再設定トークンを続けて発行しない間隔
+pub const PASSWORD_RESET_REQUEST_INTERVAL_SECS: i64 = 60;
+/// bcrypt は72バイトより後ろを無視するので、それより長いパスワードは受け付けない
+const MAX_PASSWORD_BYTES: usize = 72;
+const PASSWORD_RESET_TOKEN_BYTES: usize = 32;
+
+#[async_trait]
+pub trait PasswordService {
+    /// 現在のパスワードを確認してから変更し、current_session_id 以外のセッションを失効させる
+    async fn change_password(
+        &self,
+        user_id: &UserId,
+        current_session_id: &SessionId,
+        current_password: &str,
+        new_password: &str,
+    ) -> ServiceResult<()>;
+
+    /// 再設定トークンを発行して通知する
+    /// ユーザーの有無を推測されないよう、存在しないユーザー名でもエラーにしない
+    /// 直前に発行していた場合も、エラーにせず発行しない
+    async fn request_reset(&self, username: &str, now: i64) -> ServiceResult<()>;
+
+    /// トークンを使ってパスワードを再設定し、対象のユーザーIDを返す
+    /// 古いパスワードで作られたセッションはすべて失効させる
+    async fn confirm_reset(
+        &self,
+        token: &str,
+        new_password: &str,
+        now: i64,
+    ) -> ServiceResult<UserId>;
+}
+
+pub trait HavePasswordService {
+    type Service: PasswordService;
+
+    fn password_service(&self) -> &Self::Service;
+}
+
+pub trait PasswordServiceImpl:
+    Sync
+    + HaveDBPool
+    + HaveUserRepository
+    + HavePasswordResetTokenRepository
+    + HavePasswordResetNotifier
+    + HaveUserCache
+    + HaveSessionStore
+{
+}
+
+#[async_trait]
+impl<T: PasswordServiceImpl> PasswordService for T {
+    async fn change_password(
+        &self,
+        user_id: &UserId,
+        current_session_id: &SessionId,
+        current_password: &str,
+        new_password: &str,
+    ) -> ServiceResult<()> {
+        validate_password(new_password)?;
+
+        let mut conn = self.get_db_pool().acquire().await?;
+        let user = self
+            .user_repo()
+            .find(&mut conn, user_id)
+            .await?
+            .ok_or(ServiceError::NotFoundUser)?;
+
+        let verified = match &user.hashed_password {
+            Some(hashed_password) => bcrypt::verify(current_password, hashed_password)?,
+            None => false,
+        };
+        if !verified {
+            return Err(ServiceError::IncorrectPassword);
+        }
+
+        let hashed_password = self.user_repo().hash_password(new_password)?;
+        self.user_repo()
+            .update_password(&mut conn, user_id, &hashed_password)
+            .await?;
+        self.user_cache().remove(user_id);
+        self.session_store()
+            .delete_by_user_id_except(user_id, current_session_id)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn request_reset(&self, username: &str, now: i64) -> ServiceResult<()> {
+        let mut tx = self.get_db_pool().begin().await?;
+        let Some(user) = self.user_repo().find_by_name(&mut tx, username).await? else {
+            return Ok(());
+        };
+        // 通知を大量に送りつけられないよう、ユーザーごとに間隔を空ける
+        let latest = self
+            .password_reset_token_repo()
+            .find_latest_by_user_id(&mut tx, &user.id)
+            .await?;
+        if latest.is_some_and(|t| t.created_at > now - PASSWORD_RESET_REQUEST_INTERVAL_SECS) {
+            return Ok(());
+        }
+
+        let token = generate_token();
+        let expires_at = now + PASSWORD_RESET_TOKEN_TTL_SECS;
+        // 新しいトークンを発行したら古いものは使えなくする
+        self.password_reset_token_repo()
+            .delete_unused_by_user_id(&mut tx, &user.id)
+            .await?;
+        self.password_reset_token_repo()
+            .create(
+                &mut tx,
+                &CreatePasswordResetToken {
+                    user_id: user.id.clone(),
+                    token_hash: token_hash(&token),
+                    expires_at,
+                    created_at: now,
+                },
+            )
+            .await?;
+        tx.commit().await?;
+
+        self.password_reset_notifier()
+            .notify(&user, &token, expires_at)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn confirm_reset(
+        &self,
+        token: &str,
+        new_password: &str,
+        now: i64,
+    ) -> ServiceResult<UserId> {
+        validate_password(new_password)?;
+
+        let mut tx = self.get_db_pool().begin().await?;
+        let reset_token = self
+            .password_reset_token_repo()
+            .find_by_token_hash(&mut tx, &token_hash(token))
+            .await?
+            .filter(|t| t.used_at.is_none() && t.expires_at > now)
+            .ok_or(ServiceError::InvalidPasswordResetToken)?;
+
+        // 同じトークンが同時に使われても再設定できるのは1回だけにする
+        if !self
+            .password_reset_token_repo()
+            .mark_used(&mut tx, &reset_token.id, now)
+            .await?
+        {
+            return Err(ServiceError::InvalidPasswordResetToken);
+        }
+
+        let hashed_password = self.user_repo().hash_password(new_password)?;
+        self.user_repo()
+            .update_password(&mut tx, &reset_token.user_id, &hashed_password)
+            .await?;
+        tx.commit().await?;
+        self.user_cache().remove(&reset_token.user_id);
+        self.session_store()
+            .delete_by_user_id(&reset_token.user_id)
+            .await?;
+
+        Ok(reset_token.user_id)
+    }
+}
+
+pub fn validate_password(password: &str) -> ServiceResult<()> {
+    if password.is_empty() {
+        return Err(ServiceError::InvalidPassword("password must not be empty"));
+    }
+    if password.len() > MAX_PASSWORD_BYTES {
+        return Err(ServiceError::InvalidPassword(
+            "password must be at most 72 bytes",
+        ));
+    }
+
+    Ok(())
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; PASSWORD_RESET_TOKEN_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub fn token_hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,83 @@
+use crate::db::get_db_pool;
+use crate::models::session::{Session, SessionId};
+use crate::models::user::{User, UserId};
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::password_service::PasswordService;
+use crate::services::ServiceError;
+use crate::storages::session_store::SessionStore;
+use fake::{Fake, Faker};
+
+#[tokio::test]
+async fn changed_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let user_id: UserId = Faker.fake();
+    let mut user: User = Faker.fake();
+    user.id = user_id.clone();
+    user.hashed_password = Some(bcrypt::hash("old", 4).unwrap());
+
+    service
+        .mock_user_repo
+        .expect_find()
+        .returning(move |_, _| Ok(Some(user.clone())));
+    service
+        .mock_user_repo
+        .expect_hash_password()
+        .returning(|p| bcrypt::hash(p, 4).map_err(Into::into));
+    let uid = user_id.clone();
+    service
+        .mock_user_repo
+        .expect_update_password()
+        .withf(move |_, id, hashed| id == &uid && bcrypt::verify("new", hashed).unwrap())
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    for id in ["current", "other"] {
+        service
+            .session_store
+            .create(&Session {
+                id: SessionId::new(id.to_owned()),
+                user_id: user_id.clone(),
+                expires_at: i64::MAX,
+            })
+            .await
+            .unwrap();
+    }
+    let current = SessionId::new("current".to_owned());
+
+    let result = service
+        .change_password(&user_id, &current, "old", "new")
+        .await;
+    assert!(result.is_ok());
+
+    // 変更した本人のセッションだけが残る
+    let store = &service.session_store;
+    assert!(store.find(&current).await.unwrap().is_some());
+    assert!(store
+        .find(&SessionId::new("other".to_owned()))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn incorrect_password_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let user_id: UserId = Faker.fake();
+    let mut user: User = Faker.fake();
+    user.hashed_password = Some(bcrypt::hash("old", 4).unwrap());
+
+    service
+        .mock_user_repo
+        .expect_find()
+        .returning(move |_, _| Ok(Some(user.clone())));
+    service.mock_user_repo.expect_update_password().never();
+
+    let result = service
+        .change_password(&user_id, &Faker.fake(), "wrong", "new")
+        .await;
+    assert!(matches!(result, Err(ServiceError::IncorrectPassword)));
+}
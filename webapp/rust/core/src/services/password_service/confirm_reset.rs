@@ -0,0 +1,113 @@
+use crate::db::get_db_pool;
+use crate::models::password_reset_token::PasswordResetToken;
+use crate::models::session::{Session, SessionId};
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::password_service::{token_hash, PasswordService};
+use crate::services::ServiceError;
+use crate::storages::session_store::SessionStore;
+use fake::{Fake, Faker};
+
+fn reset_token(token: &str, expires_at: i64, used_at: Option<i64>) -> PasswordResetToken {
+    PasswordResetToken {
+        id: Faker.fake(),
+        user_id: Faker.fake(),
+        token_hash: token_hash(token),
+        expires_at,
+        used_at,
+        created_at: 0,
+    }
+}
+
+#[tokio::test]
+async fn reset_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let token = reset_token("token", 2_000, None);
+    let user_id = token.user_id.clone();
+
+    service
+        .mock_password_reset_token_repo
+        .expect_find_by_token_hash()
+        .withf(|_, hash| hash == token_hash("token"))
+        .returning(move |_, _| Ok(Some(token.clone())));
+    service
+        .mock_password_reset_token_repo
+        .expect_mark_used()
+        .returning(|_, _, _| Ok(true));
+    service
+        .mock_user_repo
+        .expect_hash_password()
+        .returning(|p| Ok(p.to_owned()));
+    let uid = user_id.clone();
+    service
+        .mock_user_repo
+        .expect_update_password()
+        .withf(move |_, id, hashed| id == &uid && hashed == "new")
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    let session_id = SessionId::new("session".to_owned());
+    service
+        .session_store
+        .create(&Session {
+            id: session_id.clone(),
+            user_id: user_id.clone(),
+            expires_at: i64::MAX,
+        })
+        .await
+        .unwrap();
+
+    let got = service.confirm_reset("token", "new", 1_000).await.unwrap();
+    assert_eq!(got, user_id);
+    // 古いパスワードで作られたセッションは失効している
+    assert!(service
+        .session_store
+        .find(&session_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn expired_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let token = reset_token("token", 1_000, None);
+    service
+        .mock_password_reset_token_repo
+        .expect_find_by_token_hash()
+        .returning(move |_, _| Ok(Some(token.clone())));
+    service.mock_user_repo.expect_update_password().never();
+
+    let result = service.confirm_reset("token", "new", 1_000).await;
+    assert!(matches!(
+        result,
+        Err(ServiceError::InvalidPasswordResetToken)
+    ));
+}
+
+#[tokio::test]
+async fn already_used_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let token = reset_token("token", 2_000, None);
+    service
+        .mock_password_reset_token_repo
+        .expect_find_by_token_hash()
+        .returning(move |_, _| Ok(Some(token.clone())));
+    // 先に別のリクエストが使用済みにした
+    service
+        .mock_password_reset_token_repo
+        .expect_mark_used()
+        .returning(|_, _, _| Ok(false));
+    service.mock_user_repo.expect_update_password().never();
+
+    let result = service.confirm_reset("token", "new", 1_000).await;
+    assert!(matches!(
+        result,
+        Err(ServiceError::InvalidPasswordResetToken)
+    ));
+}
@@ -0,0 +1,116 @@
+use crate::db::get_db_pool;
+use crate::models::password_reset_token::PasswordResetToken;
+use crate::models::user::User;
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::password_service::{
+    token_hash, PasswordService, PASSWORD_RESET_REQUEST_INTERVAL_SECS,
+    PASSWORD_RESET_TOKEN_TTL_SECS,
+};
+use fake::{Fake, Faker};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn notified_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let user: User = Faker.fake();
+    let now = 1_000;
+
+    let found = user.clone();
+    service
+        .mock_user_repo
+        .expect_find_by_name()
+        .returning(move |_, _| Ok(Some(found.clone())));
+    // 前回の発行から間隔が空いている
+    let mut latest: PasswordResetToken = Faker.fake();
+    latest.created_at = now - PASSWORD_RESET_REQUEST_INTERVAL_SECS;
+    service
+        .mock_password_reset_token_repo
+        .expect_find_latest_by_user_id()
+        .returning(move |_, _| Ok(Some(latest.clone())));
+    service
+        .mock_password_reset_token_repo
+        .expect_delete_unused_by_user_id()
+        .times(1)
+        .returning(|_, _| Ok(()));
+    let stored_hash = Arc::new(Mutex::new(String::new()));
+    let stored = stored_hash.clone();
+    service
+        .mock_password_reset_token_repo
+        .expect_create()
+        .withf(move |_, t| {
+            t.expires_at == now + PASSWORD_RESET_TOKEN_TTL_SECS && t.created_at == now
+        })
+        .returning(move |_, t| {
+            *stored.lock().unwrap() = t.token_hash.clone();
+            Ok(Faker.fake())
+        });
+    let sent_token = Arc::new(Mutex::new(String::new()));
+    let sent = sent_token.clone();
+    service
+        .mock_password_reset_notifier
+        .expect_notify()
+        .times(1)
+        .returning(move |_, token, _| {
+            *sent.lock().unwrap() = token.to_owned();
+            Ok(())
+        });
+
+    service.request_reset(user.name.inner(), now).await.unwrap();
+
+    // 通知したトークンそのものは保存しない
+    let sent_token = sent_token.lock().unwrap().clone();
+    let stored_hash = stored_hash.lock().unwrap().clone();
+    assert_ne!(sent_token, stored_hash);
+    assert_eq!(token_hash(&sent_token), stored_hash);
+}
+
+#[tokio::test]
+async fn unknown_user_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    service
+        .mock_user_repo
+        .expect_find_by_name()
+        .returning(|_, _| Ok(None));
+    service
+        .mock_password_reset_token_repo
+        .expect_create()
+        .never();
+    service.mock_password_reset_notifier.expect_notify().never();
+
+    let result = service.request_reset("nobody", 1_000).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn throttled_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let user: User = Faker.fake();
+    let now = 1_000;
+
+    let found = user.clone();
+    service
+        .mock_user_repo
+        .expect_find_by_name()
+        .returning(move |_, _| Ok(Some(found.clone())));
+    let mut latest: PasswordResetToken = Faker.fake();
+    latest.created_at = now - PASSWORD_RESET_REQUEST_INTERVAL_SECS + 1;
+    service
+        .mock_password_reset_token_repo
+        .expect_find_latest_by_user_id()
+        .returning(move |_, _| Ok(Some(latest.clone())));
+    service
+        .mock_password_reset_token_repo
+        .expect_create()
+        .never();
+    service.mock_password_reset_notifier.expect_notify().never();
+
+    // 続けて要求されてもエラーにはしない
+    let result = service.request_reset(user.name.inner(), now).await;
+    assert!(result.is_ok());
+}
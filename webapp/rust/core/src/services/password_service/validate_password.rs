@@ -0,0 +1,29 @@
+use crate::services::password_service::validate_password;
+use crate::services::ServiceError;
+
+#[test]
+fn valid_case() {
+    assert!(validate_password("s3cret").is_ok());
+    assert!(validate_password(&"a".repeat(72)).is_ok());
+}
+
+#[test]
+fn empty_case() {
+    assert!(matches!(
+        validate_password(""),
+        Err(ServiceError::InvalidPassword(_))
+    ));
+}
+
+#[test]
+fn too_long_case() {
+    assert!(matches!(
+        validate_password(&"a".repeat(73)),
+        Err(ServiceError::InvalidPassword(_))
+    ));
+    // バイト数で数える
+    assert!(matches!(
+        validate_password(&"あ".repeat(25)),
+        Err(ServiceError::InvalidPassword(_))
+    ));
+}
@@ -1,4 +1,5 @@
 use crate::db::HaveDBPool;
+use crate::models::cursor::{Cursor, CursorPage, PageCursor};
 use crate::models::livestream::LivestreamId;
 use crate::models::reaction::{CreateReaction, Reaction, ReactionId};
 use crate::repos::reaction_repository::{HaveReactionRepository, ReactionRepository};
@@ -14,6 +15,13 @@ pub trait ReactionService {
         livestream_id: &LivestreamId,
         limit: Option<i64>,
     ) -> ServiceResult<Vec<Reaction>>;
+
+    async fn find_page_by_livestream_id(
+        &self,
+        livestream_id: &LivestreamId,
+        cursor: Option<&PageCursor<Cursor>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<Reaction, Cursor>>;
 }
 
 pub trait HaveReactionService {
@@ -54,4 +62,37 @@ impl<T: ReactionServiceImpl> ReactionService for T {
 
         Ok(result)
     }
+
+    async fn find_page_by_livestream_id(
+        &self,
+        livestream_id: &LivestreamId,
+        cursor: Option<&PageCursor<Cursor>>,
+        limit: i64,
+    ) -> ServiceResult<CursorPage<Reaction, Cursor>> {
+        let mut conn = self.get_db_pool().acquire().await?;
+        let repo = self.reaction_repo();
+
+        let reactions = match cursor {
+            None => {
+                repo.find_all_by_livestream_id_limit(&mut conn, livestream_id, limit)
+                    .await?
+            }
+            Some(PageCursor::Before(c)) => {
+                repo.find_all_by_livestream_id_before_cursor(&mut conn, livestream_id, c, limit)
+                    .await?
+            }
+            Some(PageCursor::After(c)) => {
+                let mut reactions = repo
+                    .find_all_by_livestream_id_after_cursor(&mut conn, livestream_id, c, limit)
+                    .await?;
+                reactions.reverse();
+                reactions
+            }
+        };
+
+        Ok(CursorPage::build(reactions, cursor, limit, |r| Cursor {
+            created_at: r.created_at,
+            id: *r.id.inner(),
+        }))
+    }
 }
@@ -0,0 +1,103 @@
+use crate::models::session::{Session, SessionId};
+use crate::models::user::UserId;
+use crate::services::ServiceResult;
+use crate::storages::session_store::{HaveSessionStore, SessionStore};
+use async_trait::async_trait;
+
+#[cfg(test)]
+mod create;
+#[cfg(test)]
+mod find_active;
+#[cfg(test)]
+mod renew;
+#[cfg(test)]
+mod revoke_all_by_user_id;
+
+#[async_trait]
+pub trait SessionService {
+    /// 新しいセッションを発行する。ついでに期限切れのセッションを掃除する
+    async fn create(&self, user_id: &UserId, expires_at: i64) -> ServiceResult<Session>;
+
+    /// ログアウト済みや期限切れのセッションは None を返す
+    async fn find_active(&self, id: &SessionId, now: i64) -> ServiceResult<Option<Session>>;
+
+    /// 有効期限を延長する
+    async fn renew(&self, id: &SessionId, expires_at: i64) -> ServiceResult<()>;
+
+    async fn revoke(&self, id: &SessionId) -> ServiceResult<()>;
+
+    /// 指定したユーザーの全端末のセッションを失効させ、失効させた件数を返す
+    async fn revoke_all_by_user_id(&self, user_id: &UserId) -> ServiceResult<u64>;
+}
+
+pub trait HaveSessionService {
+    type Service: SessionService;
+
+    fn session_service(&self) -> &Self::Service;
+}
+
+pub trait SessionServiceImpl: Sync + HaveSessionStore {}
+
+#[async_trait]
+impl<T: SessionServiceImpl> SessionService for T {
+    async fn create(&self, user_id: &UserId, expires_at: i64) -> ServiceResult<Session> {
+        let now = chrono::Utc::now().timestamp();
+        self.session_store().delete_expired(now).await?;
+
+        let session = Session {
+            id: SessionId::new(uuid::Uuid::new_v4().to_string()),
+            user_id: user_id.clone(),
+            expires_at,
+        };
+        self.session_store().create(&session).await?;
+
+        Ok(session)
+    }
+
+    async fn find_active(&self, id: &SessionId, now: i64) -> ServiceResult<Option<Session>> {
+        let session = self.session_store().find(id).await?;
+
+        Ok(session.filter(|session| session.expires_at > now))
+    }
+
+    async fn renew(&self, id: &SessionId, expires_at: i64) -> ServiceResult<()> {
+        self.session_store()
+            .update_expires_at(id, expires_at)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, id: &SessionId) -> ServiceResult<()> {
+        self.session_store().delete(id).await?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_by_user_id(&self, user_id: &UserId) -> ServiceResult<u64> {
+        let count = self.session_store().delete_by_user_id(user_id).await?;
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::services::session_service::SessionServiceImpl;
+    use crate::storages::session_store::{HaveSessionStore, InMemorySessionStore};
+
+    #[derive(Default)]
+    pub struct InMemorySessionService {
+        pub store: InMemorySessionStore,
+    }
+
+    impl HaveSessionStore for InMemorySessionService {
+        type Store = InMemorySessionStore;
+
+        fn session_store(&self) -> &Self::Store {
+            &self.store
+        }
+    }
+
+    impl SessionServiceImpl for InMemorySessionService {}
+}
@@ -0,0 +1,46 @@
+use crate::models::session::Session;
+use crate::models::user::UserId;
+use crate::services::session_service::tests::InMemorySessionService;
+use crate::services::session_service::SessionService;
+use crate::storages::session_store::SessionStore;
+use fake::{Fake, Faker};
+
+#[tokio::test]
+async fn stored_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let expires_at = chrono::Utc::now().timestamp() + 3600;
+
+    let session = service.create(&user_id, expires_at).await.unwrap();
+    assert_eq!(session.user_id, user_id);
+    assert_eq!(session.expires_at, expires_at);
+
+    let got = service.store.find(&session.id).await.unwrap();
+    assert_eq!(got, Some(session));
+}
+
+#[tokio::test]
+async fn issues_distinct_ids() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let expires_at = chrono::Utc::now().timestamp() + 3600;
+
+    let first = service.create(&user_id, expires_at).await.unwrap();
+    let second = service.create(&user_id, expires_at).await.unwrap();
+    assert_ne!(first.id, second.id);
+}
+
+#[tokio::test]
+async fn purges_expired_sessions() {
+    let service = InMemorySessionService::default();
+    let mut expired: Session = Faker.fake();
+    expired.expires_at = chrono::Utc::now().timestamp() - 1;
+    service.store.create(&expired).await.unwrap();
+
+    let user_id: UserId = Faker.fake();
+    let expires_at = chrono::Utc::now().timestamp() + 3600;
+    service.create(&user_id, expires_at).await.unwrap();
+
+    let got = service.store.find(&expired.id).await.unwrap();
+    assert_eq!(got, None);
+}
@@ -0,0 +1,39 @@
+use crate::models::user::UserId;
+use crate::services::session_service::tests::InMemorySessionService;
+use crate::services::session_service::SessionService;
+use fake::{Fake, Faker};
+
+#[tokio::test]
+async fn active_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let now = chrono::Utc::now().timestamp();
+    let session = service.create(&user_id, now + 3600).await.unwrap();
+
+    let got = service.find_active(&session.id, now).await.unwrap();
+    assert_eq!(got, Some(session));
+}
+
+#[tokio::test]
+async fn revoked_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let now = chrono::Utc::now().timestamp();
+    let session = service.create(&user_id, now + 3600).await.unwrap();
+
+    service.revoke(&session.id).await.unwrap();
+
+    let got = service.find_active(&session.id, now).await.unwrap();
+    assert_eq!(got, None);
+}
+
+#[tokio::test]
+async fn expired_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let now = chrono::Utc::now().timestamp();
+    let session = service.create(&user_id, now + 3600).await.unwrap();
+
+    let got = service.find_active(&session.id, now + 3600).await.unwrap();
+    assert_eq!(got, None);
+}
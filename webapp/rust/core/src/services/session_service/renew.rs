@@ -0,0 +1,32 @@
+use crate::models::user::UserId;
+use crate::services::session_service::tests::InMemorySessionService;
+use crate::services::session_service::SessionService;
+use fake::{Fake, Faker};
+
+#[tokio::test]
+async fn extended_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let now = chrono::Utc::now().timestamp();
+    let session = service.create(&user_id, now + 60).await.unwrap();
+
+    service.renew(&session.id, now + 3600).await.unwrap();
+
+    let got = service.find_active(&session.id, now + 60).await.unwrap();
+    assert_eq!(got.map(|s| s.expires_at), Some(now + 3600));
+}
+
+#[tokio::test]
+async fn revoked_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+    let now = chrono::Utc::now().timestamp();
+    let session = service.create(&user_id, now + 60).await.unwrap();
+    service.revoke(&session.id).await.unwrap();
+
+    // 失効済みのセッションは延長しても復活しない
+    service.renew(&session.id, now + 3600).await.unwrap();
+
+    let got = service.find_active(&session.id, now).await.unwrap();
+    assert_eq!(got, None);
+}
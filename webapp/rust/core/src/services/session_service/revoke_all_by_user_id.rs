@@ -0,0 +1,37 @@
+use crate::models::user::UserId;
+use crate::services::session_service::tests::InMemorySessionService;
+use crate::services::session_service::SessionService;
+use fake::{Fake, Faker};
+
+#[tokio::test]
+async fn revokes_only_the_users_sessions() {
+    let service = InMemorySessionService::default();
+    let user_id = UserId::new(1);
+    let other_user_id = UserId::new(2);
+    let now = chrono::Utc::now().timestamp();
+
+    let mut sessions = Vec::new();
+    for _ in 0..3 {
+        sessions.push(service.create(&user_id, now + 3600).await.unwrap());
+    }
+    let other = service.create(&other_user_id, now + 3600).await.unwrap();
+
+    let count = service.revoke_all_by_user_id(&user_id).await.unwrap();
+    assert_eq!(count, 3);
+
+    for session in sessions {
+        let got = service.find_active(&session.id, now).await.unwrap();
+        assert_eq!(got, None);
+    }
+    let got = service.find_active(&other.id, now).await.unwrap();
+    assert_eq!(got, Some(other));
+}
+
+#[tokio::test]
+async fn no_sessions_case() {
+    let service = InMemorySessionService::default();
+    let user_id: UserId = Faker.fake();
+
+    let count = service.revoke_all_by_user_id(&user_id).await.unwrap();
+    assert_eq!(count, 0);
+}
@@ -1,3 +1,4 @@
+use crate::caches::{partition_cached, Cache, HaveTagCache};
 use crate::db::HaveDBPool;
 use crate::models::tag::{Tag, TagId};
 use crate::repos::tag_repository::{HaveTagRepository, TagRepository};
@@ -8,6 +9,7 @@ use async_trait::async_trait;
 pub trait TagService {
     async fn find(&self, tag_id: &TagId) -> ServiceResult<Tag>;
     async fn find_all(&self) -> ServiceResult<Vec<Tag>>;
+    async fn find_many(&self, tag_ids: &[TagId]) -> ServiceResult<Vec<Tag>>;
 }
 
 pub trait HaveTagService {
@@ -16,13 +18,18 @@ pub trait HaveTagService {
     fn tag_service(&self) -> &Self::Service;
 }
 
-pub trait TagServiceImpl: Sync + HaveDBPool + HaveTagRepository {}
+pub trait TagServiceImpl: Sync + HaveDBPool + HaveTagRepository + HaveTagCache {}
 
 #[async_trait]
 impl<T: TagServiceImpl> TagService for T {
     async fn find(&self, tag_id: &TagId) -> ServiceResult<Tag> {
+        if let Some(tag) = self.tag_cache().get(tag_id) {
+            return Ok(tag);
+        }
+
         let mut conn = self.get_db_pool().acquire().await?;
         let tag = self.tag_repo().find(&mut conn, tag_id).await?;
+        self.tag_cache().set(tag.id.clone(), tag.clone());
         Ok(tag)
     }
 
@@ -32,4 +39,20 @@ impl<T: TagServiceImpl> TagService for T {
 
         Ok(tags)
     }
+
+    async fn find_many(&self, tag_ids: &[TagId]) -> ServiceResult<Vec<Tag>> {
+        let (hits, misses) = partition_cached(self.tag_cache(), tag_ids);
+        let mut tags: Vec<Tag> = hits.into_iter().map(|(_, tag)| tag).collect();
+        if misses.is_empty() {
+            return Ok(tags);
+        }
+
+        let mut conn = self.get_db_pool().acquire().await?;
+        for tag in self.tag_repo().find_many(&mut conn, &misses).await? {
+            self.tag_cache().set(tag.id.clone(), tag.clone());
+            tags.push(tag);
+        }
+
+        Ok(tags)
+    }
 }
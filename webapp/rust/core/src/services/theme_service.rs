@@ -1,32 +1,128 @@
+#[cfg(test)]
+mod validate_update_theme;
+
+use crate::caches::{partition_cached, Cache, HaveThemeCache};
 use crate::db::HaveDBPool;
-use crate::models::theme::Theme;
+use crate::models::theme::{Theme, UpdateTheme, CHAT_COLOR_SCHEMES, FONTS};
 use crate::models::user::UserId;
 use crate::repos::theme_repository::{HaveThemeRepository, ThemeRepository};
-use crate::services::ServiceResult;
+use crate::services::{ServiceError, ServiceResult};
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait ThemeService {
     async fn find_by_user_id(&self, user_id: &UserId) -> ServiceResult<Theme>;
+    async fn find_many_by_user_ids(&self, user_ids: &[UserId]) -> ServiceResult<Vec<Theme>>;
+    async fn update(&self, user_id: &UserId, theme: &UpdateTheme) -> ServiceResult<Theme>;
 }
 
+/// themes.background_image_url は VARCHAR(2048)
+pub const MAX_BACKGROUND_IMAGE_URL_LEN: usize = 2048;
+
 pub trait HaveThemeService {
     type Service: ThemeService;
 
     fn theme_service(&self) -> &Self::Service;
 }
 
-pub trait ThemeServiceImpl: Sync + HaveDBPool + HaveThemeRepository {}
+pub trait ThemeServiceImpl: Sync + HaveDBPool + HaveThemeRepository + HaveThemeCache {}
 
 #[async_trait]
 impl<T: ThemeServiceImpl> ThemeService for T {
     async fn find_by_user_id(&self, user_id: &UserId) -> ServiceResult<Theme> {
+        if let Some(theme) = self.theme_cache().get(user_id) {
+            return Ok(theme);
+        }
+
         let mut conn = self.get_db_pool().acquire().await?;
         let theme = self
             .theme_repo()
             .find_by_user_id(&mut conn, user_id)
             .await?;
+        self.theme_cache().set(user_id.clone(), theme.clone());
 
         Ok(theme)
     }
+
+    async fn find_many_by_user_ids(&self, user_ids: &[UserId]) -> ServiceResult<Vec<Theme>> {
+        let (hits, misses) = partition_cached(self.theme_cache(), user_ids);
+        let mut themes: Vec<Theme> = hits.into_iter().map(|(_, theme)| theme).collect();
+        if misses.is_empty() {
+            return Ok(themes);
+        }
+
+        let mut conn = self.get_db_pool().acquire().await?;
+        for theme in self
+            .theme_repo()
+            .find_many_by_user_ids(&mut conn, &misses)
+            .await?
+        {
+            self.theme_cache().set(theme.user_id.clone(), theme.clone());
+            themes.push(theme);
+        }
+
+        Ok(themes)
+    }
+
+    async fn update(&self, user_id: &UserId, theme: &UpdateTheme) -> ServiceResult<Theme> {
+        validate_update_theme(theme)?;
+
+        {
+            let mut conn = self.get_db_pool().acquire().await?;
+            self.theme_repo().update(&mut conn, user_id, theme).await?;
+        }
+        self.theme_cache().remove(user_id);
+
+        self.find_by_user_id(user_id).await
+    }
+}
+
+pub fn validate_update_theme(theme: &UpdateTheme) -> ServiceResult<()> {
+    if let Some(accent_color) = &theme.accent_color {
+        if !is_hex_color(accent_color) {
+            return Err(ServiceError::InvalidTheme(
+                "accent_color must be a hex color like #rrggbb",
+            ));
+        }
+    }
+    // 空文字は背景画像の解除
+    if let Some(url) = theme
+        .background_image_url
+        .as_deref()
+        .filter(|u| !u.is_empty())
+    {
+        if url.len() > MAX_BACKGROUND_IMAGE_URL_LEN {
+            return Err(ServiceError::InvalidTheme(
+                "background_image_url must be at most 2048 bytes",
+            ));
+        }
+        let valid = url::Url::parse(url)
+            .map(|u| matches!(u.scheme(), "http" | "https") && u.host().is_some())
+            .unwrap_or(false);
+        if !valid {
+            return Err(ServiceError::InvalidTheme(
+                "background_image_url must be an http(s) URL",
+            ));
+        }
+    }
+    if let Some(font) = &theme.font {
+        if !FONTS.contains(&font.as_str()) {
+            return Err(ServiceError::InvalidTheme("unknown font"));
+        }
+    }
+    if let Some(chat_color_scheme) = &theme.chat_color_scheme {
+        if !CHAT_COLOR_SCHEMES.contains(&chat_color_scheme.as_str()) {
+            return Err(ServiceError::InvalidTheme("unknown chat_color_scheme"));
+        }
+    }
+
+    Ok(())
+}
+
+/// #rgb または #rrggbb
+fn is_hex_color(s: &str) -> bool {
+    match s.strip_prefix('#') {
+        Some(hex) => matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
 }
@@ -0,0 +1,81 @@
+use crate::models::theme::UpdateTheme;
+use crate::services::theme_service::{validate_update_theme, MAX_BACKGROUND_IMAGE_URL_LEN};
+use crate::services::ServiceError;
+
+fn assert_invalid(theme: UpdateTheme) {
+    assert!(matches!(
+        validate_update_theme(&theme),
+        Err(ServiceError::InvalidTheme(_))
+    ));
+}
+
+#[test]
+fn valid_case() {
+    let theme = UpdateTheme {
+        dark_mode: Some(true),
+        accent_color: Some("#1a2B3c".to_owned()),
+        background_image_url: Some("https://example.com/bg.png".to_owned()),
+        font: Some("monospace".to_owned()),
+        chat_color_scheme: Some("high-contrast".to_owned()),
+    };
+    assert!(validate_update_theme(&theme).is_ok());
+
+    let theme = UpdateTheme {
+        accent_color: Some("#abc".to_owned()),
+        background_image_url: Some(String::new()),
+        ..Default::default()
+    };
+    assert!(validate_update_theme(&theme).is_ok());
+}
+
+#[test]
+fn nothing_to_update_case() {
+    assert!(validate_update_theme(&UpdateTheme::default()).is_ok());
+}
+
+#[test]
+fn invalid_accent_color_case() {
+    for color in ["ff9900", "#ff99", "#gg9900", "#ff99000", "red"] {
+        assert_invalid(UpdateTheme {
+            accent_color: Some(color.to_owned()),
+            ..Default::default()
+        });
+    }
+}
+
+#[test]
+fn invalid_background_image_url_case() {
+    for url in [
+        "javascript:alert(1)",
+        "data:image/png;base64,AAAA",
+        "ftp://example.com/bg.png",
+        "/images/bg.png",
+        "https://",
+    ] {
+        assert_invalid(UpdateTheme {
+            background_image_url: Some(url.to_owned()),
+            ..Default::default()
+        });
+    }
+
+    let url = format!(
+        "https://example.com/{}",
+        "a".repeat(MAX_BACKGROUND_IMAGE_URL_LEN)
+    );
+    assert_invalid(UpdateTheme {
+        background_image_url: Some(url),
+        ..Default::default()
+    });
+}
+
+#[test]
+fn unknown_choice_case() {
+    assert_invalid(UpdateTheme {
+        font: Some("comic-sans".to_owned()),
+        ..Default::default()
+    });
+    assert_invalid(UpdateTheme {
+        chat_color_scheme: Some("rainbow".to_owned()),
+        ..Default::default()
+    });
+}
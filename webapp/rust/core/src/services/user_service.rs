@@ -1,14 +1,20 @@
 #[cfg(test)]
+mod commit_or_remove_record;
+#[cfg(test)]
 mod create;
+#[cfg(test)]
+mod validate_update_user;
 
+use crate::caches::{partition_cached, Cache, HaveIconHashCache, HaveThemeCache, HaveUserCache};
 use crate::commands::pdnsutil_command::{HavePDNSUtilCommand, PDNSUtilCommand};
-use crate::commands::CommandOutput;
 use crate::db::HaveDBPool;
-use crate::models::user::{CreateUser, User, UserId, UserName};
+use crate::models::user::{CreateUser, UpdateUser, User, UserId, UserName};
 use crate::repos::theme_repository::{HaveThemeRepository, ThemeRepository};
 use crate::repos::user_repository::{HaveUserRepository, UserRepository};
-use crate::services::ServiceResult;
+use crate::services::{ServiceError, ServiceResult};
+use crate::validation::username::HaveUsernamePolicy;
 use async_trait::async_trait;
+use std::future::Future;
 
 #[async_trait]
 pub trait UserService {
@@ -16,12 +22,23 @@ pub trait UserService {
         &self,
         user: &CreateUser,
         dark_mode: bool,
-        powerdns_subdomain_address: &str,
-    ) -> ServiceResult<(User, CommandOutput)>;
+        record_content: &str,
+    ) -> ServiceResult<User>;
     async fn find(&self, id: &UserId) -> ServiceResult<Option<User>>;
+    async fn find_many(&self, ids: &[UserId]) -> ServiceResult<Vec<User>>;
     async fn find_by_name(&self, name: &str) -> ServiceResult<Option<User>>;
+    /// プロフィールを部分更新し、更新後のユーザーを返す
+    async fn update(&self, id: &UserId, user: &UpdateUser) -> ServiceResult<User>;
 }
 
+/// users.display_name は VARCHAR(255)
+pub const MAX_DISPLAY_NAME_CHARS: usize = 255;
+pub const MAX_DESCRIPTION_CHARS: usize = 2000;
+
+/// DNSの更新は一時的に失敗することがあるので、登録時は何度か試す
+pub const DNS_PROVISION_ATTEMPTS: u32 = 3;
+const DNS_PROVISION_BACKOFF: std::time::Duration = std::time::Duration::from_millis(50);
+
 pub trait HaveUserService {
     type Service: UserService;
 
@@ -29,7 +46,15 @@ pub trait HaveUserService {
 }
 
 pub trait UserServiceImpl:
-    Sync + HaveDBPool + HaveUserRepository + HaveThemeRepository + HavePDNSUtilCommand
+    Sync
+    + HaveDBPool
+    + HaveUserRepository
+    + HaveThemeRepository
+    + HavePDNSUtilCommand
+    + HaveUserCache
+    + HaveThemeCache
+    + HaveIconHashCache
+    + HaveUsernamePolicy
 {
 }
 
@@ -39,48 +64,187 @@ impl<T: UserServiceImpl> UserService for T {
         &self,
         user: &CreateUser,
         dark_mode: bool,
-        powerdns_subdomain_address: &str,
-    ) -> ServiceResult<(User, CommandOutput)> {
+        record_content: &str,
+    ) -> ServiceResult<User> {
+        let dns_label = self.username_policy().validate(&user.name)?;
+        // 返すユーザーとDBに保存するハッシュを揃えるため、先に一度だけ計算する
+        let hashed_password = self.user_repo().hash_password(&user.password)?;
+
+        // 1. DBへの登録はコミットせずに保留する
         let mut tx = self.get_db_pool().begin().await?;
 
-        let user_id = self.user_repo().create(&mut tx, user).await?;
+        if self
+            .user_repo()
+            .exists_by_name_ignore_case(&mut tx, &user.name)
+            .await?
+        {
+            return Err(ServiceError::UsernameTaken);
+        }
+
+        let user_id = self
+            .user_repo()
+            .create(&mut tx, user, &hashed_password)
+            .await?;
 
         self.theme_repo()
             .create(&mut tx, &user_id, dark_mode)
             .await?;
 
-        let output = self
-            .pdnsutil_command()
-            .add_record(&user.name, powerdns_subdomain_address)
-            .await?;
+        // 2. DNSレコードを追加する。失敗したら tx を捨ててDBの登録も取り消す
+        add_record_with_retry(self, &dns_label, record_content).await?;
 
-        if output.success {
-            tx.commit().await?;
-        }
+        // 3. コミットに失敗したら、追加したDNSレコードを消して補償する
+        commit_or_remove_record(self, &dns_label, tx.commit()).await?;
 
-        let hashed_password = self.user_repo().hash_password(&user.password)?;
+        // 初期化前に同じIDで作られたユーザーの情報が残らないようにする
+        self.user_cache().remove(&user_id);
+        self.theme_cache().remove(&user_id);
+        self.icon_hash_cache().remove(&user_id);
 
-        Ok((
-            User {
-                id: user_id,
-                name: UserName::new(user.name.clone()),
-                display_name: Some(user.display_name.clone()),
-                description: Some(user.description.clone()),
-                hashed_password: Some(hashed_password),
-            },
-            output,
-        ))
+        Ok(User {
+            id: user_id,
+            name: UserName::new(user.name.clone()),
+            display_name: Some(user.display_name.clone()),
+            description: Some(user.description.clone()),
+            hashed_password: Some(hashed_password),
+        })
     }
 
     async fn find(&self, id: &UserId) -> ServiceResult<Option<User>> {
+        if let Some(user) = self.user_cache().get(id) {
+            return Ok(Some(user));
+        }
+
         let mut conn = self.get_db_pool().acquire().await?;
         let user = self.user_repo().find(&mut conn, id).await?;
+        if let Some(user) = &user {
+            self.user_cache().set(user.id.clone(), user.clone());
+        }
         Ok(user)
     }
 
+    async fn find_many(&self, ids: &[UserId]) -> ServiceResult<Vec<User>> {
+        let (hits, misses) = partition_cached(self.user_cache(), ids);
+        let mut users: Vec<User> = hits.into_iter().map(|(_, user)| user).collect();
+        if misses.is_empty() {
+            return Ok(users);
+        }
+
+        let mut conn = self.get_db_pool().acquire().await?;
+        for user in self.user_repo().find_many(&mut conn, &misses).await? {
+            self.user_cache().set(user.id.clone(), user.clone());
+            users.push(user);
+        }
+
+        Ok(users)
+    }
+
     async fn find_by_name(&self, name: &str) -> ServiceResult<Option<User>> {
         let mut conn = self.get_db_pool().acquire().await?;
         let user = self.user_repo().find_by_name(&mut conn, name).await?;
         Ok(user)
     }
+
+    async fn update(&self, id: &UserId, user: &UpdateUser) -> ServiceResult<User> {
+        validate_update_user(user)?;
+
+        {
+            let mut conn = self.get_db_pool().acquire().await?;
+            self.user_repo().update(&mut conn, id, user).await?;
+        }
+        self.user_cache().remove(id);
+
+        self.find(id).await?.ok_or(ServiceError::NotFoundUser)
+    }
+}
+
+/// DNSレコードを追加する。コマンドの起動失敗や異常終了は一時的なものとみなして再試行する
+async fn add_record_with_retry<T: UserServiceImpl>(
+    service: &T,
+    dns_label: &str,
+    record_content: &str,
+) -> ServiceResult<()> {
+    let mut detail = String::new();
+    for attempt in 1..=DNS_PROVISION_ATTEMPTS {
+        match service
+            .pdnsutil_command()
+            .add_record(dns_label, record_content)
+            .await
+        {
+            Ok(output) if output.success => return Ok(()),
+            Ok(output) => {
+                detail = format!(
+                    "stdout={} stderr={}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr),
+                );
+            }
+            Err(e) => detail = e.to_string(),
+        }
+        tracing::warn!(
+            "failed to add DNS record {} (attempt {}/{}): {}",
+            dns_label,
+            attempt,
+            DNS_PROVISION_ATTEMPTS,
+            detail
+        );
+        if attempt < DNS_PROVISION_ATTEMPTS {
+            tokio::time::sleep(DNS_PROVISION_BACKOFF * attempt).await;
+        }
+    }
+
+    Err(ServiceError::DnsProvisioningFailed {
+        attempts: DNS_PROVISION_ATTEMPTS,
+        detail,
+    })
+}
+
+/// コミットに失敗したら、先に追加したDNSレコードを消してからコミットのエラーを返す
+async fn commit_or_remove_record<T, F>(service: &T, dns_label: &str, commit: F) -> ServiceResult<()>
+where
+    T: UserServiceImpl,
+    F: Future<Output = Result<(), sqlx::Error>> + Send,
+{
+    if let Err(e) = commit.await {
+        remove_orphaned_record(service, dns_label).await;
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// 補償処理の失敗は元のエラーを隠さないようにログに残すだけにする
+async fn remove_orphaned_record<T: UserServiceImpl>(service: &T, dns_label: &str) {
+    match service.pdnsutil_command().delete_record(dns_label).await {
+        Ok(output) if output.success => {}
+        Ok(output) => tracing::error!(
+            "failed to remove orphaned DNS record {}: {}",
+            dns_label,
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(e) => tracing::error!("failed to remove orphaned DNS record {}: {}", dns_label, e),
+    }
+}
+
+pub fn validate_update_user(user: &UpdateUser) -> ServiceResult<()> {
+    if let Some(display_name) = &user.display_name {
+        if display_name.trim().is_empty() {
+            return Err(ServiceError::InvalidUserProfile(
+                "display_name must not be empty",
+            ));
+        }
+        if display_name.chars().count() > MAX_DISPLAY_NAME_CHARS {
+            return Err(ServiceError::InvalidUserProfile(
+                "display_name must be at most 255 characters",
+            ));
+        }
+    }
+    if let Some(description) = &user.description {
+        if description.chars().count() > MAX_DESCRIPTION_CHARS {
+            return Err(ServiceError::InvalidUserProfile(
+                "description must be at most 2000 characters",
+            ));
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,56 @@
+use crate::commands::{CommandError, CommandOutput};
+use crate::db::get_lazy_db_pool;
+use crate::repos::manager::tests::MockRepositoryManager;
+use crate::services::user_service::commit_or_remove_record;
+use crate::services::ServiceError;
+
+fn command_output(success: bool) -> CommandOutput {
+    CommandOutput {
+        success,
+        stdout: Vec::new(),
+        stderr: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn committed_case() {
+    let mut service = MockRepositoryManager::new(get_lazy_db_pool());
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .never();
+
+    commit_or_remove_record(&service, "alice", async { Ok(()) })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn commit_fail_case() {
+    let mut service = MockRepositoryManager::new(get_lazy_db_pool());
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .withf(|name| name == "alice")
+        .times(1)
+        .returning(|_| Ok(command_output(true)));
+
+    let result =
+        commit_or_remove_record(&service, "alice", async { Err(sqlx::Error::PoolClosed) }).await;
+    assert!(matches!(result, Err(ServiceError::SqlxError(_))));
+}
+
+#[tokio::test]
+async fn remove_record_fail_case() {
+    let mut service = MockRepositoryManager::new(get_lazy_db_pool());
+    service
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .times(1)
+        .returning(|_| Err(CommandError::TestError));
+
+    // 補償に失敗しても、返すのはコミットのエラー
+    let result =
+        commit_or_remove_record(&service, "alice", async { Err(sqlx::Error::PoolClosed) }).await;
+    assert!(matches!(result, Err(ServiceError::SqlxError(_))));
+}
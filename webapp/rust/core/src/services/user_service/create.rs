@@ -3,9 +3,53 @@ use crate::db::get_db_pool;
 use crate::models::user::{CreateUser, UserId};
 use crate::repos::manager::tests::MockRepositoryManager;
 use crate::repos::ReposError::TestError;
-use crate::services::user_service::UserService;
+use crate::services::user_service::{UserService, DNS_PROVISION_ATTEMPTS};
+use crate::services::ServiceError;
 use fake::{Fake, Faker};
 
+fn command_output(success: bool) -> CommandOutput {
+    CommandOutput {
+        success,
+        stdout: "stdout".as_bytes().to_vec(),
+        stderr: "stderr".as_bytes().to_vec(),
+    }
+}
+
+/// DNSの追加までは成功するように mock を用意する
+fn expect_db_steps(
+    service: &mut MockRepositoryManager,
+    user: &CreateUser,
+    dark_mode: bool,
+) -> UserId {
+    let expect_user_id: UserId = Faker.fake();
+    service
+        .mock_user_repo
+        .expect_exists_by_name_ignore_case()
+        .returning(|_, _| Ok(false));
+    service
+        .mock_user_repo
+        .expect_hash_password()
+        .returning(|p| Ok(format!("hashed:{p}")));
+
+    let got_user = user.clone();
+    let hashed = format!("hashed:{}", user.password);
+    let uid = expect_user_id.clone();
+    service
+        .mock_user_repo
+        .expect_create()
+        .withf(move |_, u, h| u == &got_user && h == hashed)
+        .returning(move |_, _, _| Ok(uid.clone()));
+
+    let uid = expect_user_id.clone();
+    service
+        .mock_theme_repo
+        .expect_create()
+        .withf(move |_, u, m| u == &uid && m == &dark_mode)
+        .returning(move |_, _, _| Ok(()));
+
+    expect_user_id
+}
+
 #[tokio::test]
 async fn user_repo_create_fail() {
     let db_pool = get_db_pool().await.unwrap();
@@ -15,12 +59,21 @@ async fn user_repo_create_fail() {
     let dark_mode: bool = Faker.fake();
     let domain: String = Faker.fake();
 
+    service
+        .mock_user_repo
+        .expect_exists_by_name_ignore_case()
+        .returning(|_, _| Ok(false));
+    service
+        .mock_user_repo
+        .expect_hash_password()
+        .returning(|p| Ok(p.to_owned()));
     let got_user = user.clone();
     service
         .mock_user_repo
         .expect_create()
-        .withf(move |_, u| u == &got_user)
-        .returning(move |_, _| Err(TestError));
+        .withf(move |_, u, _| u == &got_user)
+        .returning(move |_, _, _| Err(TestError));
+    service.mock_pdns_util_command.expect_add_record().never();
 
     let result = service.create(&user, dark_mode, &domain).await;
     assert!(result.is_err())
@@ -35,61 +88,59 @@ async fn theme_repo_create_fail() {
     let dark_mode: bool = Faker.fake();
     let domain: String = Faker.fake();
 
+    service
+        .mock_user_repo
+        .expect_exists_by_name_ignore_case()
+        .returning(|_, _| Ok(false));
+    service
+        .mock_user_repo
+        .expect_hash_password()
+        .returning(|p| Ok(p.to_owned()));
     let expect_user_id: UserId = Faker.fake();
-    let got_user = user.clone();
-    let uid = expect_user_id.clone();
     service
         .mock_user_repo
         .expect_create()
-        .withf(move |_, u| u == &got_user)
-        .returning(move |_, _| Ok(uid.clone()));
-
-    let uid = expect_user_id.clone();
+        .returning(move |_, _, _| Ok(expect_user_id.clone()));
     service
         .mock_theme_repo
         .expect_create()
-        .withf(move |_, u, m| u == &uid && m == &dark_mode)
         .returning(move |_, _, _| Err(TestError));
+    service.mock_pdns_util_command.expect_add_record().never();
 
     let result = service.create(&user, dark_mode, &domain).await;
     assert!(result.is_err())
 }
 
 #[tokio::test]
-async fn pdnsutil_command_add_record_error() {
+async fn pdnsutil_command_add_record_retry() {
     let db_pool = get_db_pool().await.unwrap();
 
     let mut service = MockRepositoryManager::new(db_pool);
     let user: CreateUser = Faker.fake();
     let dark_mode: bool = Faker.fake();
     let domain: String = Faker.fake();
+    let expect_user_id = expect_db_steps(&mut service, &user, dark_mode);
 
-    let expect_user_id: UserId = Faker.fake();
-    let got_user = user.clone();
-    let uid = expect_user_id.clone();
-    service
-        .mock_user_repo
-        .expect_create()
-        .withf(move |_, u| u == &got_user)
-        .returning(move |_, _| Ok(uid.clone()));
-
-    let uid = expect_user_id.clone();
+    // 1回目は起動に失敗し、2回目で成功する
+    let mut seq = mockall::Sequence::new();
     service
-        .mock_theme_repo
-        .expect_create()
-        .withf(move |_, u, m| u == &uid && m == &dark_mode)
-        .returning(move |_, _, _| Ok(()));
-
+        .mock_pdns_util_command
+        .expect_add_record()
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_, _| Err(CommandError::TestError));
     let got_user = user.clone();
     let dm = domain.clone();
     service
         .mock_pdns_util_command
         .expect_add_record()
-        .withf(move |name, d| name == got_user.name && d == dm)
-        .returning(move |_, _| Err(CommandError::TestError));
+        .withf(move |name, d| name == got_user.name.to_lowercase() && d == dm)
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_, _| Ok(command_output(true)));
 
-    let result = service.create(&user, dark_mode, &domain).await;
-    assert!(result.is_err())
+    let u = service.create(&user, dark_mode, &domain).await.unwrap();
+    assert_eq!(u.id, expect_user_id);
 }
 
 #[tokio::test]
@@ -100,45 +151,24 @@ async fn pdnsutil_command_add_record_fail() {
     let user: CreateUser = Faker.fake();
     let dark_mode: bool = Faker.fake();
     let domain: String = Faker.fake();
+    expect_db_steps(&mut service, &user, dark_mode);
 
-    let expect_user_id: UserId = Faker.fake();
-    let got_user = user.clone();
-    let uid = expect_user_id.clone();
-    service
-        .mock_user_repo
-        .expect_create()
-        .withf(move |_, u| u == &got_user)
-        .returning(move |_, _| Ok(uid.clone()));
-
-    let uid = expect_user_id.clone();
-    service
-        .mock_theme_repo
-        .expect_create()
-        .withf(move |_, u, m| u == &uid && m == &dark_mode)
-        .returning(move |_, _, _| Ok(()));
-
-    let got_user = user.clone();
-    let dm = domain.clone();
     service
         .mock_pdns_util_command
         .expect_add_record()
-        .withf(move |name, d| name == got_user.name && d == dm)
-        .returning(move |_, _| {
-            Ok(CommandOutput {
-                success: false,
-                stdout: "stdout".as_bytes().to_vec(),
-                stderr: "stderr".as_bytes().to_vec(),
-            })
-        });
-
+        .times(DNS_PROVISION_ATTEMPTS as usize)
+        .returning(|_, _| Ok(command_output(false)));
+    // DNSの追加に失敗したときはDBをロールバックするだけで、レコードは消さない
     service
-        .mock_user_repo
-        .expect_hash_password()
-        .returning(|_| Ok(Faker.fake()));
+        .mock_pdns_util_command
+        .expect_delete_record()
+        .never();
 
-    let (u, out) = service.create(&user, dark_mode, &domain).await.unwrap();
-    assert_eq!(out.success, false);
-    assert_eq!(u.id, expect_user_id)
+    let result = service.create(&user, dark_mode, &domain).await;
+    assert!(matches!(
+        result,
+        Err(ServiceError::DnsProvisioningFailed { attempts, .. }) if attempts == DNS_PROVISION_ATTEMPTS
+    ));
 }
 
 #[tokio::test]
@@ -149,43 +179,55 @@ async fn success_case() {
     let user: CreateUser = Faker.fake();
     let dark_mode: bool = Faker.fake();
     let domain: String = Faker.fake();
-
-    let expect_user_id: UserId = Faker.fake();
-    let got_user = user.clone();
-    let uid = expect_user_id.clone();
-    service
-        .mock_user_repo
-        .expect_create()
-        .withf(move |_, u| u == &got_user)
-        .returning(move |_, _| Ok(uid.clone()));
-
-    let uid = expect_user_id.clone();
-    service
-        .mock_theme_repo
-        .expect_create()
-        .withf(move |_, u, m| u == &uid && m == &dark_mode)
-        .returning(move |_, _, _| Ok(()));
+    let expect_user_id = expect_db_steps(&mut service, &user, dark_mode);
 
     let got_user = user.clone();
     let dm = domain.clone();
     service
         .mock_pdns_util_command
         .expect_add_record()
-        .withf(move |name, d| name == got_user.name && d == dm)
-        .returning(move |_, _| {
-            Ok(CommandOutput {
-                success: true,
-                stdout: "stdout".as_bytes().to_vec(),
-                stderr: "stderr".as_bytes().to_vec(),
-            })
-        });
+        .withf(move |name, d| name == got_user.name.to_lowercase() && d == dm)
+        .times(1)
+        .returning(|_, _| Ok(command_output(true)));
+
+    let u = service.create(&user, dark_mode, &domain).await.unwrap();
+    assert_eq!(u.id, expect_user_id);
+    // 返すユーザーのハッシュはDBに保存したものと同じ
+    assert_eq!(u.hashed_password, Some(format!("hashed:{}", user.password)));
+}
+
+#[tokio::test]
+async fn invalid_username_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let mut user: CreateUser = Faker.fake();
+    user.name = "pipe".to_owned();
+    service.mock_user_repo.expect_create().never();
+    service.mock_pdns_util_command.expect_add_record().never();
 
+    let result = service.create(&user, false, "127.0.0.1").await;
+    assert!(matches!(result, Err(ServiceError::Validation(_))));
+}
+
+#[tokio::test]
+async fn username_taken_case() {
+    let db_pool = get_db_pool().await.unwrap();
+
+    let mut service = MockRepositoryManager::new(db_pool);
+    let user: CreateUser = Faker.fake();
+    let name = user.name.clone();
     service
         .mock_user_repo
         .expect_hash_password()
-        .returning(|_| Ok(Faker.fake()));
+        .returning(|p| Ok(p.to_owned()));
+    service
+        .mock_user_repo
+        .expect_exists_by_name_ignore_case()
+        .withf(move |_, n| n == name)
+        .returning(|_, _| Ok(true));
+    service.mock_user_repo.expect_create().never();
 
-    let (u, out) = service.create(&user, dark_mode, &domain).await.unwrap();
-    assert_eq!(out.success, true);
-    assert_eq!(u.id, expect_user_id)
+    let result = service.create(&user, false, "127.0.0.1").await;
+    assert!(matches!(result, Err(ServiceError::UsernameTaken)));
 }
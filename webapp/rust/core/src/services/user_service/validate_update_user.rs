@@ -0,0 +1,52 @@
+use crate::models::user::UpdateUser;
+use crate::services::user_service::{
+    validate_update_user, MAX_DESCRIPTION_CHARS, MAX_DISPLAY_NAME_CHARS,
+};
+use crate::services::ServiceError;
+
+#[test]
+fn valid_case() {
+    let user = UpdateUser {
+        display_name: Some("あ".repeat(MAX_DISPLAY_NAME_CHARS)),
+        description: Some(String::new()),
+    };
+    assert!(validate_update_user(&user).is_ok());
+}
+
+#[test]
+fn nothing_to_update_case() {
+    assert!(validate_update_user(&UpdateUser::default()).is_ok());
+}
+
+#[test]
+fn blank_display_name_case() {
+    let user = UpdateUser {
+        display_name: Some("  ".to_owned()),
+        description: None,
+    };
+    assert!(matches!(
+        validate_update_user(&user),
+        Err(ServiceError::InvalidUserProfile(_))
+    ));
+}
+
+#[test]
+fn too_long_case() {
+    let user = UpdateUser {
+        display_name: Some("a".repeat(MAX_DISPLAY_NAME_CHARS + 1)),
+        description: None,
+    };
+    assert!(matches!(
+        validate_update_user(&user),
+        Err(ServiceError::InvalidUserProfile(_))
+    ));
+
+    let user = UpdateUser {
+        display_name: None,
+        description: Some("a".repeat(MAX_DESCRIPTION_CHARS + 1)),
+    };
+    assert!(matches!(
+        validate_update_user(&user),
+        Err(ServiceError::InvalidUserProfile(_))
+    ));
+}
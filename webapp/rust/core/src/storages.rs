@@ -0,0 +1,25 @@
+use std::io;
+use thiserror::Error;
+
+pub mod icon_storage;
+pub mod login_attempt_store;
+pub mod ng_word_purge_job_store;
+pub mod session_store;
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("invalid storage key: {0}")]
+    InvalidKey(String),
+    #[error("storage request failed: {0}")]
+    RequestError(String),
+    #[error("SQLx error: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("storage config error: {0}")]
+    ConfigError(String),
+    #[error("test error")]
+    TestError,
+}
+
+pub type StorageResult<T> = Result<T, StorageError>;
@@ -0,0 +1,19 @@
+use crate::storages::StorageResult;
+use async_trait::async_trait;
+
+/// アイコン画像の保存先
+/// キーは画像のSHA-256で、同じ内容の画像は同じキーに保存される
+#[cfg_attr(any(feature = "test", test), mockall::automock)]
+#[async_trait]
+pub trait IconStorage {
+    async fn put(&self, key: &str, image: &[u8]) -> StorageResult<()>;
+
+    async fn get(&self, key: &str) -> StorageResult<Option<Vec<u8>>>;
+}
+
+pub trait HaveIconStorage {
+    type Storage: Sync + IconStorage;
+
+    /// 外部ストレージを使わず、MySQLに画像を保存する場合は None
+    fn icon_storage(&self) -> Option<&Self::Storage>;
+}
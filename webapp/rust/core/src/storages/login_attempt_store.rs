@@ -0,0 +1,95 @@
+use crate::models::login_attempt::LoginAttempt;
+use crate::storages::StorageResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// ログイン失敗回数の保存先
+#[cfg_attr(any(feature = "test", test), mockall::automock)]
+#[async_trait]
+pub trait LoginAttemptStore {
+    async fn find(&self, key: &str) -> StorageResult<Option<LoginAttempt>>;
+
+    /// 失敗回数を1増やし、更新後の記録を返す
+    /// 最後の失敗が window_start より前なら1から数え直す
+    async fn record_failure(
+        &self,
+        key: &str,
+        now: i64,
+        window_start: i64,
+    ) -> StorageResult<LoginAttempt>;
+
+    async fn lock(&self, key: &str, locked_until: i64) -> StorageResult<()>;
+
+    async fn delete(&self, key: &str) -> StorageResult<()>;
+
+    /// 最後の失敗が last_failed_before より前で、ロックも解けている記録を消す
+    async fn delete_expired(&self, last_failed_before: i64, now: i64) -> StorageResult<u64>;
+}
+
+pub trait HaveLoginAttemptStore {
+    type Store: Sync + LoginAttemptStore;
+
+    fn login_attempt_store(&self) -> &Self::Store;
+}
+
+/// プロセス内に失敗回数を保持する実装。テストや単一プロセスでの動作確認で使う
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryLoginAttemptStore {
+    attempts: Arc<Mutex<HashMap<String, LoginAttempt>>>,
+}
+
+#[async_trait]
+impl LoginAttemptStore for InMemoryLoginAttemptStore {
+    async fn find(&self, key: &str) -> StorageResult<Option<LoginAttempt>> {
+        Ok(self.attempts.lock().unwrap().get(key).cloned())
+    }
+
+    async fn record_failure(
+        &self,
+        key: &str,
+        now: i64,
+        window_start: i64,
+    ) -> StorageResult<LoginAttempt> {
+        let mut attempts = self.attempts.lock().unwrap();
+        let attempt = attempts
+            .entry(key.to_owned())
+            .or_insert_with(|| LoginAttempt {
+                attempt_key: key.to_owned(),
+                failures: 0,
+                last_failed_at: now,
+                locked_until: 0,
+            });
+        if attempt.last_failed_at < window_start {
+            attempt.failures = 0;
+        }
+        attempt.failures += 1;
+        attempt.last_failed_at = now;
+
+        Ok(attempt.clone())
+    }
+
+    async fn lock(&self, key: &str, locked_until: i64) -> StorageResult<()> {
+        if let Some(attempt) = self.attempts.lock().unwrap().get_mut(key) {
+            attempt.locked_until = locked_until;
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> StorageResult<()> {
+        self.attempts.lock().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, last_failed_before: i64, now: i64) -> StorageResult<u64> {
+        let mut attempts = self.attempts.lock().unwrap();
+        let before = attempts.len();
+        attempts.retain(|_, attempt| {
+            attempt.last_failed_at >= last_failed_before || attempt.locked_until > now
+        });
+
+        Ok((before - attempts.len()) as u64)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::models::livestream::LivestreamId;
+use crate::models::ng_word::NgWordId;
+use crate::models::ng_word_purge_job::{NgWordPurgeJob, NgWordPurgeJobId, NgWordPurgeJobStatus};
+use crate::storages::StorageResult;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod create;
+
+/// 保持しておく終了済みのジョブの件数
+pub const MAX_FINISHED_JOBS: usize = 10_000;
+
+/// NGワードを登録したときの、過去のコメントを消すジョブの進み具合の保存先
+#[async_trait]
+pub trait NgWordPurgeJobStore {
+    /// 待機中のジョブを作る
+    async fn create(
+        &self,
+        livestream_id: &LivestreamId,
+        word_id: &NgWordId,
+    ) -> StorageResult<NgWordPurgeJob>;
+
+    async fn find(&self, id: &NgWordPurgeJobId) -> StorageResult<Option<NgWordPurgeJob>>;
+
+    async fn update(&self, job: &NgWordPurgeJob) -> StorageResult<()>;
+}
+
+pub trait HaveNgWordPurgeJobStore {
+    type Store: Sync + NgWordPurgeJobStore;
+
+    fn ng_word_purge_job_store(&self) -> &Self::Store;
+}
+
+/// プロセス内にジョブを保持する実装
+/// ジョブはプロセス内で動くので、再起動したら進み具合も一緒に失われてよい
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryNgWordPurgeJobStore {
+    jobs: Arc<Mutex<BTreeMap<i64, NgWordPurgeJob>>>,
+    last_id: Arc<AtomicI64>,
+}
+
+#[async_trait]
+impl NgWordPurgeJobStore for InMemoryNgWordPurgeJobStore {
+    async fn create(
+        &self,
+        livestream_id: &LivestreamId,
+        word_id: &NgWordId,
+    ) -> StorageResult<NgWordPurgeJob> {
+        let mut jobs = self.jobs.lock().unwrap();
+
+        // 古いものから、終了済みのジョブを上限を超えた分だけ捨てる
+        let finished: Vec<i64> = jobs
+            .iter()
+            .filter(|(_, job)| job.status.is_finished())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in finished
+            .iter()
+            .take(finished.len().saturating_sub(MAX_FINISHED_JOBS - 1))
+        {
+            jobs.remove(id);
+        }
+
+        let id = self.last_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let job = NgWordPurgeJob {
+            id: NgWordPurgeJobId::new(id),
+            livestream_id: livestream_id.clone(),
+            word_id: word_id.clone(),
+            status: NgWordPurgeJobStatus::Queued,
+            scanned: 0,
+            deleted: 0,
+            error: None,
+        };
+        jobs.insert(id, job.clone());
+
+        Ok(job)
+    }
+
+    async fn find(&self, id: &NgWordPurgeJobId) -> StorageResult<Option<NgWordPurgeJob>> {
+        Ok(self.jobs.lock().unwrap().get(id.inner()).cloned())
+    }
+
+    async fn update(&self, job: &NgWordPurgeJob) -> StorageResult<()> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .insert(*job.id.inner(), job.clone());
+
+        Ok(())
+    }
+}
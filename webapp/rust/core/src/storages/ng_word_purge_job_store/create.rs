@@ -0,0 +1,64 @@
+use crate::models::livestream::LivestreamId;
+use crate::models::ng_word::NgWordId;
+use crate::models::ng_word_purge_job::NgWordPurgeJobStatus;
+use crate::storages::ng_word_purge_job_store::{
+    InMemoryNgWordPurgeJobStore, NgWordPurgeJobStore, MAX_FINISHED_JOBS,
+};
+
+#[tokio::test]
+async fn create_and_update() {
+    let store = InMemoryNgWordPurgeJobStore::default();
+    let livestream_id = LivestreamId::new(1);
+
+    let first = store
+        .create(&livestream_id, &NgWordId::new(10))
+        .await
+        .unwrap();
+    let second = store
+        .create(&livestream_id, &NgWordId::new(11))
+        .await
+        .unwrap();
+    assert_ne!(first.id, second.id);
+    assert_eq!(first.status, NgWordPurgeJobStatus::Queued);
+
+    let mut job = first.clone();
+    job.status = NgWordPurgeJobStatus::Completed;
+    job.scanned = 3;
+    job.deleted = 1;
+    store.update(&job).await.unwrap();
+
+    assert_eq!(store.find(&first.id).await.unwrap(), Some(job));
+    assert_eq!(store.find(&second.id).await.unwrap(), Some(second));
+}
+
+#[tokio::test]
+async fn discard_old_finished_jobs() {
+    let store = InMemoryNgWordPurgeJobStore::default();
+    let livestream_id = LivestreamId::new(1);
+
+    let running = store
+        .create(&livestream_id, &NgWordId::new(1))
+        .await
+        .unwrap();
+    let mut finished = Vec::new();
+    for i in 0..MAX_FINISHED_JOBS {
+        let mut job = store
+            .create(&livestream_id, &NgWordId::new(i as i64))
+            .await
+            .unwrap();
+        job.status = NgWordPurgeJobStatus::Completed;
+        store.update(&job).await.unwrap();
+        finished.push(job);
+    }
+
+    let latest = store
+        .create(&livestream_id, &NgWordId::new(0))
+        .await
+        .unwrap();
+
+    // 実行中のジョブは残し、最も古い終了済みのジョブから捨てる
+    assert!(store.find(&running.id).await.unwrap().is_some());
+    assert!(store.find(&finished[0].id).await.unwrap().is_none());
+    assert!(store.find(&finished[1].id).await.unwrap().is_some());
+    assert!(store.find(&latest.id).await.unwrap().is_some());
+}
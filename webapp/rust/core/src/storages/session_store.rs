@@ -0,0 +1,103 @@
+use crate::models::session::{Session, SessionId};
+use crate::models::user::UserId;
+use crate::storages::StorageResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// ログインセッションの保存先
+/// 削除したセッションは以後のリクエストで失効済みとして扱われる
+#[cfg_attr(any(feature = "test", test), mockall::automock)]
+#[async_trait]
+pub trait SessionStore {
+    async fn create(&self, session: &Session) -> StorageResult<()>;
+
+    async fn find(&self, id: &SessionId) -> StorageResult<Option<Session>>;
+
+    async fn update_expires_at(&self, id: &SessionId, expires_at: i64) -> StorageResult<()>;
+
+    async fn delete(&self, id: &SessionId) -> StorageResult<()>;
+
+    /// 指定したユーザーのセッションをすべて削除し、削除した件数を返す
+    async fn delete_by_user_id(&self, user_id: &UserId) -> StorageResult<u64>;
+
+    /// 指定したユーザーの keep 以外のセッションを削除し、削除した件数を返す
+    async fn delete_by_user_id_except(
+        &self,
+        user_id: &UserId,
+        keep: &SessionId,
+    ) -> StorageResult<u64>;
+
+    /// 有効期限が now 以前のセッションを削除し、削除した件数を返す
+    async fn delete_expired(&self, now: i64) -> StorageResult<u64>;
+}
+
+pub trait HaveSessionStore {
+    type Store: Sync + SessionStore;
+
+    fn session_store(&self) -> &Self::Store;
+}
+
+/// プロセス内にセッションを保持する実装。テストや単一プロセスでの動作確認で使う
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySessionStore {
+    sessions: Arc<Mutex<HashMap<SessionId, Session>>>,
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn create(&self, session: &Session) -> StorageResult<()> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.id.clone(), session.clone());
+
+        Ok(())
+    }
+
+    async fn find(&self, id: &SessionId) -> StorageResult<Option<Session>> {
+        Ok(self.sessions.lock().unwrap().get(id).cloned())
+    }
+
+    async fn update_expires_at(&self, id: &SessionId, expires_at: i64) -> StorageResult<()> {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(id) {
+            session.expires_at = expires_at;
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &SessionId) -> StorageResult<()> {
+        self.sessions.lock().unwrap().remove(id);
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(&self, user_id: &UserId) -> StorageResult<u64> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let before = sessions.len();
+        sessions.retain(|_, session| &session.user_id != user_id);
+
+        Ok((before - sessions.len()) as u64)
+    }
+
+    async fn delete_by_user_id_except(
+        &self,
+        user_id: &UserId,
+        keep: &SessionId,
+    ) -> StorageResult<u64> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let before = sessions.len();
+        sessions.retain(|id, session| &session.user_id != user_id || id == keep);
+
+        Ok((before - sessions.len()) as u64)
+    }
+
+    async fn delete_expired(&self, now: i64) -> StorageResult<u64> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let before = sessions.len();
+        sessions.retain(|_, session| session.expires_at > now);
+
+        Ok((before - sessions.len()) as u64)
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+use std::fmt;
+
+pub mod username;
+
+/// 入力項目ごとの検証エラー
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    /// クライアントが判定に使う機械向けのコード
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// 検証で見つかったエラーを全てまとめたもの
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors(Vec<FieldError>);
+
+impl ValidationErrors {
+    pub fn push(&mut self, field: &'static str, code: &'static str, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field,
+            code,
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.0
+    }
+
+    /// エラーが無ければ Ok にする
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self
+            .0
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect();
+        write!(f, "validation failed: {}", messages.join(", "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
@@ -0,0 +1,181 @@
+use crate::validation::ValidationErrors;
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod dns_label;
+#[cfg(test)]
+mod record_label;
+#[cfg(test)]
+mod validate;
+
+/// ユーザー名は <ユーザー名>.<ゾーン> のDNSラベルになる
+pub const MAX_DNS_LABEL_LEN: usize = 63;
+
+pub const RESERVED_USERNAMES_ENV_KEY: &str = "ISUCON13_RESERVED_USERNAMES";
+
+pub const DEFAULT_RESERVED_USERNAMES: &[&str] = &[
+    "admin", "api", "assets", "dns", "ftp", "isupipe", "mail", "ns", "ns1", "ns2", "pipe", "root",
+    "smtp", "static", "support", "system", "www",
+];
+
+const FIELD: &str = "name";
+
+/// 大文字と小文字を区別せずに同じ名前のユーザーがいるときのエラー
+pub fn taken_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::default();
+    errors.push(FIELD, "taken", "name is already taken");
+    errors
+}
+
+/// ユーザー名の検証ルール
+#[derive(Debug, Clone)]
+pub struct UsernamePolicy {
+    /// 小文字で保持する
+    reserved: HashSet<String>,
+}
+
+impl Default for UsernamePolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESERVED_USERNAMES.iter().copied())
+    }
+}
+
+impl UsernamePolicy {
+    pub fn new<I, S>(reserved: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            reserved: reserved
+                .into_iter()
+                .map(|name| name.as_ref().trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        }
+    }
+
+    /// ISUCON13_RESERVED_USERNAMES (カンマ区切り) があればデフォルトの予約名を置き換える
+    pub fn from_env() -> Self {
+        match std::env::var(RESERVED_USERNAMES_ENV_KEY) {
+            Ok(names) => Self::new(names.split(',')),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn is_reserved(&self, name: &str) -> bool {
+        self.reserved.contains(&name.to_lowercase())
+    }
+
+    /// 検証して、DNSに登録するラベルを返す
+    pub fn validate(&self, name: &str) -> Result<String, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if name.is_empty() {
+            errors.push(FIELD, "required", "name is required");
+            return Err(errors);
+        }
+
+        let label = if name.is_ascii() {
+            validate_ascii_label(name, false, &mut errors);
+            name.to_ascii_lowercase()
+        } else {
+            match idn_to_label(name) {
+                Some(label) => {
+                    validate_ascii_label(&label, true, &mut errors);
+                    label
+                }
+                None => {
+                    errors.push(
+                        FIELD,
+                        "invalid_idn",
+                        "name must be a valid internationalized domain label in lowercase NFC form",
+                    );
+                    return Err(errors);
+                }
+            }
+        };
+
+        if self.is_reserved(name) || self.is_reserved(&label) {
+            errors.push(
+                FIELD,
+                "reserved",
+                format!("the username '{name}' is reserved"),
+            );
+        }
+
+        errors.into_result().map(|_| label)
+    }
+}
+
+pub trait HaveUsernamePolicy {
+    fn username_policy(&self) -> &UsernamePolicy;
+}
+
+/// ユーザー名に対応するDNSラベル。検証済みのユーザー名なら必ず Some になる
+pub fn dns_label(name: &str) -> Option<String> {
+    if name.is_ascii() {
+        Some(name.to_ascii_lowercase())
+    } else {
+        idn_to_label(name)
+    }
+}
+
+/// 登録済みのユーザーのレコード名。レコードを消すときと突き合わせるときはこれを使う
+/// 検証導入前に登録されたユーザー名は、小文字にしたものがそのままレコード名になっている
+pub fn record_label(name: &str) -> String {
+    dns_label(name).unwrap_or_else(|| name.to_lowercase())
+}
+
+/// 国際化ラベルを xn-- 形式に変換する
+/// 大文字や非正規化の文字列は別の名前と同じラベルになりうるので受け付けない
+fn idn_to_label(name: &str) -> Option<String> {
+    if name.contains('.') || name.starts_with('-') || name.ends_with('-') {
+        return None;
+    }
+    // 長さやハイフンの位置は ASCII のラベルと同じく validate_ascii_label で検証する
+    let label = idna::Config::default()
+        .use_std3_ascii_rules(true)
+        .to_ascii(name)
+        .ok()?;
+    let (unicode, result) = idna::domain_to_unicode(&label);
+    if result.is_err() || unicode != name {
+        return None;
+    }
+
+    Some(label)
+}
+
+fn validate_ascii_label(label: &str, from_idn: bool, errors: &mut ValidationErrors) {
+    if label.len() > MAX_DNS_LABEL_LEN {
+        errors.push(
+            FIELD,
+            "too_long",
+            format!("name must be at most {MAX_DNS_LABEL_LEN} bytes as a DNS label"),
+        );
+    }
+    if !label
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    {
+        errors.push(
+            FIELD,
+            "invalid_characters",
+            "name may only contain letters, digits and hyphens",
+        );
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        errors.push(
+            FIELD,
+            "invalid_hyphen",
+            "name must not start or end with a hyphen",
+        );
+    } else if !from_idn && label.get(2..4) == Some("--") {
+        // ab--xxx の形は xn-- などの拡張用に予約されている
+        // ASCII で xn-- を直接指定されると国際化ユーザー名とラベルが衝突する
+        errors.push(
+            FIELD,
+            "invalid_hyphen",
+            "name must not contain hyphens in the third and fourth positions",
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use crate::validation::username::dns_label;
+
+#[test]
+fn ascii_case() {
+    assert_eq!(dns_label("Test001").unwrap(), "test001");
+}
+
+#[test]
+fn idn_case() {
+    assert_eq!(dns_label("bücher").unwrap(), "xn--bcher-kva");
+    assert!(dns_label("bücher.example").is_none());
+}
@@ -0,0 +1,13 @@
+use crate::validation::username::record_label;
+
+#[test]
+fn valid_name_case() {
+    assert_eq!(record_label("Test001"), "test001");
+    assert_eq!(record_label("bücher"), "xn--bcher-kva");
+}
+
+#[test]
+fn legacy_name_case() {
+    // 検証導入前の名前は小文字にしてそのまま使う
+    assert_eq!(record_label("Bücher.Example"), "bücher.example");
+}
@@ -0,0 +1,62 @@
+use crate::validation::username::{UsernamePolicy, MAX_DNS_LABEL_LEN};
+
+fn codes(policy: &UsernamePolicy, name: &str) -> Vec<&'static str> {
+    match policy.validate(name) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.errors().iter().map(|e| e.code).collect(),
+    }
+}
+
+#[test]
+fn valid_case() {
+    let policy = UsernamePolicy::default();
+    assert_eq!(policy.validate("ayamazaki0").unwrap(), "ayamazaki0");
+    assert_eq!(policy.validate("AbC-123").unwrap(), "abc-123");
+    assert_eq!(
+        policy.validate(&"a".repeat(MAX_DNS_LABEL_LEN)).unwrap(),
+        "a".repeat(MAX_DNS_LABEL_LEN)
+    );
+}
+
+#[test]
+fn idn_case() {
+    let policy = UsernamePolicy::default();
+    assert_eq!(policy.validate("bücher").unwrap(), "xn--bcher-kva");
+
+    // 大文字の国際化ユーザー名は小文字のものとラベルが衝突するので拒否する
+    assert_eq!(codes(&policy, "Müller"), vec!["invalid_idn"]);
+    assert_eq!(codes(&policy, "bücher.example"), vec!["invalid_idn"]);
+    assert_eq!(codes(&policy, "-bücher"), vec!["invalid_idn"]);
+    assert_eq!(codes(&policy, &"aü".repeat(40)), vec!["too_long"]);
+}
+
+#[test]
+fn invalid_format_case() {
+    let policy = UsernamePolicy::default();
+    assert_eq!(codes(&policy, ""), vec!["required"]);
+    assert_eq!(
+        codes(&policy, &"a".repeat(MAX_DNS_LABEL_LEN + 1)),
+        vec!["too_long"]
+    );
+    assert_eq!(codes(&policy, "foo_bar"), vec!["invalid_characters"]);
+    assert_eq!(codes(&policy, "foo.bar"), vec!["invalid_characters"]);
+    assert_eq!(codes(&policy, "-foo"), vec!["invalid_hyphen"]);
+    assert_eq!(codes(&policy, "foo-"), vec!["invalid_hyphen"]);
+    assert_eq!(codes(&policy, "xn--bcher-kva"), vec!["invalid_hyphen"]);
+    assert_eq!(
+        codes(&policy, "-foo_"),
+        vec!["invalid_characters", "invalid_hyphen"]
+    );
+}
+
+#[test]
+fn reserved_case() {
+    let policy = UsernamePolicy::default();
+    assert_eq!(codes(&policy, "pipe"), vec!["reserved"]);
+    assert_eq!(codes(&policy, "WWW"), vec!["reserved"]);
+
+    let policy = UsernamePolicy::new(["Foo", " bar ", ""]);
+    assert_eq!(codes(&policy, "foo"), vec!["reserved"]);
+    assert_eq!(codes(&policy, "bar"), vec!["reserved"]);
+    assert!(codes(&policy, "pipe").is_empty());
+}
@@ -0,0 +1,32 @@
+//! MySQLに保存されているアイコン画像を、ISUCON13_ICON_STORAGE で指定した保存先に移す
+//! 移した行は image を NULL にするので、途中で止まっても再実行すれば続きから移せる
+
+use isupipe_core::db::build_database_connection_options;
+use isupipe_core::services::icon_service::IconService;
+use isupipe_infra::caches::CacheManagerInfra;
+use isupipe_infra::services::icon_service::IconServiceInfra;
+use isupipe_infra::storages::IconStorageInfra;
+
+const BATCH_SIZE: i64 = 100;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::var_os("RUST_LOG").is_none() {
+        std::env::set_var("RUST_LOG", "info");
+    }
+    tracing_subscriber::fmt::init();
+
+    let Some(icon_storage) = IconStorageInfra::from_env()? else {
+        return Err("ISUCON13_ICON_STORAGE must be filesystem or s3 to migrate icons".into());
+    };
+
+    let pool = sqlx::mysql::MySqlPoolOptions::new()
+        .connect_with(build_database_connection_options())
+        .await?;
+
+    let service = IconServiceInfra::new(pool, CacheManagerInfra::new(), Some(icon_storage));
+    let moved = service.move_blobs_to_storage(BATCH_SIZE).await?;
+    tracing::info!("moved {} icon images out of MySQL", moved);
+
+    Ok(())
+}
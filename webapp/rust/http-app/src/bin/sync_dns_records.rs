@@ -0,0 +1,61 @@
+//! users テーブルに合わせて、ユーザーのサブドメインのレコードを追加・更新・削除する
+//! リストアや initialize の後にゾーンとずれたときに使う
+//! --dry-run を付けると差分を表示するだけで何も変更しない
+
+use isupipe_core::commands::pdnsutil_command::SubdomainRecordConfig;
+use isupipe_core::db::build_database_connection_options;
+use isupipe_core::services::dns_record_service::DnsRecordService;
+use isupipe_core::validation::username::UsernamePolicy;
+use isupipe_infra::commands::pdnsutil_command::PDNSUtilCommandInfra;
+use isupipe_infra::services::dns_record_service::DnsRecordServiceInfra;
+use std::sync::Arc;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::var_os("RUST_LOG").is_none() {
+        std::env::set_var("RUST_LOG", "info");
+    }
+    tracing_subscriber::fmt::init();
+
+    let mut dry_run = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            _ => return Err(format!("unknown argument: {}", arg).into()),
+        }
+    }
+
+    let subdomain_record = SubdomainRecordConfig::from_env()?;
+    let pdnsutil_command = PDNSUtilCommandInfra::from_env(subdomain_record.clone())?;
+
+    let pool = sqlx::mysql::MySqlPoolOptions::new()
+        .connect_with(build_database_connection_options())
+        .await?;
+
+    let service = DnsRecordServiceInfra::new(
+        pool,
+        pdnsutil_command,
+        subdomain_record.clone(),
+        Arc::new(UsernamePolicy::from_env()),
+    );
+    let diff = service.reconcile(dry_run).await?;
+
+    for label in &diff.missing {
+        println!("+ {}.{}", label, subdomain_record.zone());
+    }
+    for label in &diff.changed {
+        println!("~ {}.{}", label, subdomain_record.zone());
+    }
+    for label in &diff.orphaned {
+        println!("- {}.{}", label, subdomain_record.zone());
+    }
+    tracing::info!(
+        "{} {} missing, {} changed and {} orphaned records",
+        if dry_run { "found" } else { "reconciled" },
+        diff.missing.len(),
+        diff.changed.len(),
+        diff.orphaned.len()
+    );
+
+    Ok(())
+}
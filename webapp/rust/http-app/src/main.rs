@@ -1,8 +1,24 @@
+use isupipe_core::commands::pdnsutil_command::SubdomainRecordConfig;
 use isupipe_core::db::build_database_connection_options;
+use isupipe_core::services::login_throttle_service::LoginThrottleService;
+use isupipe_core::services::manager::ServiceManager;
+use isupipe_core::validation::username::UsernamePolicy;
+use isupipe_http_core::avatars::{init_default_avatars, DefaultAvatars};
+use isupipe_http_core::client_ip::TrustedProxies;
+use isupipe_http_core::compat::BenchCompat;
+use isupipe_http_core::hub::LivestreamEventHub;
 use isupipe_http_core::routes::routes;
+use isupipe_http_core::session::{renew_session, SessionConfig};
 use isupipe_http_core::state::AppState;
+use isupipe_infra::commands::pdnsutil_command::PDNSUtilCommandInfra;
+use isupipe_infra::notifiers::PasswordResetNotifierInfra;
 use isupipe_infra::services::manager::ServiceManagerInfra;
+use isupipe_infra::storages::IconStorageInfra;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// ログイン失敗回数の古い記録を消す間隔
+const LOGIN_ATTEMPT_PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -23,22 +39,60 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         DEFAULT_SECRET.to_owned()
     };
 
-    const POWERDNS_SUBDOMAIN_ADDRESS_ENV_KEY: &str = "ISUCON13_POWERDNS_SUBDOMAIN_ADDRESS";
-    let Ok(powerdns_subdomain_address) = std::env::var(POWERDNS_SUBDOMAIN_ADDRESS_ENV_KEY) else {
-        panic!(
-            "environ {} must be provided",
-            POWERDNS_SUBDOMAIN_ADDRESS_ENV_KEY
-        );
-    };
+    // アイコン未設定のユーザーに返す画像。ISUCON13_DEFAULT_AVATAR で切り替える
+    init_default_avatars(DefaultAvatars::from_env()?);
+
+    // アイコン画像の保存先。ISUCON13_ICON_STORAGE で切り替える
+    let icon_storage = IconStorageInfra::from_env()?;
+
+    // セッションの有効期間と Cookie の属性。ISUCON13_SESSION_* で変更する
+    let session_config = SessionConfig::from_env()?;
+
+    // パスワード再設定トークンの通知先。ISUCON13_PASSWORD_RESET_NOTIFIER で切り替える
+    let password_reset_notifier = PasswordResetNotifierInfra::from_env()?;
+
+    // ユーザーのサブドメインに登録するレコード。ISUCON13_PDNS_ZONE, ISUCON13_PDNS_RECORD_TYPE,
+    // ISUCON13_PDNS_RECORD_TTL と ISUCON13_POWERDNS_SUBDOMAIN_ADDRESS (必須) で設定する
+    let subdomain_record = SubdomainRecordConfig::from_env()?;
+
+    // ユーザーのサブドメインの登録先。ISUCON13_PDNS_BACKEND で pdnsutil と HTTP API を切り替える
+    let pdnsutil_command = PDNSUtilCommandInfra::from_env(subdomain_record.clone())?;
+
+    // 登録できないユーザー名。ISUCON13_RESERVED_USERNAMES (カンマ区切り) で置き換える
+    let username_policy = UsernamePolicy::from_env();
 
-    let service = ServiceManagerInfra::new(pool.clone());
+    // X-Real-IP を信頼する接続元。ISUCON13_TRUSTED_PROXIES (カンマ区切りのアドレスか CIDR) で変更する
+    let trusted_proxies = TrustedProxies::from_env()?;
 
+    // ベンチマーカーとの互換のための応答。ISUCON13_BENCH_COMPAT=false で本来の応答を返す
+    let bench_compat = BenchCompat::from_env()?;
+
+    let service = ServiceManagerInfra::new(
+        pool.clone(),
+        icon_storage,
+        password_reset_notifier,
+        pdnsutil_command,
+        username_policy,
+    );
+
+    // 失敗回数は存在しないユーザー名でも記録されるので、数え直しになったものを定期的に消す
+    tokio::spawn(prune_login_attempts(service.clone()));
+
+    let state = AppState {
+        service,
+        key: axum_extra::extract::cookie::Key::derive_from(&secret),
+        session_config: Arc::new(session_config),
+        subdomain_record: Arc::new(subdomain_record),
+        livestream_hub: Arc::new(LivestreamEventHub::new()),
+        trusted_proxies: Arc::new(trusted_proxies),
+        bench_compat: Arc::new(bench_compat),
+    };
     let app = routes()
-        .with_state(AppState {
-            service,
-            key: axum_extra::extract::cookie::Key::derive_from(&secret),
-            powerdns_subdomain_address: Arc::new(powerdns_subdomain_address),
-        })
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            renew_session,
+        ))
+        .with_state(state)
         .layer(tower_http::trace::TraceLayer::new_for_http());
 
     // HTTPサーバ起動
@@ -48,12 +102,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         const LISTEN_PORT: u16 = 8080;
         axum::Server::bind(&std::net::SocketAddr::from(([0, 0, 0, 0], LISTEN_PORT)))
     }
-    .serve(app.into_make_service())
+    .serve(app.into_make_service_with_connect_info::<std::net::SocketAddr>())
     .await?;
 
     Ok(())
 }
 
+async fn prune_login_attempts<S: ServiceManager>(service: S) {
+    let mut interval = tokio::time::interval(LOGIN_ATTEMPT_PRUNE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let now = chrono::Utc::now().timestamp();
+        if let Err(e) = service.login_throttle_service().prune_expired(now).await {
+            tracing::warn!("failed to prune login attempts: {:?}", e);
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize)]
 struct Session {
     id: String,
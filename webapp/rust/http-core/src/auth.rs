@@ -0,0 +1,76 @@
+use crate::error::Error;
+use crate::session::CookieSession;
+use crate::state::AppState;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum_extra::extract::cookie::Key;
+use axum_extra::extract::SignedCookieJar;
+use chrono::Utc;
+use isupipe_core::models::session::SessionId;
+use isupipe_core::models::user::{UserId, UserName};
+use isupipe_core::services::manager::ServiceManager;
+use isupipe_core::services::session_service::SessionService;
+
+/// セッションを検証して取り出したログイン中のユーザー
+/// セッションがなければ 403、期限切れやログアウト済み、壊れたセッションなら 401 を返す
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: UserId,
+    pub name: UserName,
+    pub session_id: SessionId,
+}
+
+/// ログインしていなくてもよいハンドラ向け。有効なセッションがなければ None
+#[derive(Debug, Clone)]
+pub struct OptionalAuthUser(pub Option<AuthUser>);
+
+#[axum::async_trait]
+impl<S: ServiceManager> FromRequestParts<AppState<S>> for AuthUser {
+    type Rejection = Error;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState<S>,
+    ) -> Result<Self, Self::Rejection> {
+        let Ok(jar) = SignedCookieJar::<Key>::from_request_parts(parts, state).await;
+        let cookie_session = CookieSession::load(&jar).await?;
+
+        let now = Utc::now().timestamp();
+        if now > cookie_session.expires_at {
+            return Err(Error::Unauthorized("session has expired".into()));
+        }
+
+        // Cookie が有効でもサーバー側で失効させたセッションは受け付けない
+        let session = state
+            .service
+            .session_service()
+            .find_active(&cookie_session.id, now)
+            .await?
+            .ok_or(Error::Unauthorized("session has been revoked".into()))?;
+        if session.user_id != cookie_session.user_id {
+            return Err(Error::Unauthorized("invalid session".into()));
+        }
+
+        Ok(Self {
+            id: session.user_id,
+            name: cookie_session.username,
+            session_id: session.id,
+        })
+    }
+}
+
+#[axum::async_trait]
+impl<S: ServiceManager> FromRequestParts<AppState<S>> for OptionalAuthUser {
+    type Rejection = Error;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState<S>,
+    ) -> Result<Self, Self::Rejection> {
+        match AuthUser::from_request_parts(parts, state).await {
+            Ok(user) => Ok(Self(Some(user))),
+            Err(Error::Forbidden(_) | Error::Unauthorized(_)) => Ok(Self(None)),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::error::Error;
+use isupipe_core::icon_image::{self, IconFormat, IconImageError, THUMBNAIL_SIZES};
+use isupipe_core::identicon;
+use isupipe_core::services::icon_service::icon_hash;
+use std::sync::{Arc, OnceLock};
+
+#[cfg(test)]
+mod from_env;
+#[cfg(test)]
+mod load;
+
+const DEFAULT_AVATAR_ENV_KEY: &str = "ISUCON13_DEFAULT_AVATAR";
+/// ISUCON13_DEFAULT_AVATAR にこの値を指定するとユーザー名からアイコンを生成する
+const IDENTICON: &str = "identicon";
+const EMBEDDED_AVATAR: &[u8] = include_bytes!("../../../img/NoImage.jpg");
+/// 生成アイコンのサイズ。サムネイルはこれとは別に要求されたサイズで生成する
+const IDENTICON_SIZE: u32 = 256;
+
+static DEFAULT_AVATARS: OnceLock<DefaultAvatars> = OnceLock::new();
+
+#[derive(Debug)]
+pub struct DefaultAvatar {
+    pub mime_type: String,
+    pub image: Vec<u8>,
+}
+
+/// アイコンを設定していないユーザーに返す画像
+pub enum DefaultAvatars {
+    /// 全員に同じ画像を返す
+    Image {
+        avatar: Arc<DefaultAvatar>,
+        /// THUMBNAIL_SIZES ごとのサムネイル。起動時に生成しておく
+        thumbnails: Vec<(u32, Arc<DefaultAvatar>)>,
+        hash: String,
+    },
+    /// ユーザー名から生成したアイコンを返す
+    Identicon,
+}
+
+impl DefaultAvatars {
+    /// バイナリに埋め込んだ NoImage.jpg を使う
+    pub fn embedded() -> Self {
+        Self::image(EMBEDDED_AVATAR.to_vec(), IconFormat::Jpeg)
+            .expect("embedded default avatar must be a valid JPEG")
+    }
+
+    pub fn identicon() -> Self {
+        Self::Identicon
+    }
+
+    /// ISUCON13_DEFAULT_AVATAR から組み立てる
+    /// 未指定なら埋め込み画像、identicon なら生成アイコン、それ以外は画像ファイルのパスとして読み込む
+    pub fn from_env() -> Result<Self, Error> {
+        match std::env::var(DEFAULT_AVATAR_ENV_KEY) {
+            Err(_) => Ok(Self::embedded()),
+            Ok(value) if value.is_empty() => Ok(Self::embedded()),
+            Ok(value) if value == IDENTICON => Ok(Self::identicon()),
+            Ok(path) => Self::load(&path),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let image = std::fs::read(path).map_err(|e| {
+            Error::InternalServerError(format!("failed to read default avatar {}: {}", path, e))
+        })?;
+        let format = IconFormat::sniff(&image).ok_or_else(|| {
+            Error::InternalServerError(format!("default avatar {} is not a supported image", path))
+        })?;
+
+        Self::image(image, format).map_err(|e| {
+            Error::InternalServerError(format!("invalid default avatar {}: {}", path, e))
+        })
+    }
+
+    fn image(image: Vec<u8>, format: IconFormat) -> Result<Self, IconImageError> {
+        let thumbnails = THUMBNAIL_SIZES
+            .iter()
+            .map(|&size| Ok((size, Arc::new(thumbnail(&image, size)?))))
+            .collect::<Result<_, IconImageError>>()?;
+
+        Ok(Self::Image {
+            hash: icon_hash(&image),
+            avatar: Arc::new(DefaultAvatar {
+                mime_type: format.mime_type().to_owned(),
+                image,
+            }),
+            thumbnails,
+        })
+    }
+
+    /// 指定したユーザーのデフォルトアイコンのハッシュ
+    pub fn hash(&self, username: &str) -> String {
+        match self {
+            Self::Image { hash, .. } => hash.clone(),
+            Self::Identicon => identicon::hash(username),
+        }
+    }
+
+    /// 指定したユーザーのデフォルトアイコン。size を指定するとその大きさのサムネイルを返す
+    /// 画像の生成は重いのでブロッキングスレッドで行う
+    pub async fn avatar(
+        &self,
+        username: &str,
+        size: Option<u32>,
+    ) -> Result<Arc<DefaultAvatar>, Error> {
+        match (self, size) {
+            (Self::Image { avatar, .. }, None) => Ok(avatar.clone()),
+            (
+                Self::Image {
+                    avatar, thumbnails, ..
+                },
+                Some(size),
+            ) => match thumbnails.iter().find(|(s, _)| *s == size) {
+                Some((_, thumbnail)) => Ok(thumbnail.clone()),
+                None => {
+                    let avatar = avatar.clone();
+                    let thumbnail =
+                        tokio::task::spawn_blocking(move || thumbnail(&avatar.image, size))
+                            .await
+                            .map_err(|e| Error::InternalServerError(e.to_string()))?
+                            .map_err(|e| Error::InternalServerError(e.to_string()))?;
+                    Ok(Arc::new(thumbnail))
+                }
+            },
+            (Self::Identicon, size) => {
+                let username = username.to_owned();
+                let size = size.unwrap_or(IDENTICON_SIZE);
+                let image =
+                    tokio::task::spawn_blocking(move || identicon::generate(&username, size))
+                        .await
+                        .map_err(|e| Error::InternalServerError(e.to_string()))?;
+                Ok(Arc::new(DefaultAvatar {
+                    mime_type: IconFormat::Png.mime_type().to_owned(),
+                    image,
+                }))
+            }
+        }
+    }
+}
+
+fn thumbnail(image: &[u8], size: u32) -> Result<DefaultAvatar, IconImageError> {
+    let thumbnail = icon_image::thumbnail(image, size)?;
+    Ok(DefaultAvatar {
+        mime_type: thumbnail.mime_type.to_owned(),
+        image: thumbnail.image,
+    })
+}
+
+/// 起動時にデフォルトアイコンを設定する。2回目以降の呼び出しは無視する
+pub fn init_default_avatars(avatars: DefaultAvatars) {
+    if DEFAULT_AVATARS.set(avatars).is_err() {
+        tracing::warn!("default avatars are already initialized");
+    }
+}
+
+/// 起動時に設定したデフォルトアイコン。未設定なら埋め込み画像を使う
+pub fn default_avatars() -> &'static DefaultAvatars {
+    DEFAULT_AVATARS.get_or_init(DefaultAvatars::embedded)
+}
@@ -0,0 +1,32 @@
+use crate::avatars::{DefaultAvatars, DEFAULT_AVATAR_ENV_KEY, EMBEDDED_AVATAR};
+use isupipe_core::identicon;
+use isupipe_core::services::icon_service::icon_hash;
+
+// 環境変数を書き換えるので、並行して走らないよう1つのテストにまとめる
+#[tokio::test]
+async fn from_env_case() {
+    std::env::remove_var(DEFAULT_AVATAR_ENV_KEY);
+    let avatars = DefaultAvatars::from_env().unwrap();
+    assert_eq!(avatars.hash("alice"), icon_hash(EMBEDDED_AVATAR));
+
+    std::env::set_var(DEFAULT_AVATAR_ENV_KEY, "");
+    let avatars = DefaultAvatars::from_env().unwrap();
+    assert_eq!(avatars.hash("alice"), icon_hash(EMBEDDED_AVATAR));
+
+    // 画像を描かずにハッシュが決まる
+    std::env::set_var(DEFAULT_AVATAR_ENV_KEY, "identicon");
+    let avatars = DefaultAvatars::from_env().unwrap();
+    assert!(matches!(avatars, DefaultAvatars::Identicon));
+    assert_eq!(avatars.hash("alice"), identicon::hash("alice"));
+    assert_ne!(avatars.hash("alice"), avatars.hash("bob"));
+    let avatar = avatars.avatar("alice", Some(64)).await.unwrap();
+    assert_eq!(avatar.mime_type, "image/png");
+    assert_eq!(avatar.image, identicon::generate("alice", 64));
+
+    // それ以外は画像ファイルのパスとして読み込む
+    let path = std::env::temp_dir().join(format!("avatar-{}", uuid::Uuid::new_v4()));
+    std::env::set_var(DEFAULT_AVATAR_ENV_KEY, &path);
+    assert!(DefaultAvatars::from_env().is_err());
+
+    std::env::remove_var(DEFAULT_AVATAR_ENV_KEY);
+}
@@ -0,0 +1,50 @@
+use crate::avatars::DefaultAvatars;
+use isupipe_core::icon_image::{self, THUMBNAIL_SIZES};
+use isupipe_core::identicon;
+use isupipe_core::services::icon_service::icon_hash;
+use std::path::PathBuf;
+
+fn temp_file(contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("avatar-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn loaded_case() {
+    let image = identicon::generate("alice", 128);
+    let path = temp_file(&image);
+
+    let avatars = DefaultAvatars::load(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // 全員に同じ画像を返す
+    assert!(matches!(avatars, DefaultAvatars::Image { .. }));
+    assert_eq!(avatars.hash("alice"), icon_hash(&image));
+    assert_eq!(avatars.hash("bob"), icon_hash(&image));
+    let avatar = avatars.avatar("alice", None).await.unwrap();
+    assert_eq!(avatar.mime_type, "image/png");
+    assert_eq!(avatar.image, image);
+    for size in THUMBNAIL_SIZES {
+        let thumbnail = avatars.avatar("alice", Some(size)).await.unwrap();
+        assert_eq!(
+            thumbnail.image,
+            icon_image::thumbnail(&image, size).unwrap().image
+        );
+    }
+}
+
+#[test]
+fn not_found_case() {
+    let path = std::env::temp_dir().join(format!("avatar-{}", uuid::Uuid::new_v4()));
+    assert!(DefaultAvatars::load(path.to_str().unwrap()).is_err());
+}
+
+#[test]
+fn not_image_case() {
+    let path = temp_file(b"not an image");
+    let result = DefaultAvatars::load(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+}
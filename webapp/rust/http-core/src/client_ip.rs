@@ -0,0 +1,136 @@
+use crate::error::Error;
+use crate::state::AppState;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use isupipe_core::services::manager::ServiceManager;
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+#[cfg(test)]
+mod resolve;
+#[cfg(test)]
+mod trusted_proxies;
+
+/// nginx が付ける接続元IPのヘッダ
+const REAL_IP_HEADER: &str = "x-real-ip";
+
+const TRUSTED_PROXIES_ENV_KEY: &str = "ISUCON13_TRUSTED_PROXIES";
+/// 同じホストの nginx からの接続だけを信頼する
+const DEFAULT_TRUSTED_PROXIES: &str = "127.0.0.1,::1";
+
+/// X-Real-IP を信頼してよい接続相手。アドレスか CIDR で指定する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedProxies(Vec<(IpAddr, u8)>);
+
+impl Default for TrustedProxies {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TRUSTED_PROXIES).unwrap()
+    }
+}
+
+impl TrustedProxies {
+    /// ISUCON13_TRUSTED_PROXIES (カンマ区切り) があればデフォルトを置き換える。空ならどこからも信頼しない
+    pub fn from_env() -> Result<Self, Error> {
+        match std::env::var(TRUSTED_PROXIES_ENV_KEY) {
+            Ok(value) => Self::parse(&value).map_err(|message| {
+                Error::InternalServerError(format!(
+                    "invalid {}: {}",
+                    TRUSTED_PROXIES_ENV_KEY, message
+                ))
+            }),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut proxies = Vec::new();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (addr, prefix) = match entry.split_once('/') {
+                Some((addr, prefix)) => (addr, Some(prefix)),
+                None => (entry, None),
+            };
+            let addr: IpAddr = addr
+                .parse()
+                .map_err(|_| format!("invalid address: {}", entry))?;
+            let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+            let prefix = match prefix {
+                Some(prefix) => prefix
+                    .parse()
+                    .ok()
+                    .filter(|prefix| *prefix <= max_prefix)
+                    .ok_or_else(|| format!("invalid prefix length: {}", entry))?,
+                None => max_prefix,
+            };
+            proxies.push((addr, prefix));
+        }
+
+        Ok(Self(proxies))
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        // IPv4 射影アドレスで受けた場合も IPv4 として比べる
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            IpAddr::V4(_) => ip,
+        };
+        self.0.iter().any(|(addr, prefix)| match (addr, ip) {
+            (IpAddr::V4(addr), IpAddr::V4(ip)) => {
+                mask(u32::from(*addr).into(), *prefix, 32)
+                    == mask(u32::from(ip).into(), *prefix, 32)
+            }
+            (IpAddr::V6(addr), IpAddr::V6(ip)) => {
+                mask(u128::from(*addr), *prefix, 128) == mask(u128::from(ip), *prefix, 128)
+            }
+            _ => false,
+        })
+    }
+}
+
+fn mask(bits: u128, prefix: u8, width: u32) -> u128 {
+    let host_bits = width - u32::from(prefix);
+    if host_bits >= 128 {
+        0
+    } else {
+        bits >> host_bits
+    }
+}
+
+/// 接続相手が信頼できるプロキシのときだけ X-Real-IP を使い、それ以外は接続相手のアドレスを使う
+pub fn resolve(
+    peer_ip: Option<IpAddr>,
+    real_ip: Option<&str>,
+    trusted_proxies: &TrustedProxies,
+) -> Option<IpAddr> {
+    match peer_ip {
+        Some(peer_ip) if trusted_proxies.contains(peer_ip) => real_ip
+            .and_then(|value| value.trim().parse().ok())
+            .or(Some(peer_ip)),
+        peer_ip => peer_ip,
+    }
+}
+
+/// リクエストの接続元IP
+/// 信頼できるプロキシ経由なら X-Real-IP を、それ以外は接続相手のアドレスを使う。どちらも無ければ None
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[axum::async_trait]
+impl<S: ServiceManager> FromRequestParts<AppState<S>> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState<S>,
+    ) -> Result<Self, Self::Rejection> {
+        let peer_ip = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let real_ip = parts
+            .headers
+            .get(REAL_IP_HEADER)
+            .and_then(|value| value.to_str().ok());
+
+        Ok(Self(resolve(peer_ip, real_ip, &state.trusted_proxies)))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::client_ip::{resolve, TrustedProxies};
+use std::net::IpAddr;
+
+fn ip(value: &str) -> IpAddr {
+    value.parse().unwrap()
+}
+
+#[test]
+fn trusted_proxy_uses_header() {
+    let proxies = TrustedProxies::default();
+
+    assert_eq!(
+        resolve(Some(ip("127.0.0.1")), Some("198.51.100.7"), &proxies),
+        Some(ip("198.51.100.7"))
+    );
+}
+
+#[test]
+fn untrusted_peer_ignores_header() {
+    // 直接接続したクライアントは X-Real-IP で接続元を偽れない
+    let proxies = TrustedProxies::default();
+
+    assert_eq!(
+        resolve(Some(ip("203.0.113.5")), Some("198.51.100.7"), &proxies),
+        Some(ip("203.0.113.5"))
+    );
+}
+
+#[test]
+fn trusted_proxy_without_header() {
+    let proxies = TrustedProxies::default();
+
+    assert_eq!(
+        resolve(Some(ip("127.0.0.1")), None, &proxies),
+        Some(ip("127.0.0.1"))
+    );
+    assert_eq!(
+        resolve(Some(ip("127.0.0.1")), Some("not an ip"), &proxies),
+        Some(ip("127.0.0.1"))
+    );
+}
+
+#[test]
+fn unknown_peer_ignores_header() {
+    let proxies = TrustedProxies::default();
+
+    assert_eq!(resolve(None, Some("198.51.100.7"), &proxies), None);
+}
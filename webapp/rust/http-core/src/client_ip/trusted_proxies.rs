@@ -0,0 +1,48 @@
+use crate::client_ip::TrustedProxies;
+use std::net::IpAddr;
+
+fn ip(value: &str) -> IpAddr {
+    value.parse().unwrap()
+}
+
+#[test]
+fn default_is_loopback() {
+    let proxies = TrustedProxies::default();
+
+    assert!(proxies.contains(ip("127.0.0.1")));
+    assert!(proxies.contains(ip("::1")));
+    assert!(proxies.contains(ip("::ffff:127.0.0.1")));
+    assert!(!proxies.contains(ip("192.0.2.1")));
+}
+
+#[test]
+fn cidr() {
+    let proxies = TrustedProxies::parse("10.0.0.0/8, 2001:db8::/32").unwrap();
+
+    assert!(proxies.contains(ip("10.1.2.3")));
+    assert!(!proxies.contains(ip("11.0.0.1")));
+    assert!(proxies.contains(ip("2001:db8::1")));
+    assert!(!proxies.contains(ip("2001:db9::1")));
+}
+
+#[test]
+fn zero_prefix_matches_family() {
+    let proxies = TrustedProxies::parse("0.0.0.0/0").unwrap();
+
+    assert!(proxies.contains(ip("198.51.100.1")));
+    assert!(!proxies.contains(ip("2001:db8::1")));
+}
+
+#[test]
+fn empty_trusts_nobody() {
+    let proxies = TrustedProxies::parse("").unwrap();
+
+    assert!(!proxies.contains(ip("127.0.0.1")));
+}
+
+#[test]
+fn invalid() {
+    assert!(TrustedProxies::parse("localhost").is_err());
+    assert!(TrustedProxies::parse("10.0.0.0/33").is_err());
+    assert!(TrustedProxies::parse("::/129").is_err());
+}
@@ -0,0 +1,56 @@
+//! ベンチマーカーが本来とは違う応答を期待している箇所の切り替え
+//! ベンチマーカーはデフォルトの設定で動かすので、互換の応答をデフォルトにしている
+//! ISUCON13_BENCH_COMPAT=false で本来の応答を返す
+//!
+//! - 重複したユーザー名の登録: ベンチマーカーは 500 を期待している
+//!   (bench/scenario/core_pretest_abnormal.go)。本来は 400 を返す。どちらでも本文には name の検証エラーを入れる
+
+use crate::error::Error;
+use axum::http::StatusCode;
+use isupipe_core::validation::username::taken_errors;
+
+#[cfg(test)]
+mod username_taken;
+
+const BENCH_COMPAT_ENV_KEY: &str = "ISUCON13_BENCH_COMPAT";
+
+#[derive(Debug, Clone)]
+pub struct BenchCompat {
+    enabled: bool,
+}
+
+impl Default for BenchCompat {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl BenchCompat {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    pub fn from_env() -> Result<Self, Error> {
+        match std::env::var(BENCH_COMPAT_ENV_KEY).as_deref() {
+            Err(_) | Ok("" | "1" | "true") => Ok(Self::new(true)),
+            Ok("0" | "false") => Ok(Self::new(false)),
+            Ok(value) => Err(Error::InternalServerError(format!(
+                "invalid {}: must be true or false: {}",
+                BENCH_COMPAT_ENV_KEY, value
+            ))),
+        }
+    }
+
+    /// 重複したユーザー名の登録に返すエラー
+    pub fn username_taken(&self) -> Error {
+        let status = if self.enabled {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::BAD_REQUEST
+        };
+        Error::ValidationWithStatus {
+            status,
+            errors: taken_errors(),
+        }
+    }
+}
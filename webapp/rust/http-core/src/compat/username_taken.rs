@@ -0,0 +1,27 @@
+use crate::compat::BenchCompat;
+use axum::http::StatusCode;
+use axum::response::IntoResponse as _;
+
+async fn respond(compat: BenchCompat) -> (StatusCode, serde_json::Value) {
+    let response = compat.username_taken().into_response();
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn compat_case() {
+    // ベンチマーカーは 500 を期待している
+    let (status, body) = respond(BenchCompat::default()).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["errors"][0]["field"], "name");
+    assert_eq!(body["errors"][0]["code"], "taken");
+}
+
+#[tokio::test]
+async fn disabled_case() {
+    let (status, body) = respond(BenchCompat::new(false)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "name");
+    assert_eq!(body["errors"][0]["code"], "taken");
+}
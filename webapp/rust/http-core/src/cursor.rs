@@ -0,0 +1,103 @@
+use crate::error::Error;
+use axum::http::{HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use base64::Engine as _;
+use isupipe_core::models::cursor::{Cursor, PageCursor};
+
+#[cfg(test)]
+mod decode_cursor;
+#[cfg(test)]
+mod decode_id_cursor;
+#[cfg(test)]
+mod parse_page_cursor;
+
+/// 次のページを取得するためのカーソルを返すヘッダ
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// カーソルを指定してlimitを省略したときのページサイズ
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+
+const ENGINE: base64::engine::GeneralPurpose = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+pub fn encode_cursor(cursor: &Cursor) -> String {
+    ENGINE.encode(format!("{}:{}", cursor.created_at, cursor.id))
+}
+
+pub fn decode_cursor(cursor: &str) -> Result<Cursor, Error> {
+    let decoded = decode(cursor)?;
+    let (created_at, id) = decoded.split_once(':').ok_or_else(invalid_cursor)?;
+
+    Ok(Cursor {
+        created_at: created_at.parse().map_err(|_| invalid_cursor())?,
+        id: id.parse().map_err(|_| invalid_cursor())?,
+    })
+}
+
+pub fn encode_id_cursor(id: i64) -> String {
+    ENGINE.encode(id.to_string())
+}
+
+pub fn decode_id_cursor(cursor: &str) -> Result<i64, Error> {
+    decode(cursor)?.parse().map_err(|_| invalid_cursor())
+}
+
+/// before/afterクエリからページングの起点を組み立てる。両方指定された場合はエラー
+pub fn parse_page_cursor<C>(
+    before: &str,
+    after: &str,
+    decode: impl Fn(&str) -> Result<C, Error>,
+) -> Result<Option<PageCursor<C>>, Error> {
+    match (before.is_empty(), after.is_empty()) {
+        (true, true) => Ok(None),
+        (false, true) => Ok(Some(PageCursor::Before(decode(before)?))),
+        (true, false) => Ok(Some(PageCursor::After(decode(after)?))),
+        (false, false) => Err(Error::BadRequest(
+            "before and after cannot be specified at the same time".into(),
+        )),
+    }
+}
+
+/// ページングしたときのレスポンスボディ
+#[derive(Debug, serde::Serialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// 同じ向きに続きを取得するためのカーソル。続きがなければ null
+    pub next_cursor: Option<String>,
+}
+
+/// 一覧のレスポンスを組み立てる
+/// paginate=true か before/after を指定されたときは items と next_cursor を持つオブジェクトを、
+/// それ以外は既存のクライアントのために配列を返す。どちらの場合も next_cursor はヘッダにも付ける
+pub fn list_response<T: serde::Serialize>(
+    items: Vec<T>,
+    next_cursor: Option<String>,
+    paged: bool,
+) -> Response {
+    let headers = next_cursor_headers(next_cursor.clone());
+    if paged {
+        (headers, axum::Json(PageResponse { items, next_cursor })).into_response()
+    } else {
+        (headers, axum::Json(items)).into_response()
+    }
+}
+
+pub fn next_cursor_headers(next_cursor: Option<String>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(next_cursor) = next_cursor {
+        // base64url なのでヘッダ値として常に妥当
+        headers.insert(
+            NEXT_CURSOR_HEADER,
+            HeaderValue::from_str(&next_cursor).unwrap(),
+        );
+    }
+    headers
+}
+
+fn decode(cursor: &str) -> Result<String, Error> {
+    let bytes = ENGINE.decode(cursor).map_err(|_| invalid_cursor())?;
+    String::from_utf8(bytes).map_err(|_| invalid_cursor())
+}
+
+fn invalid_cursor() -> Error {
+    Error::BadRequest("invalid cursor".into())
+}
@@ -0,0 +1,47 @@
+use crate::cursor::{decode_cursor, encode_cursor};
+use crate::error::Error;
+use base64::Engine as _;
+use isupipe_core::models::cursor::Cursor;
+
+#[test]
+fn round_trip_case() {
+    for cursor in [
+        Cursor {
+            created_at: 1_700_000_000,
+            id: 42,
+        },
+        Cursor {
+            created_at: 0,
+            id: 0,
+        },
+        Cursor {
+            created_at: -1,
+            id: i64::MAX,
+        },
+    ] {
+        let encoded = encode_cursor(&cursor);
+        // クエリにそのまま書ける
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(decode_cursor(&encoded).unwrap(), cursor);
+    }
+}
+
+#[test]
+fn invalid_case() {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    for cursor in [
+        "!!!".to_owned(),
+        engine.encode("100"),
+        engine.encode("a:1"),
+        engine.encode("100:b"),
+        engine.encode([0xff, 0xfe]),
+    ] {
+        assert!(
+            matches!(decode_cursor(&cursor), Err(Error::BadRequest(_))),
+            "{}",
+            cursor
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::cursor::{decode_id_cursor, encode_id_cursor};
+use crate::error::Error;
+
+#[test]
+fn round_trip_case() {
+    for id in [0, 1, 12_345, i64::MAX] {
+        assert_eq!(decode_id_cursor(&encode_id_cursor(id)).unwrap(), id);
+    }
+}
+
+#[test]
+fn invalid_case() {
+    for cursor in ["", "!!!", "YQ"] {
+        assert!(matches!(
+            decode_id_cursor(cursor),
+            Err(Error::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use crate::cursor::{decode_id_cursor, encode_id_cursor, parse_page_cursor};
+use crate::error::Error;
+use isupipe_core::models::cursor::PageCursor;
+
+#[test]
+fn parsed_case() {
+    let cursor = encode_id_cursor(10);
+    assert_eq!(parse_page_cursor("", "", decode_id_cursor).unwrap(), None);
+    assert_eq!(
+        parse_page_cursor(&cursor, "", decode_id_cursor).unwrap(),
+        Some(PageCursor::Before(10))
+    );
+    assert_eq!(
+        parse_page_cursor("", &cursor, decode_id_cursor).unwrap(),
+        Some(PageCursor::After(10))
+    );
+}
+
+#[test]
+fn both_specified_case() {
+    let cursor = encode_id_cursor(10);
+    assert!(matches!(
+        parse_page_cursor(&cursor, &cursor, decode_id_cursor),
+        Err(Error::BadRequest(_))
+    ));
+}
@@ -1,8 +1,11 @@
 use crate::responses::ResponseError;
-use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
 use isupipe_core::repos::ReposError;
 use isupipe_core::services::ServiceError;
 use isupipe_core::utils::UtilError;
+use isupipe_core::validation::username::taken_errors;
+use isupipe_core::validation::ValidationErrors;
 use std::borrow::Cow;
 
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +26,14 @@ pub enum Error {
     AsyncSession(#[from] async_session::Error),
     #[error("{0}")]
     BadRequest(Cow<'static, str>),
+    #[error("{0}")]
+    Validation(ValidationErrors),
+    /// ベンチマーカーとの互換のために 400 以外で返す検証エラー。crate::compat を参照
+    #[error("{errors}")]
+    ValidationWithStatus {
+        status: StatusCode,
+        errors: ValidationErrors,
+    },
     #[error("session error")]
     SessionError,
     #[error("unauthorized: {0}")]
@@ -31,6 +42,8 @@ pub enum Error {
     Forbidden(Cow<'static, str>),
     #[error("not found: {0}")]
     NotFound(Cow<'static, str>),
+    #[error("too many requests: retry after {retry_after} seconds")]
+    TooManyRequests { retry_after: i64 },
     #[error("{0}")]
     InternalServerError(String),
 }
@@ -42,7 +55,30 @@ impl From<ServiceError> for Error {
             ServiceError::NotFoundLivestreamComment => {
                 Self::NotFound(Cow::from("livecomment not found"))
             }
-            e => e.into(),
+            ServiceError::NotFoundUser => Self::NotFound(Cow::from("user not found")),
+            ServiceError::InvalidIconImage(e) => Self::BadRequest(Cow::from(e.to_string())),
+            e @ (ServiceError::InvalidUserProfile(_)
+            | ServiceError::InvalidTheme(_)
+            | ServiceError::InvalidPassword(_)
+            | ServiceError::IncorrectPassword
+            | ServiceError::InvalidPasswordResetToken
+            | ServiceError::CommentMatchSpam
+            | ServiceError::InvalidReservationRange) => Self::BadRequest(Cow::from(e.to_string())),
+            ServiceError::TooManyLoginAttempts { retry_after } => {
+                Self::TooManyRequests { retry_after }
+            }
+            ServiceError::Validation(errors) => Self::Validation(errors),
+            ServiceError::ReposError(e) => Self::ReposError(e),
+            ServiceError::SqlxError(e) => Self::Sqlx(e),
+            ServiceError::Bcrypt(e) => Self::Bcrypt(e),
+            ServiceError::UsernameTaken => Self::Validation(taken_errors()),
+            e @ (ServiceError::DnsProvisioningFailed { .. }
+            | ServiceError::PDNSUtilFailed(_)
+            | ServiceError::NotifierError(_)
+            | ServiceError::CommandError(_)
+            | ServiceError::NgWordMatcherError(_)
+            | ServiceError::StorageError(_)
+            | ServiceError::JoinError(_)) => Self::InternalServerError(e.to_string()),
         }
     }
 }
@@ -52,13 +88,17 @@ impl axum::response::IntoResponse for Error {
         #[derive(Debug, serde::Serialize)]
         struct ErrorResponse {
             error: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            errors: Option<ValidationErrors>,
         }
 
         let status = match self {
-            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::BadRequest(_) | Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::ValidationWithStatus { status, .. } => status,
             Self::Unauthorized(_) | Self::SessionError => StatusCode::UNAUTHORIZED,
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
             Self::Io(_)
             | Self::Sqlx(_)
             | Self::ReposError(_)
@@ -70,12 +110,24 @@ impl axum::response::IntoResponse for Error {
         };
 
         tracing::error!("{}", self);
-        (
+        let mut response = (
             status,
             axum::Json(ErrorResponse {
                 error: format!("{}", self),
+                errors: match &self {
+                    Self::Validation(errors) | Self::ValidationWithStatus { errors, .. } => {
+                        Some(errors.clone())
+                    }
+                    _ => None,
+                },
             }),
         )
-            .into_response()
+            .into_response();
+        if let Self::TooManyRequests { retry_after } = self {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after.max(1)));
+        }
+        response
     }
 }
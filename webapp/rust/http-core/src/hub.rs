@@ -0,0 +1,106 @@
+use crate::responses::livestream_comment_response::LivestreamCommentResponse;
+use crate::responses::reaction_response::ReactionResponse;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::user::UserId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+#[cfg(test)]
+mod subscribe;
+
+const CHANNEL_CAPACITY: usize = 256;
+
+type Channels<T> = Arc<Mutex<HashMap<LivestreamId, broadcast::Sender<T>>>>;
+
+/// ライブ配信ごとに購読者へメッセージを配信するためのハブ
+pub struct LivestreamHub<T: Clone> {
+    channels: Channels<T>,
+}
+
+impl<T: Clone> Default for LivestreamHub<T> {
+    fn default() -> Self {
+        Self {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T: Clone> LivestreamHub<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self, livestream_id: &LivestreamId) -> Subscription<T> {
+        let mut channels = self.channels.lock().unwrap();
+        let receiver = channels
+            .entry(livestream_id.clone())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe();
+
+        Subscription {
+            receiver: Some(receiver),
+            livestream_id: livestream_id.clone(),
+            channels: self.channels.clone(),
+        }
+    }
+
+    pub fn publish(&self, livestream_id: &LivestreamId, message: T) {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(sender) = channels.get(livestream_id) {
+            // 購読者がいなくなったチャンネルは破棄する
+            if sender.send(message).is_err() {
+                channels.remove(livestream_id);
+            }
+        }
+    }
+
+    /// 購読者がいるライブ配信の数
+    pub fn channel_count(&self) -> usize {
+        self.channels.lock().unwrap().len()
+    }
+}
+
+/// ライブ配信の購読。最後の購読が破棄されたらチャンネルも破棄する
+pub struct Subscription<T: Clone> {
+    /// Drop で先に破棄するため Option にしている
+    receiver: Option<broadcast::Receiver<T>>,
+    livestream_id: LivestreamId,
+    channels: Channels<T>,
+}
+
+impl<T: Clone> Subscription<T> {
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        match self.receiver.as_mut() {
+            Some(receiver) => receiver.recv().await,
+            None => Err(RecvError::Closed),
+        }
+    }
+}
+
+impl<T: Clone> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        // 購読の追加と同じロックの中で数えるので、破棄と同時に購読されても消さない
+        let mut channels = self.channels.lock().unwrap();
+        self.receiver.take();
+        if channels
+            .get(&self.livestream_id)
+            .is_some_and(|sender| sender.receiver_count() == 0)
+        {
+            channels.remove(&self.livestream_id);
+        }
+    }
+}
+
+/// ライブ配信の購読者に届けるイベント
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum LivestreamEvent {
+    Livecomment(Arc<LivestreamCommentResponse>),
+    Reaction(Arc<ReactionResponse>),
+    Enter { user_id: UserId },
+    Exit { user_id: UserId },
+}
+
+pub type LivestreamEventHub = LivestreamHub<Arc<LivestreamEvent>>;
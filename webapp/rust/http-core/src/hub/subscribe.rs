@@ -0,0 +1,42 @@
+use crate::hub::LivestreamHub;
+use isupipe_core::models::livestream::LivestreamId;
+
+#[tokio::test]
+async fn receive_published_message() {
+    let hub = LivestreamHub::<i64>::new();
+    let livestream_id = LivestreamId::new(1);
+    let mut subscription = hub.subscribe(&livestream_id);
+
+    hub.publish(&livestream_id, 42);
+    hub.publish(&LivestreamId::new(2), 0);
+
+    assert_eq!(subscription.recv().await.unwrap(), 42);
+}
+
+#[test]
+fn remove_channel_when_last_subscription_dropped() {
+    let hub = LivestreamHub::<i64>::new();
+    let livestream_id = LivestreamId::new(1);
+    let first = hub.subscribe(&livestream_id);
+    let second = hub.subscribe(&livestream_id);
+    hub.subscribe(&LivestreamId::new(2));
+    assert_eq!(hub.channel_count(), 1);
+
+    drop(first);
+    assert_eq!(hub.channel_count(), 1);
+
+    drop(second);
+    assert_eq!(hub.channel_count(), 0);
+}
+
+#[tokio::test]
+async fn resubscribe_after_channel_removed() {
+    let hub = LivestreamHub::<i64>::new();
+    let livestream_id = LivestreamId::new(1);
+    drop(hub.subscribe(&livestream_id));
+
+    let mut subscription = hub.subscribe(&livestream_id);
+    hub.publish(&livestream_id, 7);
+
+    assert_eq!(subscription.recv().await.unwrap(), 7);
+}
@@ -8,6 +8,8 @@ pub mod user_response;
 
 use isupipe_core::repos;
 use isupipe_core::services::ServiceError;
+use std::collections::HashSet;
+use std::hash::Hash;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ResponseError {
@@ -19,6 +21,14 @@ pub enum ResponseError {
     Repos(#[from] repos::ReposError),
     #[error("Service error: {0}")]
     Service(#[from] ServiceError),
+    #[error("not found: {0}")]
+    NotFound(&'static str),
 }
 
 pub type ResponseResult<T> = Result<T, ResponseError>;
+
+/// まとめて取得するためにIDの重複を取り除く
+fn unique_ids<'a, T: 'a + Clone + Eq + Hash>(ids: impl Iterator<Item = &'a T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    ids.filter(|id| seen.insert(*id)).cloned().collect()
+}
@@ -1,13 +1,15 @@
 use crate::responses::livestream_comment_response::LivestreamCommentResponse;
 use crate::responses::user_response::UserResponse;
-use crate::responses::ResponseResult;
-use isupipe_core::models::livestream_comment::LivestreamComment;
+use crate::responses::{unique_ids, ResponseError, ResponseResult};
+use isupipe_core::models::livestream_comment::LivestreamCommentId;
 use isupipe_core::models::livestream_comment_report::{
     LivestreamCommentReport, LivestreamCommentReportId,
 };
+use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_comment_service::LivestreamCommentService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::user_service::UserService;
+use std::collections::HashMap;
 
 #[derive(Debug, serde::Serialize)]
 pub struct LivestreamCommentReportResponse {
@@ -22,10 +24,40 @@ impl LivestreamCommentReportResponse {
         service: &S,
         reports: &[LivestreamCommentReport],
     ) -> ResponseResult<Vec<Self>> {
+        let reporter_ids = unique_ids(reports.iter().map(|r| &r.user_id));
+        let reporter_models = service.user_service().find_many(&reporter_ids).await?;
+        let reporters: HashMap<UserId, UserResponse> = reporter_models
+            .iter()
+            .map(|u| u.id.clone())
+            .zip(UserResponse::bulk_build_by_service(service, &reporter_models).await?)
+            .collect();
+
+        let comment_ids = unique_ids(reports.iter().map(|r| &r.livestream_comment_id));
+        let comment_models = service
+            .livestream_comment_service()
+            .find_many(&comment_ids)
+            .await?;
+        let comments: HashMap<LivestreamCommentId, LivestreamCommentResponse> = comment_models
+            .iter()
+            .map(|c| c.id.clone())
+            .zip(LivestreamCommentResponse::bulk_build_by_service(service, &comment_models).await?)
+            .collect();
+
         let mut result = Vec::with_capacity(reports.len());
-        for report in reports {
-            let res = Self::build_by_service(service, report).await?;
-            result.push(res)
+        for report_model in reports {
+            let reporter = reporters
+                .get(&report_model.user_id)
+                .ok_or(ResponseError::NotFound("reporter"))?;
+            let livecomment = comments
+                .get(&report_model.livestream_comment_id)
+                .ok_or(ResponseError::NotFound("livecomment"))?;
+
+            result.push(Self {
+                id: report_model.id.clone(),
+                reporter: reporter.clone(),
+                livecomment: livecomment.clone(),
+                created_at: report_model.created_at,
+            });
         }
 
         Ok(result)
@@ -35,27 +67,9 @@ impl LivestreamCommentReportResponse {
         service: &S,
         report_model: &LivestreamCommentReport,
     ) -> ResponseResult<Self> {
-        let reporter_model = service
-            .user_service()
-            .find(&report_model.user_id)
-            .await?
-            .unwrap();
-        let reporter = UserResponse::build_by_service(service, &reporter_model).await?;
-
-        let comment_service = service.livestream_comment_service();
-        let comment_model: LivestreamComment = comment_service
-            .find(&report_model.livestream_comment_id)
-            .await?
-            .unwrap();
-
-        let livecomment =
-            LivestreamCommentResponse::build_by_service(service, &comment_model).await?;
-
-        Ok(Self {
-            id: report_model.id.clone(),
-            reporter,
-            livecomment,
-            created_at: report_model.created_at,
-        })
+        let mut result =
+            Self::bulk_build_by_service(service, std::slice::from_ref(report_model)).await?;
+
+        Ok(result.remove(0))
     }
 }
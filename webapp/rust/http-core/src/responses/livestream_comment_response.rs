@@ -1,12 +1,15 @@
 use crate::responses::livestream_response::LivestreamResponse;
 use crate::responses::user_response::UserResponse;
-use crate::responses::ResponseResult;
+use crate::responses::{unique_ids, ResponseError, ResponseResult};
+use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::livestream_comment::{LivestreamComment, LivestreamCommentId};
+use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_service::LivestreamService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::user_service::UserService;
+use std::collections::HashMap;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct LivestreamCommentResponse {
     pub id: LivestreamCommentId,
     pub user: UserResponse,
@@ -21,11 +24,42 @@ impl LivestreamCommentResponse {
         service: &S,
         livestream_comments: &[LivestreamComment],
     ) -> ResponseResult<Vec<Self>> {
-        let mut result = Vec::with_capacity(livestream_comments.len());
+        let user_ids = unique_ids(livestream_comments.iter().map(|c| &c.user_id));
+        let user_models = service.user_service().find_many(&user_ids).await?;
+        let users: HashMap<UserId, UserResponse> = user_models
+            .iter()
+            .map(|u| u.id.clone())
+            .zip(UserResponse::bulk_build_by_service(service, &user_models).await?)
+            .collect();
+
+        let livestream_ids = unique_ids(livestream_comments.iter().map(|c| &c.livestream_id));
+        let livestream_models = service
+            .livestream_service()
+            .find_many(&livestream_ids)
+            .await?;
+        let livestreams: HashMap<LivestreamId, LivestreamResponse> = livestream_models
+            .iter()
+            .map(|l| l.id.clone())
+            .zip(LivestreamResponse::bulk_build_by_service(service, &livestream_models).await?)
+            .collect();
 
+        let mut result = Vec::with_capacity(livestream_comments.len());
         for comment in livestream_comments {
-            let res = Self::build_by_service(service, comment).await?;
-            result.push(res)
+            let comment_owner = users
+                .get(&comment.user_id)
+                .ok_or(ResponseError::NotFound("comment owner"))?;
+            let livestream = livestreams
+                .get(&comment.livestream_id)
+                .ok_or(ResponseError::NotFound("livestream"))?;
+
+            result.push(Self {
+                id: comment.id.clone(),
+                user: comment_owner.clone(),
+                livestream: livestream.clone(),
+                comment: comment.comment.clone(),
+                tip: comment.tip,
+                created_at: comment.created_at,
+            });
         }
 
         Ok(result)
@@ -35,27 +69,9 @@ impl LivestreamCommentResponse {
         service: &S,
         livecomment_model: &LivestreamComment,
     ) -> ResponseResult<Self> {
-        let comment_owner_model = service
-            .user_service()
-            .find(&livecomment_model.user_id)
-            .await?
-            .unwrap();
-        let comment_owner = UserResponse::build_by_service(service, &comment_owner_model).await?;
-
-        let livestream_service = service.livestream_service();
-        let livestream_model = livestream_service
-            .find(&livecomment_model.livestream_id)
-            .await?
-            .unwrap();
-        let livestream = LivestreamResponse::build_by_service(service, &livestream_model).await?;
-
-        Ok(Self {
-            id: livecomment_model.id.clone(),
-            user: comment_owner,
-            livestream,
-            comment: livecomment_model.comment.clone(),
-            tip: livecomment_model.tip,
-            created_at: livecomment_model.created_at,
-        })
+        let mut result =
+            Self::bulk_build_by_service(service, std::slice::from_ref(livecomment_model)).await?;
+
+        Ok(result.remove(0))
     }
 }
@@ -1,13 +1,16 @@
 use crate::responses::tag_response::TagResponse;
 use crate::responses::user_response::UserResponse;
-use crate::responses::ResponseResult;
+use crate::responses::{unique_ids, ResponseError, ResponseResult};
 use isupipe_core::models::livestream::{Livestream, LivestreamId};
+use isupipe_core::models::tag::{Tag, TagId};
+use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_tag_service::LivestreamTagService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::tag_service::TagService;
 use isupipe_core::services::user_service::UserService;
+use std::collections::HashMap;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct LivestreamResponse {
     pub id: LivestreamId,
     pub owner: UserResponse,
@@ -25,11 +28,59 @@ impl LivestreamResponse {
         service: &S,
         livestream_models: &[Livestream],
     ) -> ResponseResult<Vec<Self>> {
+        let owner_ids = unique_ids(livestream_models.iter().map(|l| &l.user_id));
+        let owner_models = service.user_service().find_many(&owner_ids).await?;
+        let owners: HashMap<UserId, UserResponse> = owner_models
+            .iter()
+            .map(|u| u.id.clone())
+            .zip(UserResponse::bulk_build_by_service(service, &owner_models).await?)
+            .collect();
+
+        let livestream_ids = unique_ids(livestream_models.iter().map(|l| &l.id));
+        let livestream_tag_models = service
+            .livestream_tag_service()
+            .find_many_by_livestream_ids(&livestream_ids)
+            .await?;
+
+        let tag_ids = unique_ids(livestream_tag_models.iter().map(|lt| &lt.tag_id));
+        let tag_models: HashMap<TagId, Tag> = service
+            .tag_service()
+            .find_many(&tag_ids)
+            .await?
+            .into_iter()
+            .map(|t| (t.id.clone(), t))
+            .collect();
+
+        let mut tags: HashMap<LivestreamId, Vec<TagResponse>> = HashMap::new();
+        for livestream_tag_model in &livestream_tag_models {
+            let tag_model = tag_models
+                .get(&livestream_tag_model.tag_id)
+                .ok_or(ResponseError::NotFound("tag"))?;
+            tags.entry(livestream_tag_model.livestream_id.clone())
+                .or_default()
+                .push(TagResponse {
+                    id: tag_model.id.clone(),
+                    name: tag_model.name.clone(),
+                });
+        }
+
         let mut result = Vec::with_capacity(livestream_models.len());
+        for livestream_model in livestream_models {
+            let owner = owners
+                .get(&livestream_model.user_id)
+                .ok_or(ResponseError::NotFound("livestream owner"))?;
 
-        for livestream in livestream_models {
-            let res = Self::build_by_service(service, livestream).await?;
-            result.push(res);
+            result.push(Self {
+                id: livestream_model.id.clone(),
+                owner: owner.clone(),
+                title: livestream_model.title.clone(),
+                tags: tags.get(&livestream_model.id).cloned().unwrap_or_default(),
+                description: livestream_model.description.clone(),
+                playlist_url: livestream_model.playlist_url.clone(),
+                thumbnail_url: livestream_model.thumbnail_url.clone(),
+                start_at: livestream_model.start_at,
+                end_at: livestream_model.end_at,
+            });
         }
 
         Ok(result)
@@ -39,38 +90,9 @@ impl LivestreamResponse {
         service: &S,
         livestream_model: &Livestream,
     ) -> ResponseResult<Self> {
-        let owner_model = service
-            .user_service()
-            .find(&livestream_model.user_id)
-            .await?
-            .unwrap();
-        let owner = UserResponse::build_by_service(service, &owner_model).await?;
-
-        let livestream_tag_models = service
-            .livestream_tag_service()
-            .find_all_by_livestream_id(&livestream_model.id)
-            .await?;
-
-        let mut tags = Vec::with_capacity(livestream_tag_models.len());
-        let tag_service = service.tag_service();
-        for livestream_tag_model in livestream_tag_models {
-            let tag_model = tag_service.find(&livestream_tag_model.tag_id).await?;
-            tags.push(TagResponse {
-                id: tag_model.id.clone(),
-                name: tag_model.name.clone(),
-            });
-        }
+        let mut result =
+            Self::bulk_build_by_service(service, std::slice::from_ref(livestream_model)).await?;
 
-        Ok(Self {
-            id: livestream_model.id.clone(),
-            owner,
-            title: livestream_model.title.clone(),
-            tags,
-            description: livestream_model.description.clone(),
-            playlist_url: livestream_model.playlist_url.clone(),
-            thumbnail_url: livestream_model.thumbnail_url.clone(),
-            start_at: livestream_model.start_at,
-            end_at: livestream_model.end_at,
-        })
+        Ok(result.remove(0))
     }
 }
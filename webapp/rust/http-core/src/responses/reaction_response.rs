@@ -1,12 +1,15 @@
 use crate::responses::livestream_response::LivestreamResponse;
 use crate::responses::user_response::UserResponse;
-use crate::responses::ResponseResult;
+use crate::responses::{unique_ids, ResponseError, ResponseResult};
+use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::reaction::{Reaction, ReactionId};
+use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_service::LivestreamService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::user_service::UserService;
+use std::collections::HashMap;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ReactionResponse {
     pub id: ReactionId,
     pub emoji_name: String,
@@ -20,11 +23,41 @@ impl ReactionResponse {
         service: &S,
         reactions: &[Reaction],
     ) -> ResponseResult<Vec<Self>> {
+        let user_ids = unique_ids(reactions.iter().map(|r| &r.user_id));
+        let user_models = service.user_service().find_many(&user_ids).await?;
+        let users: HashMap<UserId, UserResponse> = user_models
+            .iter()
+            .map(|u| u.id.clone())
+            .zip(UserResponse::bulk_build_by_service(service, &user_models).await?)
+            .collect();
+
+        let livestream_ids = unique_ids(reactions.iter().map(|r| &r.livestream_id));
+        let livestream_models = service
+            .livestream_service()
+            .find_many(&livestream_ids)
+            .await?;
+        let livestreams: HashMap<LivestreamId, LivestreamResponse> = livestream_models
+            .iter()
+            .map(|l| l.id.clone())
+            .zip(LivestreamResponse::bulk_build_by_service(service, &livestream_models).await?)
+            .collect();
+
         let mut result = Vec::with_capacity(reactions.len());
+        for reaction_model in reactions {
+            let user = users
+                .get(&reaction_model.user_id)
+                .ok_or(ResponseError::NotFound("reaction owner"))?;
+            let livestream = livestreams
+                .get(&reaction_model.livestream_id)
+                .ok_or(ResponseError::NotFound("livestream"))?;
 
-        for reaction in reactions {
-            let res = Self::build_by_service(service, reaction).await?;
-            result.push(res)
+            result.push(Self {
+                id: reaction_model.id.clone(),
+                emoji_name: reaction_model.emoji_name.clone(),
+                user: user.clone(),
+                livestream: livestream.clone(),
+                created_at: reaction_model.created_at,
+            });
         }
 
         Ok(result)
@@ -34,26 +67,9 @@ impl ReactionResponse {
         service: &S,
         reaction_model: &Reaction,
     ) -> ResponseResult<Self> {
-        let user_model = service
-            .user_service()
-            .find(&reaction_model.user_id)
-            .await?
-            .unwrap();
-        let user = UserResponse::build_by_service(service, &user_model).await?;
-
-        let livestream_model = service
-            .livestream_service()
-            .find(&reaction_model.livestream_id)
-            .await?
-            .unwrap();
-        let livestream = LivestreamResponse::build_by_service(service, &livestream_model).await?;
-
-        Ok(Self {
-            id: reaction_model.id.clone(),
-            emoji_name: reaction_model.emoji_name.clone(),
-            user,
-            livestream,
-            created_at: reaction_model.created_at,
-        })
+        let mut result =
+            Self::bulk_build_by_service(service, std::slice::from_ref(reaction_model)).await?;
+
+        Ok(result.remove(0))
     }
 }
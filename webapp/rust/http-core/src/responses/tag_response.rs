@@ -1,6 +1,6 @@
 use isupipe_core::models::tag::{Tag, TagId, TagName};
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct TagResponse {
     pub id: TagId,
     pub name: TagName,
@@ -1,5 +1,24 @@
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+use isupipe_core::models::theme::Theme;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct ThemeResponse {
     pub id: i64,
     pub dark_mode: bool,
+    pub accent_color: String,
+    pub background_image_url: Option<String>,
+    pub font: String,
+    pub chat_color_scheme: String,
+}
+
+impl From<Theme> for ThemeResponse {
+    fn from(theme: Theme) -> Self {
+        Self {
+            id: *theme.id.inner(),
+            dark_mode: theme.dark_mode,
+            accent_color: theme.accent_color,
+            background_image_url: theme.background_image_url,
+            font: theme.font,
+            chat_color_scheme: theme.chat_color_scheme,
+        }
+    }
 }
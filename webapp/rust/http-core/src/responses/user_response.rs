@@ -1,12 +1,14 @@
+use crate::avatars::default_avatars;
 use crate::responses::theme_response::ThemeResponse;
-use crate::responses::ResponseResult;
-use crate::FALLBACK_IMAGE;
+use crate::responses::{unique_ids, ResponseError, ResponseResult};
+use isupipe_core::models::theme::Theme;
 use isupipe_core::models::user::{User, UserId, UserName};
 use isupipe_core::services::icon_service::IconService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::theme_service::ThemeService;
+use std::collections::HashMap;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct UserResponse {
     pub id: UserId,
     pub name: UserName,
@@ -19,36 +21,55 @@ pub struct UserResponse {
 }
 
 impl UserResponse {
-    pub async fn build_by_service<S: ServiceManager>(
+    pub async fn bulk_build_by_service<S: ServiceManager>(
         service: &S,
-        user: &User,
-    ) -> ResponseResult<Self> {
-        let theme_model = service.theme_service().find_by_user_id(&user.id).await?;
+        users: &[User],
+    ) -> ResponseResult<Vec<Self>> {
+        let user_ids = unique_ids(users.iter().map(|u| &u.id));
+
+        let themes: HashMap<UserId, Theme> = service
+            .theme_service()
+            .find_many_by_user_ids(&user_ids)
+            .await?
+            .into_iter()
+            .map(|t| (t.user_id.clone(), t))
+            .collect();
 
-        let image = service
+        let icon_hashes = service
             .icon_service()
-            .find_image_by_user_id(&user.id)
+            .find_icon_hashes_by_user_ids(&user_ids)
             .await?;
 
-        let image = if let Some(image) = image {
-            image
-        } else {
-            tokio::fs::read(FALLBACK_IMAGE).await?
-        };
-
-        use sha2::digest::Digest as _;
-        let icon_hash = sha2::Sha256::digest(image);
-
-        Ok(Self {
-            id: user.id.clone(),
-            name: user.name.clone(),
-            display_name: user.display_name.clone(),
-            description: user.description.clone(),
-            theme: ThemeResponse {
-                id: theme_model.id.inner().clone(),
-                dark_mode: theme_model.dark_mode,
-            },
-            icon_hash: format!("{:x}", icon_hash),
-        })
+        let mut result = Vec::with_capacity(users.len());
+        for user in users {
+            let theme_model = themes
+                .get(&user.id)
+                .ok_or(ResponseError::NotFound("theme"))?;
+
+            let icon_hash = match icon_hashes.get(&user.id) {
+                Some(icon_hash) => icon_hash.clone(),
+                None => default_avatars().hash(user.name.inner()),
+            };
+
+            result.push(Self {
+                id: user.id.clone(),
+                name: user.name.clone(),
+                display_name: user.display_name.clone(),
+                description: user.description.clone(),
+                theme: ThemeResponse::from(theme_model.clone()),
+                icon_hash,
+            });
+        }
+
+        Ok(result)
+    }
+
+    pub async fn build_by_service<S: ServiceManager>(
+        service: &S,
+        user: &User,
+    ) -> ResponseResult<Self> {
+        let mut result = Self::bulk_build_by_service(service, std::slice::from_ref(user)).await?;
+
+        Ok(result.remove(0))
     }
 }
@@ -1,12 +1,16 @@
 use crate::routes::initialize_routes::initialize_handler;
 use crate::routes::livestream_routes::{get_my_livestreams_handler, livestreams_routes};
-use crate::routes::login_routes::login_handler;
+use crate::routes::login_routes::{login_handler, logout_all_handler, logout_handler};
+use crate::routes::password_routes::{
+    post_password_reset_confirm_handler, post_password_reset_handler,
+};
 use crate::routes::payment_routes::get_payment_result;
 use crate::routes::register_routes::register_handler;
 use crate::routes::tag_routes::get_tag_handler;
-use crate::routes::user_icon_routes::post_icon_handler;
+use crate::routes::user_icon_routes::{post_icon_handler, POST_ICON_BODY_LIMIT};
 use crate::routes::user_routes::user_routes;
 use crate::state::AppState;
+use axum::extract::DefaultBodyLimit;
 use axum::Router;
 use isupipe_core::services::manager::ServiceManager;
 
@@ -15,7 +19,9 @@ pub mod livestream_comment_report_routes;
 pub mod livestream_comment_routes;
 pub mod livestream_reaction_routes;
 pub mod livestream_routes;
+pub mod livestream_socket_routes;
 pub mod login_routes;
+pub mod password_routes;
 pub mod payment_routes;
 pub mod register_routes;
 pub mod tag_routes;
@@ -30,7 +36,21 @@ pub fn routes<S: ServiceManager + 'static>() -> Router<AppState<S>> {
         .route("/api/tag", axum::routing::get(get_tag_handler))
         .route("/api/register", axum::routing::post(register_handler))
         .route("/api/login", axum::routing::post(login_handler))
-        .route("/api/icon", axum::routing::post(post_icon_handler))
+        .route("/api/logout", axum::routing::post(logout_handler))
+        .route("/api/logout/all", axum::routing::post(logout_all_handler))
+        .route(
+            "/api/password-reset",
+            axum::routing::post(post_password_reset_handler),
+        )
+        .route(
+            "/api/password-reset/confirm",
+            axum::routing::post(post_password_reset_confirm_handler),
+        )
+        .route(
+            "/api/icon",
+            axum::routing::post(post_icon_handler)
+                .layer(DefaultBodyLimit::max(POST_ICON_BODY_LIMIT)),
+        )
         // 課金情報
         .route("/api/payment", axum::routing::get(get_payment_result))
         .nest("/api/user/", user_routes())
@@ -1,31 +1,20 @@
+use crate::auth::AuthUser;
 use crate::error::Error;
 use crate::responses::livestream_comment_report_response::LivestreamCommentReportResponse;
 use crate::state::AppState;
-use crate::{verify_user_session, DEFAULT_SESSION_ID_KEY, DEFAULT_USER_ID_KEY};
-use async_session::{CookieStore, SessionStore};
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum_extra::extract::SignedCookieJar;
 use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::livestream_comment::LivestreamCommentId;
-use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_comment_report_service::LivestreamCommentReportService;
 use isupipe_core::services::livestream_service::LivestreamService;
 use isupipe_core::services::manager::ServiceManager;
 
 pub async fn get_livecomment_reports_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
 ) -> Result<axum::Json<Vec<LivestreamCommentReportResponse>>, Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
     let livestream_id = LivestreamId::new(livestream_id);
 
     let livestream_model = service
@@ -34,7 +23,7 @@ pub async fn get_livecomment_reports_handler<S: ServiceManager>(
         .await?
         .unwrap();
 
-    if livestream_model.user_id.inner() != &user_id {
+    if livestream_model.user_id != user_id {
         return Err(Error::Forbidden(
             "can't get other streamer's livecomment reports".into(),
         ));
@@ -52,19 +41,9 @@ pub async fn get_livecomment_reports_handler<S: ServiceManager>(
 }
 pub async fn report_livecomment_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id, livecomment_id)): Path<(i64, i64)>,
 ) -> Result<(StatusCode, axum::Json<LivestreamCommentReportResponse>), Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
-
     let livestream_id = LivestreamId::new(livestream_id);
     let comment_id = LivestreamCommentId::new(livecomment_id);
     let report = service
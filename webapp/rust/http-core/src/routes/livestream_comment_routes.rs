@@ -1,34 +1,50 @@
+use crate::auth::AuthUser;
+use crate::cursor::{
+    decode_cursor, encode_cursor, list_response, parse_page_cursor, DEFAULT_PAGE_LIMIT,
+};
 use crate::error::Error;
+use crate::hub::{LivestreamEvent, LivestreamEventHub};
 use crate::responses::livestream_comment_response::LivestreamCommentResponse;
 use crate::state::AppState;
-use crate::{verify_user_session, DEFAULT_SESSION_ID_KEY, DEFAULT_USER_ID_KEY};
-use async_session::{CookieStore, SessionStore};
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use axum_extra::extract::SignedCookieJar;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use chrono::Utc;
+use futures_util::stream::{self, Stream, StreamExt};
 use isupipe_core::models::livestream::LivestreamId;
-use isupipe_core::models::livestream_comment::CreateLivestreamComment;
+use isupipe_core::models::livestream_comment::{CreateLivestreamComment, LivestreamCommentId};
 use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_comment_service::LivestreamCommentService;
 use isupipe_core::services::livestream_service::LivestreamService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::ServiceError;
+use std::convert::Infallible;
+use std::sync::Arc;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct GetLivestreamCommentsQuery {
     #[serde(default)]
     limit: String,
+    #[serde(default)]
+    before: String,
+    #[serde(default)]
+    after: String,
+    /// true ならカーソルを指定しない最初のページもオブジェクトで返す
+    #[serde(default)]
+    paginate: bool,
 }
 
 pub async fn get_livestream_comments_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
-    Query(GetLivestreamCommentsQuery { limit }): Query<GetLivestreamCommentsQuery>,
-) -> Result<axum::Json<Vec<LivestreamCommentResponse>>, Error> {
-    verify_user_session(&jar).await?;
-
+    Query(GetLivestreamCommentsQuery {
+        limit,
+        before,
+        after,
+        paginate,
+    }): Query<GetLivestreamCommentsQuery>,
+) -> Result<axum::response::Response, Error> {
     let livestream_id = LivestreamId::new(livestream_id);
 
     let limit = if limit.is_empty() {
@@ -37,15 +53,152 @@ pub async fn get_livestream_comments_handler<S: ServiceManager>(
         let limit: i64 = limit.parse().map_err(|_| Error::BadRequest("".into()))?;
         Some(limit)
     };
+    let cursor = parse_page_cursor(&before, &after, decode_cursor)?;
+    let paged = paginate || cursor.is_some();
+
+    let (livecomment_models, next_cursor) = if limit.is_none() && !paged {
+        let livecomment_models = service
+            .livestream_comment_service()
+            .find_all_by_livestream_id(&livestream_id, None)
+            .await?;
+        (livecomment_models, None)
+    } else {
+        let page = service
+            .livestream_comment_service()
+            .find_page_by_livestream_id(
+                &livestream_id,
+                cursor.as_ref(),
+                limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+            )
+            .await?;
+        (page.items, page.next_cursor.as_ref().map(encode_cursor))
+    };
+
+    let comments =
+        LivestreamCommentResponse::bulk_build_by_service(&service, &livecomment_models).await?;
+
+    Ok(list_response(comments, next_cursor, paged))
+}
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// 再接続時に未送信のコメントを一度に読み込む件数
+const BACKFILL_PAGE_SIZE: i64 = 100;
+
+enum CommentStreamState {
+    /// after より後のコメントを id 順に少しずつ送る
+    Backfill { after: i64 },
+    /// 未送信分を送り終えたら、その最後の id より後のコメントを届いた順に送る
+    /// 投稿の完了順は id 順とは限らないので、送ったコメントの id では絞り込まない
+    Live { watermark: i64 },
+}
+
+async fn find_missed_comments<S: ServiceManager>(
+    service: &S,
+    livestream_id: &LivestreamId,
+    after: i64,
+) -> Result<Vec<Arc<LivestreamCommentResponse>>, Error> {
     let livecomment_models = service
         .livestream_comment_service()
-        .find_all_by_livestream_id(&livestream_id, limit)
+        .find_all_by_livestream_id_after(
+            livestream_id,
+            &LivestreamCommentId::new(after),
+            BACKFILL_PAGE_SIZE,
+        )
         .await?;
+    let comments = LivestreamCommentResponse::bulk_build_by_service(service, &livecomment_models)
+        .await?
+        .into_iter()
+        .map(Arc::new)
+        .collect();
 
-    let comments =
-        LivestreamCommentResponse::bulk_build_by_service(&service, &livecomment_models).await?;
+    Ok(comments)
+}
 
-    Ok(axum::Json(comments))
+/// ライブコメントをServer-Sent Eventsで配信する
+/// 再接続時はLast-Event-IDヘッダ以降のコメントを先に送る
+pub async fn get_livestream_comments_stream_handler<S: ServiceManager + 'static>(
+    State(AppState {
+        service,
+        livestream_hub,
+        ..
+    }): State<AppState<S>>,
+    _: AuthUser,
+    headers: HeaderMap,
+    Path((livestream_id,)): Path<(i64,)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, Error> {
+    let livestream_id = LivestreamId::new(livestream_id);
+    service
+        .livestream_service()
+        .find(&livestream_id)
+        .await?
+        .ok_or(Error::NotFound("livestream not found".into()))?;
+
+    let last_event_id = match headers.get(LAST_EVENT_ID_HEADER) {
+        Some(value) => {
+            let last_event_id: i64 = value
+                .to_str()
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .ok_or(Error::BadRequest("invalid Last-Event-ID".into()))?;
+            Some(last_event_id)
+        }
+        None => None,
+    };
+
+    // 取りこぼしを防ぐため、未送信分を取得する前に購読しておく
+    let subscription = livestream_hub.subscribe(&livestream_id);
+    let state = match last_event_id {
+        Some(after) => CommentStreamState::Backfill { after },
+        None => CommentStreamState::Live { watermark: 0 },
+    };
+
+    let stream = stream::unfold((state, subscription), move |(state, mut subscription)| {
+        let service = service.clone();
+        let livestream_id = livestream_id.clone();
+        async move {
+            match state {
+                CommentStreamState::Backfill { after } => {
+                    let comments = match find_missed_comments(&service, &livestream_id, after).await
+                    {
+                        Ok(comments) => comments,
+                        Err(e) => {
+                            tracing::error!("failed to load missed livecomments: {:?}", e);
+                            return None;
+                        }
+                    };
+                    let last = comments.last().map_or(after, |c| *c.id.inner());
+                    let next = if (comments.len() as i64) < BACKFILL_PAGE_SIZE {
+                        CommentStreamState::Live { watermark: last }
+                    } else {
+                        CommentStreamState::Backfill { after: last }
+                    };
+                    Some((comments, (next, subscription)))
+                }
+                CommentStreamState::Live { watermark } => loop {
+                    // 受信が追いつかずに取りこぼした場合はストリームを終了し、クライアントに再接続させる
+                    let event = subscription.recv().await.ok()?;
+                    if let LivestreamEvent::Livecomment(c) = &*event {
+                        if *c.id.inner() > watermark {
+                            let next = CommentStreamState::Live { watermark };
+                            return Some((vec![c.clone()], (next, subscription)));
+                        }
+                    }
+                },
+            }
+        }
+    })
+    .flat_map(stream::iter)
+    .map(|c| {
+        let event = Event::default()
+            .event("livecomment")
+            .id(c.id.inner().to_string())
+            .json_data(&*c)
+            .unwrap_or_else(|_| Event::default().comment("serialize error"));
+        Ok(event)
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -55,25 +208,35 @@ pub struct PostLivecommentRequest {
 }
 
 pub async fn post_livecomment_handler<S: ServiceManager>(
-    State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    State(AppState {
+        service,
+        livestream_hub,
+        ..
+    }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
     axum::Json(req): axum::Json<PostLivecommentRequest>,
 ) -> Result<(StatusCode, axum::Json<LivestreamCommentResponse>), Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
     let livestream_id = LivestreamId::new(livestream_id);
 
+    let livecomment =
+        create_livecomment(&service, &livestream_hub, &user_id, &livestream_id, req).await?;
+
+    Ok((StatusCode::CREATED, axum::Json(livecomment)))
+}
+
+/// ライブコメントを投稿し、ライブ配信の購読者に配信する
+/// HTTPとWebSocketの両方から使う
+pub async fn create_livecomment<S: ServiceManager>(
+    service: &S,
+    livestream_hub: &LivestreamEventHub,
+    user_id: &UserId,
+    livestream_id: &LivestreamId,
+    req: PostLivecommentRequest,
+) -> Result<LivestreamCommentResponse, Error> {
     let livestream_model = service
         .livestream_service()
-        .find(&livestream_id)
+        .find(livestream_id)
         .await?
         .ok_or(Error::NotFound("livestream not found".into()))?;
 
@@ -83,7 +246,7 @@ pub async fn post_livecomment_handler<S: ServiceManager>(
         .create(&CreateLivestreamComment {
             user_id: user_id.clone(),
             livestream_id: livestream_model.id.clone(),
-            comment: req.comment.clone(),
+            comment: req.comment,
             tip: req.tip,
             created_at: now,
         })
@@ -92,9 +255,13 @@ pub async fn post_livecomment_handler<S: ServiceManager>(
     match result {
         Ok(comment) => {
             let livecomment =
-                LivestreamCommentResponse::build_by_service(&service, &comment).await?;
+                LivestreamCommentResponse::build_by_service(service, &comment).await?;
+            livestream_hub.publish(
+                &livestream_model.id,
+                Arc::new(LivestreamEvent::Livecomment(Arc::new(livecomment.clone()))),
+            );
 
-            Ok((StatusCode::CREATED, axum::Json(livecomment)))
+            Ok(livecomment)
         }
         Err(e) => match e {
             ServiceError::CommentMatchSpam => Err(Error::BadRequest(
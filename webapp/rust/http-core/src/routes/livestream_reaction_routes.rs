@@ -1,31 +1,45 @@
+use crate::auth::AuthUser;
+use crate::cursor::{
+    decode_cursor, encode_cursor, list_response, parse_page_cursor, DEFAULT_PAGE_LIMIT,
+};
 use crate::error::Error;
+use crate::hub::{LivestreamEvent, LivestreamEventHub};
 use crate::responses::reaction_response::ReactionResponse;
 use crate::state::AppState;
-use crate::{verify_user_session, DEFAULT_SESSION_ID_KEY, DEFAULT_USER_ID_KEY};
-use async_session::{CookieStore, SessionStore};
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum_extra::extract::SignedCookieJar;
 use chrono::Utc;
 use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::reaction::{CreateReaction, Reaction};
 use isupipe_core::models::user::UserId;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::reaction_service::ReactionService;
+use std::sync::Arc;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct GetReactionsQuery {
     #[serde(default)]
     pub limit: String,
+    #[serde(default)]
+    pub before: String,
+    #[serde(default)]
+    pub after: String,
+    /// true ならカーソルを指定しない最初のページもオブジェクトで返す
+    #[serde(default)]
+    pub paginate: bool,
 }
 
 pub async fn get_reactions_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
-    Query(GetReactionsQuery { limit }): Query<GetReactionsQuery>,
-) -> Result<axum::Json<Vec<ReactionResponse>>, Error> {
-    verify_user_session(&jar).await?;
+    Query(GetReactionsQuery {
+        limit,
+        before,
+        after,
+        paginate,
+    }): Query<GetReactionsQuery>,
+) -> Result<axum::response::Response, Error> {
     let livestream_id = LivestreamId::new(livestream_id);
 
     let limit = if limit.is_empty() {
@@ -34,14 +48,30 @@ pub async fn get_reactions_handler<S: ServiceManager>(
         let limit: i64 = limit.parse().map_err(|_| Error::BadRequest("".into()))?;
         Some(limit)
     };
-    let reaction_models = service
-        .reaction_service()
-        .find_all_by_livestream_id_limit(&livestream_id, limit)
-        .await?;
+    let cursor = parse_page_cursor(&before, &after, decode_cursor)?;
+    let paged = paginate || cursor.is_some();
+
+    let (reaction_models, next_cursor) = if limit.is_none() && !paged {
+        let reaction_models = service
+            .reaction_service()
+            .find_all_by_livestream_id_limit(&livestream_id, None)
+            .await?;
+        (reaction_models, None)
+    } else {
+        let page = service
+            .reaction_service()
+            .find_page_by_livestream_id(
+                &livestream_id,
+                cursor.as_ref(),
+                limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+            )
+            .await?;
+        (page.items, page.next_cursor.as_ref().map(encode_cursor))
+    };
 
     let reactions = ReactionResponse::bulk_build_by_service(&service, &reaction_models).await?;
 
-    Ok(axum::Json(reactions))
+    Ok(list_response(reactions, next_cursor, paged))
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -50,23 +80,32 @@ pub struct PostReactionRequest {
 }
 
 pub async fn post_reaction_handler<S: ServiceManager>(
-    State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    State(AppState {
+        service,
+        livestream_hub,
+        ..
+    }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
     axum::Json(req): axum::Json<PostReactionRequest>,
 ) -> Result<(StatusCode, axum::Json<ReactionResponse>), Error> {
-    verify_user_session(&jar).await?;
+    let livestream_id = LivestreamId::new(livestream_id);
 
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
+    let reaction =
+        create_reaction(&service, &livestream_hub, &user_id, &livestream_id, req).await?;
 
-    let livestream_id = LivestreamId::new(livestream_id);
+    Ok((StatusCode::CREATED, axum::Json(reaction)))
+}
 
+/// リアクションを投稿し、ライブ配信の購読者に配信する
+/// HTTPとWebSocketの両方から使う
+pub async fn create_reaction<S: ServiceManager>(
+    service: &S,
+    livestream_hub: &LivestreamEventHub,
+    user_id: &UserId,
+    livestream_id: &LivestreamId,
+    req: PostReactionRequest,
+) -> Result<ReactionResponse, Error> {
     let created_at = Utc::now().timestamp();
     let reaction_id = service
         .reaction_service()
@@ -79,7 +118,7 @@ pub async fn post_reaction_handler<S: ServiceManager>(
         .await?;
 
     let reaction = ReactionResponse::build_by_service(
-        &service,
+        service,
         &Reaction {
             id: reaction_id,
             user_id: user_id.clone(),
@@ -89,6 +128,10 @@ pub async fn post_reaction_handler<S: ServiceManager>(
         },
     )
     .await?;
+    livestream_hub.publish(
+        livestream_id,
+        Arc::new(LivestreamEvent::Reaction(Arc::new(reaction.clone()))),
+    );
 
-    Ok((StatusCode::CREATED, axum::Json(reaction)))
+    Ok(reaction)
 }
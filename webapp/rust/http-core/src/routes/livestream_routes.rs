@@ -1,30 +1,40 @@
+use crate::auth::AuthUser;
+use crate::cursor::{
+    decode_id_cursor, encode_id_cursor, list_response, parse_page_cursor, DEFAULT_PAGE_LIMIT,
+};
 use crate::error::Error;
+use crate::hub::LivestreamEvent;
 use crate::responses::livestream_response::LivestreamResponse;
 use crate::routes::livestream_comment_report_routes::{
     get_livecomment_reports_handler, report_livecomment_handler,
 };
-use crate::routes::livestream_comment_routes::post_livecomment_handler;
+use crate::routes::livestream_comment_routes::{
+    get_livestream_comments_handler, get_livestream_comments_stream_handler,
+    post_livecomment_handler,
+};
 use crate::routes::livestream_reaction_routes::{get_reactions_handler, post_reaction_handler};
+use crate::routes::livestream_socket_routes::livestream_socket_handler;
 use crate::state::AppState;
-use crate::{verify_user_session, DEFAULT_SESSION_ID_KEY, DEFAULT_USER_ID_KEY};
-use async_session::{CookieStore, SessionStore};
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Router;
-use axum_extra::extract::SignedCookieJar;
 use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use isupipe_core::models::livestream::{CreateLivestream, Livestream, LivestreamId};
 use isupipe_core::models::livestream_statistics::LivestreamStatistics;
 use isupipe_core::models::livestream_viewers_history::CreateLivestreamViewersHistory;
-use isupipe_core::models::ng_word::{CreateNgWord, NgWord, NgWordId};
+use isupipe_core::models::ng_word::{CreateNgWord, NgWord, NgWordId, NgWordMatchMode};
+use isupipe_core::models::ng_word_purge_job::{NgWordPurgeJob, NgWordPurgeJobId};
 use isupipe_core::models::tag::{TagId, TagName};
-use isupipe_core::models::user::UserId;
 use isupipe_core::services::livestream_service::LivestreamService;
 use isupipe_core::services::livestream_statistics_service::LivestreamStatisticsService;
 use isupipe_core::services::livestream_viewers_history_service::LivestreamViewersHistoryService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::ng_word_service::NgWordService;
 use isupipe_core::services::ServiceError;
+use std::sync::Arc;
+
+#[cfg(test)]
+mod livestreams_routes;
 
 // handle /api/livestreams/
 pub fn livestreams_routes<S: ServiceManager + 'static>() -> Router<AppState<S>> {
@@ -46,7 +56,11 @@ fn livestream_routes<S: ServiceManager + 'static>() -> Router<AppState<S>> {
     Router::new()
         .route(
             "/livecomment",
-            axum::routing::get(get_livestream_handler).post(post_livecomment_handler),
+            axum::routing::get(get_livestream_comments_handler).post(post_livecomment_handler),
+        )
+        .route(
+            "/livecomment/stream",
+            axum::routing::get(get_livestream_comments_stream_handler),
         )
         .route(
             "/livecomment/:livecomment_id/report",
@@ -62,8 +76,13 @@ fn livestream_routes<S: ServiceManager + 'static>() -> Router<AppState<S>> {
         )
         .route("/ngwords", axum::routing::get(get_ngwords))
         .route("/moderate", axum::routing::post(moderate_handler))
+        .route(
+            "/moderate/:job_id",
+            axum::routing::get(get_moderate_job_handler),
+        )
         .route("/enter", axum::routing::post(enter_livestream_handler))
         .route("/exit", axum::routing::post(exit_livestream_handler))
+        .route("/ws", axum::routing::get(livestream_socket_handler))
         .route(
             "/statistics",
             axum::routing::get(get_livestream_statistics_handler),
@@ -83,23 +102,13 @@ pub struct ReserveLivestreamRequest {
 
 pub async fn reserve_livestream_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
     axum::Json(req): axum::Json<ReserveLivestreamRequest>,
 ) -> Result<(StatusCode, axum::Json<LivestreamResponse>), Error> {
-    verify_user_session(&jar).await?;
-
     if req.tags.iter().any(|&tag_id| tag_id > 103) {
         tracing::error!("unexpected tags: {:?}", req);
     }
 
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
-
     // 2023/11/25 10:00からの１年間の期間内であるかチェック
     let term_start_at = Utc.from_utc_datetime(
         &NaiveDate::from_ymd_opt(2023, 11, 25)
@@ -168,6 +177,13 @@ pub struct SearchLivestreamsQuery {
     pub tag: String,
     #[serde(default)]
     pub limit: String,
+    #[serde(default)]
+    pub before: String,
+    #[serde(default)]
+    pub after: String,
+    /// true ならカーソルを指定しない最初のページもオブジェクトで返す
+    #[serde(default)]
+    pub paginate: bool,
 }
 
 pub async fn search_livestreams_handler<S: ServiceManager>(
@@ -175,9 +191,17 @@ pub async fn search_livestreams_handler<S: ServiceManager>(
     Query(SearchLivestreamsQuery {
         tag: key_tag_name,
         limit,
+        before,
+        after,
+        paginate,
     }): Query<SearchLivestreamsQuery>,
-) -> Result<axum::Json<Vec<LivestreamResponse>>, Error> {
-    let livestream_models: Vec<Livestream> = if key_tag_name.is_empty() {
+) -> Result<axum::response::Response, Error> {
+    let cursor = parse_page_cursor(&before, &after, |c| {
+        decode_id_cursor(c).map(LivestreamId::new)
+    })?;
+    let paged = paginate || cursor.is_some();
+
+    let (livestream_models, next_cursor): (Vec<Livestream>, _) = if key_tag_name.is_empty() {
         let limit = if limit.is_empty() {
             None
         } else {
@@ -188,38 +212,55 @@ pub async fn search_livestreams_handler<S: ServiceManager>(
             Some(limit)
         };
 
-        service
-            .livestream_service()
-            .find_recent_livestreams(limit)
-            .await?
+        if limit.is_none() && !paged {
+            let livestream_models = service
+                .livestream_service()
+                .find_recent_livestreams(None)
+                .await?;
+            (livestream_models, None)
+        } else {
+            let page = service
+                .livestream_service()
+                .find_recent_livestreams_page(cursor.as_ref(), limit.unwrap_or(DEFAULT_PAGE_LIMIT))
+                .await?;
+            let next_cursor = page.next_cursor.map(|id| encode_id_cursor(*id.inner()));
+            (page.items, next_cursor)
+        }
     } else {
         let key_tag_name = TagName::new(key_tag_name);
-        // タグによる取得
-        service
-            .livestream_service()
-            .find_recent_by_tag_name(&key_tag_name)
-            .await?
+        // タグによる取得。limit はこれまで無視していたので、ページングを要求されたときだけ使う
+        if paged {
+            let limit = if limit.is_empty() {
+                DEFAULT_PAGE_LIMIT
+            } else {
+                limit
+                    .parse()
+                    .map_err(|_| Error::BadRequest("failed to parse limit".into()))?
+            };
+            let page = service
+                .livestream_service()
+                .find_recent_by_tag_name_page(&key_tag_name, cursor.as_ref(), limit)
+                .await?;
+            let next_cursor = page.next_cursor.map(|id| encode_id_cursor(*id.inner()));
+            (page.items, next_cursor)
+        } else {
+            let livestream_models = service
+                .livestream_service()
+                .find_recent_by_tag_name(&key_tag_name)
+                .await?;
+            (livestream_models, None)
+        }
     };
 
     let livestreams =
         LivestreamResponse::bulk_build_by_service(&service, &livestream_models).await?;
 
-    Ok(axum::Json(livestreams))
+    Ok(list_response(livestreams, next_cursor, paged))
 }
 pub async fn get_my_livestreams_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
 ) -> Result<axum::Json<Vec<LivestreamResponse>>, Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
-
     let livestream_models = service
         .livestream_service()
         .find_all_by_user_id(&user_id)
@@ -233,10 +274,9 @@ pub async fn get_my_livestreams_handler<S: ServiceManager>(
 
 pub async fn get_livestream_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
 ) -> Result<axum::Json<LivestreamResponse>, Error> {
-    verify_user_session(&jar).await?;
     let livestream_id = LivestreamId::new(livestream_id);
 
     let livestream_model = service.livestream_service().find(&livestream_id).await?;
@@ -254,18 +294,9 @@ pub async fn get_livestream_handler<S: ServiceManager>(
 }
 pub async fn get_ngwords<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
 ) -> Result<axum::Json<Vec<NgWord>>, Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
     let livestream_id = LivestreamId::new(livestream_id);
 
     let ng_words = service
@@ -278,30 +309,25 @@ pub async fn get_ngwords<S: ServiceManager>(
 #[derive(Debug, serde::Deserialize)]
 pub struct ModerateRequest {
     ng_word: String,
+    /// 省略時は部分一致
+    #[serde(default)]
+    match_mode: NgWordMatchMode,
 }
 
 #[derive(Debug, serde::Serialize)]
 pub struct ModerateResponse {
     word_id: NgWordId,
+    /// 過去のコメントを消すジョブ。進み具合は GET /moderate/:job_id で確認する
+    purge_job: NgWordPurgeJob,
 }
 
 // NGワードを登録
 pub async fn moderate_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
     axum::Json(req): axum::Json<ModerateRequest>,
 ) -> Result<(StatusCode, axum::Json<ModerateResponse>), Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
-
     let livestream_id = LivestreamId::new(livestream_id);
 
     // 配信者自身の配信に対するmoderateなのかを検証
@@ -316,12 +342,13 @@ pub async fn moderate_handler<S: ServiceManager>(
     }
 
     let created_at = Utc::now().timestamp();
-    let word_id = service
+    let (word_id, purge_job) = service
         .ng_word_service()
         .create(&CreateNgWord {
             user_id: user_id.clone(),
             livestream_id: livestream_id.clone(),
             word: req.ng_word,
+            match_mode: req.match_mode,
             created_at,
         })
         .await?;
@@ -330,25 +357,52 @@ pub async fn moderate_handler<S: ServiceManager>(
         StatusCode::CREATED,
         axum::Json(ModerateResponse {
             word_id: word_id.clone(),
+            purge_job,
         }),
     ))
 }
 
+// NGワード登録時に始めた、過去のコメントを消すジョブの進み具合
+pub async fn get_moderate_job_handler<S: ServiceManager>(
+    State(AppState { service, .. }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path((livestream_id, job_id)): Path<(i64, i64)>,
+) -> Result<axum::Json<NgWordPurgeJob>, Error> {
+    let livestream_id = LivestreamId::new(livestream_id);
+    let job_id = NgWordPurgeJobId::new(job_id);
+
+    let is_exist = service
+        .livestream_service()
+        .exist_by_id_and_user_id(&livestream_id, &user_id)
+        .await?;
+    if !is_exist {
+        return Err(Error::BadRequest(
+            "A streamer can't moderate livestreams that other streamers own".into(),
+        ));
+    }
+
+    let job = service
+        .ng_word_service()
+        .find_purge_job(&livestream_id, &job_id)
+        .await?;
+    match job {
+        Some(job) => Ok(axum::Json(job)),
+        None => Err(Error::NotFound(
+            "not found moderation job that has the given id".into(),
+        )),
+    }
+}
+
 // viewerテーブルの廃止
 pub async fn enter_livestream_handler<S: ServiceManager>(
-    State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    State(AppState {
+        service,
+        livestream_hub,
+        ..
+    }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
 ) -> Result<(), Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
     let livestream_id = LivestreamId::new(livestream_id);
 
     let created_at = Utc::now().timestamp();
@@ -361,39 +415,34 @@ pub async fn enter_livestream_handler<S: ServiceManager>(
             created_at,
         })
         .await?;
+    livestream_hub.publish(&livestream_id, Arc::new(LivestreamEvent::Enter { user_id }));
 
     Ok(())
 }
 pub async fn exit_livestream_handler<S: ServiceManager>(
-    State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    State(AppState {
+        service,
+        livestream_hub,
+        ..
+    }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
 ) -> Result<(), Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
     let livestream_id = LivestreamId::new(livestream_id);
 
     service
         .livestream_viewers_history_service()
         .delete_by_livestream_id_and_user_id(&livestream_id, &user_id)
         .await?;
+    livestream_hub.publish(&livestream_id, Arc::new(LivestreamEvent::Exit { user_id }));
 
     Ok(())
 }
 pub async fn get_livestream_statistics_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((livestream_id,)): Path<(i64,)>,
 ) -> Result<axum::Json<LivestreamStatistics>, Error> {
-    verify_user_session(&jar).await?;
-
     let livestream_id = LivestreamId::new(livestream_id);
 
     let livestream = service
@@ -0,0 +1,183 @@
+use crate::client_ip::TrustedProxies;
+use crate::compat::BenchCompat;
+use crate::cursor::NEXT_CURSOR_HEADER;
+use crate::hub::LivestreamEventHub;
+use crate::routes::routes;
+use crate::session::SessionConfig;
+use crate::state::AppState;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::response::IntoResponse as _;
+use axum_extra::extract::cookie::Key;
+use axum_extra::extract::SignedCookieJar;
+use chrono::Utc;
+use fake::{Fake, Faker};
+use isupipe_core::commands::pdnsutil_command::{RecordType, SubdomainRecordConfig};
+use isupipe_core::db::{get_db_pool, DBPool};
+use isupipe_core::models::livestream::{CreateLivestream, LivestreamId};
+use isupipe_core::models::livestream_comment::CreateLivestreamComment;
+use isupipe_core::models::user::{CreateUser, UserName};
+use isupipe_core::repos::livestream_comment_repository::LivestreamCommentRepository;
+use isupipe_core::repos::livestream_repository::LivestreamRepository;
+use isupipe_core::repos::theme_repository::ThemeRepository;
+use isupipe_core::repos::user_repository::UserRepository;
+use isupipe_core::services::session_service::{HaveSessionService, SessionService};
+use isupipe_core::validation::username::UsernamePolicy;
+use isupipe_infra::commands::pdnsutil_cli_command::PDNSUtilCliCommandInfra;
+use isupipe_infra::commands::pdnsutil_command::PDNSUtilCommandInfra;
+use isupipe_infra::notifiers::PasswordResetNotifierInfra;
+use isupipe_infra::repos::livestream_comment_repository::LivestreamCommentRepositoryInfra;
+use isupipe_infra::repos::livestream_repository::LivestreamRepositoryInfra;
+use isupipe_infra::repos::theme_repository::ThemeRepositoryInfra;
+use isupipe_infra::repos::user_repository::UserRepositoryInfra;
+use isupipe_infra::services::manager::ServiceManagerInfra;
+use std::sync::Arc;
+use tower::ServiceExt as _;
+
+fn app_state(db_pool: DBPool) -> AppState<ServiceManagerInfra> {
+    let subdomain_record =
+        SubdomainRecordConfig::new("u.isucon.dev", RecordType::A, 0, "127.0.0.1").unwrap();
+    let pdnsutil_command =
+        PDNSUtilCommandInfra::Cli(PDNSUtilCliCommandInfra::new(subdomain_record.clone()));
+    let service = ServiceManagerInfra::new(
+        db_pool,
+        None,
+        PasswordResetNotifierInfra::Log,
+        pdnsutil_command,
+        UsernamePolicy::default(),
+    );
+
+    AppState {
+        service,
+        key: Key::generate(),
+        session_config: Arc::new(SessionConfig::default()),
+        subdomain_record: Arc::new(subdomain_record),
+        livestream_hub: Arc::new(LivestreamEventHub::new()),
+        trusted_proxies: Arc::new(TrustedProxies::default()),
+        bench_compat: Arc::new(BenchCompat::default()),
+    }
+}
+
+/// コメントを3件持つ配信と、ログイン済みのセッションの Cookie を用意する
+async fn seed(db_pool: &DBPool, state: &AppState<ServiceManagerInfra>) -> (LivestreamId, String) {
+    let mut conn = db_pool.acquire().await.unwrap();
+
+    let mut user: CreateUser = Faker.fake();
+    user.name = format!("route{}", uuid::Uuid::new_v4().simple());
+    let user_id = UserRepositoryInfra {}
+        .create(&mut conn, &user, "hashed")
+        .await
+        .unwrap();
+    ThemeRepositoryInfra {}
+        .create(&mut conn, &user_id, false)
+        .await
+        .unwrap();
+
+    let livestream_id = LivestreamRepositoryInfra {}
+        .create(
+            &mut conn,
+            &CreateLivestream {
+                user_id: user_id.clone(),
+                title: "title".to_owned(),
+                description: "description".to_owned(),
+                playlist_url: "https://example.com/playlist.m3u8".to_owned(),
+                thumbnail_url: "https://example.com/thumbnail.jpg".to_owned(),
+                start_at: 0,
+                end_at: 3600,
+            },
+        )
+        .await
+        .unwrap();
+    for created_at in [100, 200, 300] {
+        LivestreamCommentRepositoryInfra {}
+            .create(
+                &mut conn,
+                &CreateLivestreamComment {
+                    user_id: user_id.clone(),
+                    livestream_id: livestream_id.clone(),
+                    comment: format!("comment at {}", created_at),
+                    tip: 0,
+                    created_at,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    let expires_at = Utc::now().timestamp() + 3600;
+    let session = state
+        .service
+        .session_service()
+        .create(&user_id, expires_at)
+        .await
+        .unwrap();
+    let cookie = state
+        .session_config
+        .session_cookie(&session, &UserName::new(user.name))
+        .await
+        .unwrap();
+    let response = SignedCookieJar::new(state.key.clone())
+        .add(cookie)
+        .into_response();
+    let set_cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+    let cookie = set_cookie.split(';').next().unwrap().to_owned();
+
+    (livestream_id, cookie)
+}
+
+async fn get_json(
+    state: &AppState<ServiceManagerInfra>,
+    uri: &str,
+    cookie: &str,
+) -> (Option<String>, serde_json::Value) {
+    let request = Request::builder()
+        .uri(uri)
+        .header(header::COOKIE, cookie)
+        .body(Body::empty())
+        .unwrap();
+    let response = routes()
+        .with_state(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let next_cursor = response
+        .headers()
+        .get(NEXT_CURSOR_HEADER)
+        .map(|value| value.to_str().unwrap().to_owned());
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    (next_cursor, serde_json::from_slice(&body).unwrap())
+}
+
+fn created_ats(comments: &serde_json::Value) -> Vec<i64> {
+    comments
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|comment| comment["created_at"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn livecomment_cursor_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let state = app_state(db_pool.clone());
+    let (livestream_id, cookie) = seed(&db_pool, &state).await;
+    let uri = format!("/api/livestream/{}/livecomment", livestream_id.inner());
+
+    // limit だけなら既存のクライアント向けに配列を返し、続きのカーソルはヘッダで返す
+    let (next_cursor, body) = get_json(&state, &format!("{}?limit=2", uri), &cookie).await;
+    assert_eq!(created_ats(&body), vec![300, 200]);
+    let next_cursor = next_cursor.unwrap();
+
+    let (header_cursor, body) = get_json(
+        &state,
+        &format!("{}?limit=2&before={}", uri, next_cursor),
+        &cookie,
+    )
+    .await;
+    assert_eq!(created_ats(&body["items"]), vec![100]);
+    assert!(body["next_cursor"].is_null());
+    assert_eq!(header_cursor, None);
+}
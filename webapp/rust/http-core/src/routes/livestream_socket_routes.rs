@@ -0,0 +1,205 @@
+use crate::auth::AuthUser;
+use crate::error::Error;
+use crate::hub::{LivestreamEvent, LivestreamEventHub};
+use crate::routes::livestream_comment_routes::{create_livecomment, PostLivecommentRequest};
+use crate::routes::livestream_reaction_routes::{create_reaction, PostReactionRequest};
+use crate::state::AppState;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::Response;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::user::UserId;
+use isupipe_core::services::livestream_service::LivestreamService;
+use isupipe_core::services::manager::ServiceManager;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+#[cfg(test)]
+mod client_error_message;
+#[cfg(test)]
+mod parse_client_message;
+#[cfg(test)]
+mod presence;
+
+/// 内部エラーの代わりにクライアントへ返すメッセージ
+const INTERNAL_ERROR_MESSAGE: &str = "internal server error";
+
+/// クライアントからWebSocketで送られてくるメッセージ
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Livecomment(PostLivecommentRequest),
+    Reaction(PostReactionRequest),
+}
+
+/// 送信元のクライアントにだけ返すエラー
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+enum ErrorMessage {
+    Error { message: String },
+}
+
+/// ライブコメント、リアクション、入退室をWebSocketでまとめて配信する
+/// クライアントはこのソケットからライブコメントやリアクションを投稿できる
+pub async fn livestream_socket_handler<S: ServiceManager + 'static>(
+    State(AppState {
+        service,
+        livestream_hub,
+        ..
+    }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    Path((livestream_id,)): Path<(i64,)>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, Error> {
+    let livestream_id = LivestreamId::new(livestream_id);
+
+    service
+        .livestream_service()
+        .find(&livestream_id)
+        .await?
+        .ok_or(Error::NotFound("livestream not found".into()))?;
+
+    Ok(ws.on_upgrade(move |socket| {
+        handle_livestream_socket(socket, service, livestream_hub, user_id, livestream_id)
+    }))
+}
+
+async fn handle_livestream_socket<S: ServiceManager>(
+    mut socket: WebSocket,
+    service: S,
+    livestream_hub: Arc<LivestreamEventHub>,
+    user_id: UserId,
+    livestream_id: LivestreamId,
+) {
+    let mut subscription = livestream_hub.subscribe(&livestream_id);
+    // 切断やタスクの中断で破棄されたときに退室を通知する
+    let _presence = Presence::enter(livestream_hub.clone(), &livestream_id, &user_id);
+
+    loop {
+        tokio::select! {
+            event = subscription.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    // 受信が追いついていない分は捨てて最新のイベントから送り直す
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("livestream socket lagged: skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if send_json(&mut socket, &*event).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let result =
+                    handle_client_message(&service, &livestream_hub, &user_id, &livestream_id, &text)
+                        .await;
+                if let Err(e) = result {
+                    let message = ErrorMessage::Error {
+                        message: client_error_message(&e),
+                    };
+                    if send_json(&mut socket, &message).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn handle_client_message<S: ServiceManager>(
+    service: &S,
+    livestream_hub: &LivestreamEventHub,
+    user_id: &UserId,
+    livestream_id: &LivestreamId,
+    text: &str,
+) -> Result<(), Error> {
+    let message = parse_client_message(text)?;
+
+    // 投稿したイベントはハブ経由で送信元にも届くので、ここでは返さない
+    match message {
+        ClientMessage::Livecomment(req) => {
+            create_livecomment(service, livestream_hub, user_id, livestream_id, req).await?;
+        }
+        ClientMessage::Reaction(req) => {
+            create_reaction(service, livestream_hub, user_id, livestream_id, req).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_client_message(text: &str) -> Result<ClientMessage, Error> {
+    serde_json::from_str(text)
+        .map_err(|e| Error::BadRequest(format!("invalid message: {}", e).into()))
+}
+
+/// クライアントの誤りはそのまま伝え、内部エラーの詳細はログにだけ残す
+fn client_error_message(e: &Error) -> String {
+    match e {
+        Error::BadRequest(_)
+        | Error::Validation(_)
+        | Error::Unauthorized(_)
+        | Error::Forbidden(_)
+        | Error::NotFound(_)
+        | Error::TooManyRequests { .. } => e.to_string(),
+        _ => {
+            tracing::error!("livestream socket error: {}", e);
+            INTERNAL_ERROR_MESSAGE.to_owned()
+        }
+    }
+}
+
+/// ソケットがつながっている間の入室。作ったときに入室を、破棄したときに退室を通知する
+struct Presence {
+    livestream_hub: Arc<LivestreamEventHub>,
+    livestream_id: LivestreamId,
+    user_id: UserId,
+}
+
+impl Presence {
+    fn enter(
+        livestream_hub: Arc<LivestreamEventHub>,
+        livestream_id: &LivestreamId,
+        user_id: &UserId,
+    ) -> Self {
+        livestream_hub.publish(
+            livestream_id,
+            Arc::new(LivestreamEvent::Enter {
+                user_id: user_id.clone(),
+            }),
+        );
+
+        Self {
+            livestream_hub,
+            livestream_id: livestream_id.clone(),
+            user_id: user_id.clone(),
+        }
+    }
+}
+
+impl Drop for Presence {
+    fn drop(&mut self) {
+        self.livestream_hub.publish(
+            &self.livestream_id,
+            Arc::new(LivestreamEvent::Exit {
+                user_id: self.user_id.clone(),
+            }),
+        );
+    }
+}
+
+async fn send_json<T: serde::Serialize>(socket: &mut WebSocket, message: &T) -> Result<(), Error> {
+    let text =
+        serde_json::to_string(message).map_err(|e| Error::InternalServerError(e.to_string()))?;
+    socket
+        .send(Message::Text(text))
+        .await
+        .map_err(|e| Error::InternalServerError(e.to_string()))
+}
@@ -0,0 +1,22 @@
+use crate::error::Error;
+use crate::routes::livestream_socket_routes::{client_error_message, INTERNAL_ERROR_MESSAGE};
+
+#[test]
+fn client_error_case() {
+    let e = Error::BadRequest("invalid message".into());
+    assert_eq!(client_error_message(&e), "invalid message");
+    let e = Error::NotFound("livestream not found".into());
+    assert_eq!(client_error_message(&e), "not found: livestream not found");
+}
+
+#[test]
+fn internal_error_case() {
+    // 内部の詳細はクライアントに返さない
+    for e in [
+        Error::InternalServerError("connection refused: 10.0.0.1:3306".to_owned()),
+        Error::Sqlx(sqlx::Error::RowNotFound),
+        Error::Io(std::io::Error::other("disk full")),
+    ] {
+        assert_eq!(client_error_message(&e), INTERNAL_ERROR_MESSAGE);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::error::Error;
+use crate::routes::livestream_socket_routes::{parse_client_message, ClientMessage};
+
+#[test]
+fn parsed_case() {
+    let message =
+        parse_client_message(r#"{"type":"livecomment","comment":"hello","tip":10}"#).unwrap();
+    assert!(
+        matches!(message, ClientMessage::Livecomment(req) if req.comment == "hello" && req.tip == 10)
+    );
+
+    let message = parse_client_message(r#"{"type":"reaction","emoji_name":"tada"}"#).unwrap();
+    assert!(matches!(message, ClientMessage::Reaction(req) if req.emoji_name == "tada"));
+}
+
+#[test]
+fn invalid_case() {
+    for text in [
+        "not json",
+        r#"{"type":"unknown"}"#,
+        r#"{"type":"reaction"}"#,
+    ] {
+        assert!(matches!(
+            parse_client_message(text),
+            Err(Error::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,21 @@
+use crate::hub::{LivestreamEvent, LivestreamEventHub};
+use crate::routes::livestream_socket_routes::Presence;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::user::UserId;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn enter_and_exit_case() {
+    let hub = Arc::new(LivestreamEventHub::new());
+    let livestream_id = LivestreamId::new(1);
+    let user_id = UserId::new(2);
+    let mut subscription = hub.subscribe(&livestream_id);
+
+    let presence = Presence::enter(hub.clone(), &livestream_id, &user_id);
+    let event = subscription.recv().await.unwrap();
+    assert!(matches!(&*event, LivestreamEvent::Enter { user_id: id } if id == &user_id));
+
+    drop(presence);
+    let event = subscription.recv().await.unwrap();
+    assert!(matches!(&*event, LivestreamEvent::Exit { user_id: id } if id == &user_id));
+}
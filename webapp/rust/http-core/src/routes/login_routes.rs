@@ -1,15 +1,14 @@
+use crate::auth::AuthUser;
+use crate::client_ip::ClientIp;
 use crate::error::Error;
 use crate::state::AppState;
-use crate::{
-    DEFAULT_SESSION_ID_KEY, DEFAULT_USERNAME_KEY, DEFAULT_USER_ID_KEY, DEFUALT_SESSION_EXPIRES_KEY,
-};
-use async_session::{CookieStore, SessionStore};
 use axum::extract::State;
 use axum_extra::extract::SignedCookieJar;
 use chrono::Utc;
+use isupipe_core::services::login_throttle_service::LoginThrottleService;
 use isupipe_core::services::manager::ServiceManager;
+use isupipe_core::services::session_service::SessionService;
 use isupipe_core::services::user_service::UserService;
-use uuid::Uuid;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct LoginRequest {
@@ -20,40 +19,86 @@ pub struct LoginRequest {
 // ユーザログインAPI
 // POST /api/login
 pub async fn login_handler<S: ServiceManager>(
-    State(AppState { service, .. }): State<AppState<S>>,
-    mut jar: SignedCookieJar,
+    State(AppState {
+        service,
+        session_config,
+        ..
+    }): State<AppState<S>>,
+    jar: SignedCookieJar,
+    ClientIp(client_ip): ClientIp,
     axum::Json(req): axum::Json<LoginRequest>,
 ) -> Result<(SignedCookieJar, ()), Error> {
-    let user_model = service
-        .user_service()
-        .find_by_name(&req.username)
-        .await?
-        .ok_or(Error::Unauthorized("invalid username or password".into()))?;
-
-    let hashed_password = user_model.hashed_password.unwrap();
-    if !bcrypt::verify(&req.password, &hashed_password)? {
+    let now = Utc::now();
+    let client_ip = client_ip.map(|ip| ip.to_string());
+    // ロック中はパスワードを検証しない
+    service
+        .login_throttle_service()
+        .ensure_allowed(&req.username, client_ip.as_deref(), now.timestamp())
+        .await?;
+
+    let user_model = service.user_service().find_by_name(&req.username).await?;
+    let verified = match &user_model {
+        Some(user_model) => {
+            let hashed_password = user_model.hashed_password.as_deref().unwrap();
+            bcrypt::verify(&req.password, hashed_password)?
+        }
+        None => false,
+    };
+    let Some(user_model) = user_model.filter(|_| verified) else {
+        service
+            .login_throttle_service()
+            .record_failure(&req.username, client_ip.as_deref(), now.timestamp())
+            .await?;
         return Err(Error::Unauthorized("invalid username or password".into()));
-    }
-
-    let session_end_at = Utc::now() + chrono::Duration::hours(1);
-    let session_id = Uuid::new_v4().to_string();
-    let mut sess = async_session::Session::new();
-    sess.insert(DEFAULT_SESSION_ID_KEY, session_id).unwrap();
-    sess.insert(DEFAULT_USER_ID_KEY, user_model.id).unwrap();
-    sess.insert(DEFAULT_USERNAME_KEY, user_model.name.inner())
-        .unwrap();
-    sess.insert(DEFUALT_SESSION_EXPIRES_KEY, session_end_at.timestamp())
-        .unwrap();
-    let cookie_store = CookieStore::new();
-    if let Some(cookie_value) = cookie_store.store_session(sess).await? {
-        let cookie =
-            axum_extra::extract::cookie::Cookie::build(DEFAULT_SESSION_ID_KEY, cookie_value)
-                .domain("u.isucon.dev")
-                .max_age(time::Duration::minutes(1000))
-                .path("/")
-                .finish();
-        jar = jar.add(cookie);
-    }
-
-    Ok((jar, ()))
+    };
+    service
+        .login_throttle_service()
+        .record_success(&req.username)
+        .await?;
+
+    let session_end_at = now + session_config.lifetime;
+    let session = service
+        .session_service()
+        .create(&user_model.id, session_end_at.timestamp())
+        .await?;
+    let cookie = session_config
+        .session_cookie(&session, &user_model.name)
+        .await?;
+
+    Ok((jar.add(cookie), ()))
+}
+
+// ログアウトAPI
+// POST /api/logout
+pub async fn logout_handler<S: ServiceManager>(
+    State(AppState {
+        service,
+        session_config,
+        ..
+    }): State<AppState<S>>,
+    jar: SignedCookieJar,
+    AuthUser { session_id, .. }: AuthUser,
+) -> Result<(SignedCookieJar, ()), Error> {
+    service.session_service().revoke(&session_id).await?;
+
+    Ok((jar.remove(session_config.removal_cookie()), ()))
+}
+
+// 全端末からのログアウトAPI
+// POST /api/logout/all
+pub async fn logout_all_handler<S: ServiceManager>(
+    State(AppState {
+        service,
+        session_config,
+        ..
+    }): State<AppState<S>>,
+    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<(SignedCookieJar, ()), Error> {
+    service
+        .session_service()
+        .revoke_all_by_user_id(&user_id)
+        .await?;
+
+    Ok((jar.remove(session_config.removal_cookie()), ()))
 }
@@ -0,0 +1,79 @@
+use crate::auth::AuthUser;
+use crate::error::Error;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use chrono::Utc;
+use isupipe_core::services::manager::ServiceManager;
+use isupipe_core::services::password_service::PasswordService;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PutPasswordRequest {
+    current_password: String,
+    new_password: String,
+}
+
+// パスワード変更API
+// PUT /api/user/me/password
+pub async fn put_password_handler<S: ServiceManager>(
+    State(AppState { service, .. }): State<AppState<S>>,
+    AuthUser {
+        id: user_id,
+        session_id,
+        ..
+    }: AuthUser,
+    axum::Json(req): axum::Json<PutPasswordRequest>,
+) -> Result<StatusCode, Error> {
+    // 変更した本人のセッション以外は失効させる
+    service
+        .password_service()
+        .change_password(
+            &user_id,
+            &session_id,
+            &req.current_password,
+            &req.new_password,
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PostPasswordResetRequest {
+    username: String,
+}
+
+// パスワード再設定の申請API
+// POST /api/password-reset
+// ユーザーの有無に関わらず同じレスポンスを返す
+pub async fn post_password_reset_handler<S: ServiceManager>(
+    State(AppState { service, .. }): State<AppState<S>>,
+    axum::Json(req): axum::Json<PostPasswordResetRequest>,
+) -> Result<StatusCode, Error> {
+    service
+        .password_service()
+        .request_reset(&req.username, Utc::now().timestamp())
+        .await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PostPasswordResetConfirmRequest {
+    token: String,
+    new_password: String,
+}
+
+// パスワード再設定API
+// POST /api/password-reset/confirm
+pub async fn post_password_reset_confirm_handler<S: ServiceManager>(
+    State(AppState { service, .. }): State<AppState<S>>,
+    axum::Json(req): axum::Json<PostPasswordResetConfirmRequest>,
+) -> Result<StatusCode, Error> {
+    service
+        .password_service()
+        .confirm_reset(&req.token, &req.new_password, Utc::now().timestamp())
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -6,6 +6,7 @@ use axum::http::StatusCode;
 use isupipe_core::models::user::CreateUser;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::user_service::UserService;
+use isupipe_core::services::ServiceError;
 
 #[derive(Debug, serde::Deserialize)]
 pub struct PostUserRequest {
@@ -27,16 +28,13 @@ pub struct PostUserRequestTheme {
 pub async fn register_handler<S: ServiceManager>(
     State(AppState {
         service,
-        powerdns_subdomain_address,
+        subdomain_record,
+        bench_compat,
         ..
     }): State<AppState<S>>,
     axum::Json(req): axum::Json<PostUserRequest>,
 ) -> Result<(StatusCode, axum::Json<UserResponse>), Error> {
-    if req.name == "pipe" {
-        return Err(Error::BadRequest("the username 'pipe' is reserved".into()));
-    }
-
-    let (user, output) = service
+    let user = service
         .user_service()
         .create(
             &CreateUser {
@@ -46,17 +44,13 @@ pub async fn register_handler<S: ServiceManager>(
                 password: req.password.clone(),
             },
             req.theme.dark_mode,
-            &powerdns_subdomain_address,
+            subdomain_record.content(),
         )
-        .await?;
-
-    if !output.success {
-        return Err(Error::InternalServerError(format!(
-            "pdnsutil failed with stdout={} stderr={}",
-            String::from_utf8_lossy(&output.stdout),
-            String::from_utf8_lossy(&output.stderr),
-        )));
-    }
+        .await
+        .map_err(|e| match e {
+            ServiceError::UsernameTaken => bench_compat.username_taken(),
+            e => e.into(),
+        })?;
 
     let user = UserResponse::build_by_service(&service, &user).await?;
 
@@ -1,37 +1,119 @@
+use crate::auth::AuthUser;
+use crate::avatars::default_avatars;
 use crate::error::Error;
 use crate::state::AppState;
-use crate::{verify_user_session, DEFAULT_SESSION_ID_KEY, DEFAULT_USER_ID_KEY, FALLBACK_IMAGE};
-use async_session::{CookieStore, SessionStore};
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
-use axum_extra::extract::SignedCookieJar;
-use isupipe_core::models::user::UserId;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use isupipe_core::icon_image::{MAX_ICON_BYTES, THUMBNAIL_SIZES};
+use isupipe_core::models::icon::IconImage;
 use isupipe_core::services::icon_service::IconService;
 use isupipe_core::services::manager::ServiceManager;
+use isupipe_core::services::user_service::UserService;
 
+#[derive(Debug, serde::Deserialize)]
+pub struct GetIconQuery {
+    /// サムネイルの一辺のピクセル数。省略時は元画像を返す
+    size: Option<u32>,
+}
+
+/// アイコン画像を返す
+/// 保存済みのハッシュをETagとして返し、If-None-Matchが一致すれば304を返す
 pub async fn get_icon_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
     Path((username,)): Path<(String,)>,
+    Query(GetIconQuery { size }): Query<GetIconQuery>,
+    headers: HeaderMap,
 ) -> Result<axum::response::Response, Error> {
     use axum::response::IntoResponse as _;
 
-    let image = service
+    if let Some(size) = size {
+        if !THUMBNAIL_SIZES.contains(&size) {
+            return Err(Error::BadRequest(
+                format!("size must be one of {:?}", THUMBNAIL_SIZES).into(),
+            ));
+        }
+    }
+
+    let user = service
+        .user_service()
+        .find_by_name(&username)
+        .await?
+        .ok_or(Error::NotFound(
+            "not found user that has the given username".into(),
+        ))?;
+
+    let hash = service
         .icon_service()
-        .find_image_by_user_name(&username)
-        .await?;
+        .find_icon_hashes_by_user_ids(std::slice::from_ref(&user.id))
+        .await?
+        .remove(&user.id);
 
-    let headers = [(axum::http::header::CONTENT_TYPE, "image/jpeg")];
-    if let Some(image) = image {
-        Ok((headers, image).into_response())
-    } else {
-        let file = tokio::fs::File::open(FALLBACK_IMAGE).await.unwrap();
-        let stream = tokio_util::io::ReaderStream::new(file);
-        let body = axum::body::StreamBody::new(stream);
+    // アイコン未設定のユーザーにはデフォルト画像を返す
+    let (hash, has_icon) = match hash {
+        Some(hash) => (hash, true),
+        None => (default_avatars().hash(user.name.inner()), false),
+    };
 
-        Ok((headers, body).into_response())
+    // サムネイルは元画像のハッシュとサイズから一意に決まる
+    let etag = match size {
+        Some(size) => format!("\"{}-{}\"", hash, size),
+        None => format!("\"{}\"", hash),
+    };
+    if if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
     }
+
+    let icon = if has_icon {
+        let icon = match size {
+            Some(size) => {
+                service
+                    .icon_service()
+                    .find_thumbnail_by_user_id(&user.id, size)
+                    .await?
+            }
+            None => {
+                service
+                    .icon_service()
+                    .find_image_by_user_id(&user.id)
+                    .await?
+            }
+        };
+        // ハッシュ取得後にアイコンが消えた場合はデフォルト画像を返す
+        match icon {
+            Some(icon) => icon,
+            None => default_icon(user.name.inner(), size).await?,
+        }
+    } else {
+        default_icon(user.name.inner(), size).await?
+    };
+
+    let headers = [(header::CONTENT_TYPE, icon.mime_type), (header::ETAG, etag)];
+    Ok((headers, icon.image).into_response())
+}
+
+async fn default_icon(username: &str, size: Option<u32>) -> Result<IconImage, Error> {
+    let avatar = default_avatars().avatar(username, size).await?;
+    Ok(IconImage {
+        mime_type: avatar.mime_type.clone(),
+        image: avatar.image.clone(),
+    })
+}
+
+/// If-None-Matchに指定されたETagのいずれかが一致するか
+/// 弱い比較なので W/ は無視する
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
 }
 
+/// base64で送られてくる最大サイズのアイコン画像を受け付けられるリクエストボディの上限
+pub const POST_ICON_BODY_LIMIT: usize = MAX_ICON_BYTES / 3 * 4 + 1024;
+
 #[derive(Debug, serde::Deserialize)]
 pub struct PostIconRequest {
     #[serde(deserialize_with = "from_base64")]
@@ -56,19 +138,9 @@ pub struct PostIconResponse {
 
 pub async fn post_icon_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
     axum::Json(req): axum::Json<PostIconRequest>,
 ) -> Result<(StatusCode, axum::Json<PostIconResponse>), Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
-
     let icon_id = service
         .icon_service()
         .replace_new_image(&user_id, &req.image)
@@ -1,17 +1,20 @@
+use crate::auth::AuthUser;
 use crate::error::Error;
 use crate::responses::livestream_response::LivestreamResponse;
 use crate::responses::theme_response::ThemeResponse;
 use crate::responses::user_response::UserResponse;
+use crate::routes::password_routes::put_password_handler;
 use crate::routes::user_icon_routes::get_icon_handler;
 use crate::state::AppState;
-use crate::{verify_user_session, DEFAULT_SESSION_ID_KEY, DEFAULT_USER_ID_KEY};
-use async_session::{CookieStore, SessionStore};
 use axum::extract::{Path, State};
+use axum::http::StatusCode;
 use axum::routing::get;
 use axum::Router;
 use axum_extra::extract::SignedCookieJar;
-use isupipe_core::models::user::UserId;
+use isupipe_core::models::theme::UpdateTheme;
+use isupipe_core::models::user::UpdateUser;
 use isupipe_core::models::user_statistics::UserStatistics;
+use isupipe_core::services::account_service::AccountService;
 use isupipe_core::services::livestream_service::LivestreamService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::theme_service::ThemeService;
@@ -20,7 +23,17 @@ use isupipe_core::services::user_statistics_service::UserStatisticsService;
 
 pub fn user_routes<S: ServiceManager + 'static>() -> Router<AppState<S>> {
     Router::new()
-        .route("/me", axum::routing::get(get_me_handler::<S>))
+        .route(
+            "/me",
+            axum::routing::get(get_me_handler::<S>)
+                .patch(patch_me_handler::<S>)
+                .delete(delete_me_handler::<S>),
+        )
+        .route("/me/theme", axum::routing::put(put_me_theme_handler::<S>))
+        .route(
+            "/me/password",
+            axum::routing::put(put_password_handler::<S>),
+        )
         // フロントエンドで、配信予約のコラボレーターを指定する際に必要
         .route("/:username", axum::routing::get(get_user_handler::<S>))
         .route("/:username/theme", get(get_streamer_theme_handler::<S>))
@@ -36,11 +49,9 @@ pub fn user_routes<S: ServiceManager + 'static>() -> Router<AppState<S>> {
 // GET /api/user/:username/theme
 pub async fn get_streamer_theme_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((username,)): Path<(String,)>,
 ) -> Result<axum::Json<ThemeResponse>, Error> {
-    verify_user_session(&jar).await?;
-
     let user = service
         .user_service()
         .find_by_name(&username)
@@ -51,18 +62,13 @@ pub async fn get_streamer_theme_handler<S: ServiceManager>(
 
     let theme_model = service.theme_service().find_by_user_id(&user.id).await?;
 
-    Ok(axum::Json(ThemeResponse {
-        id: theme_model.id.inner().clone(),
-        dark_mode: theme_model.dark_mode,
-    }))
+    Ok(axum::Json(theme_model.into()))
 }
 pub async fn get_user_livestreams_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((username,)): Path<(String,)>,
 ) -> Result<axum::Json<Vec<LivestreamResponse>>, Error> {
-    verify_user_session(&jar).await?;
-
     let user = service
         .user_service()
         .find_by_name(&username)
@@ -81,18 +87,8 @@ pub async fn get_user_livestreams_handler<S: ServiceManager>(
 }
 pub async fn get_me_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
 ) -> Result<axum::Json<UserResponse>, Error> {
-    verify_user_session(&jar).await?;
-
-    let cookie = jar.get(DEFAULT_SESSION_ID_KEY).ok_or(Error::SessionError)?;
-    let sess = CookieStore::new()
-        .load_session(cookie.value().to_owned())
-        .await?
-        .ok_or(Error::SessionError)?;
-    let user_id: i64 = sess.get(DEFAULT_USER_ID_KEY).ok_or(Error::SessionError)?;
-    let user_id = UserId::new(user_id);
-
     let user_model = service
         .user_service()
         .find(&user_id)
@@ -106,15 +102,94 @@ pub async fn get_me_handler<S: ServiceManager>(
     Ok(axum::Json(user))
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct PatchMeRequest {
+    display_name: Option<String>,
+    description: Option<String>,
+}
+
+// プロフィール更新API
+// PATCH /api/user/me
+pub async fn patch_me_handler<S: ServiceManager>(
+    State(AppState { service, .. }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    axum::Json(req): axum::Json<PatchMeRequest>,
+) -> Result<axum::Json<UserResponse>, Error> {
+    let user_model = service
+        .user_service()
+        .update(
+            &user_id,
+            &UpdateUser {
+                display_name: req.display_name,
+                description: req.description,
+            },
+        )
+        .await?;
+
+    let user = UserResponse::build_by_service(&service, &user_model).await?;
+
+    Ok(axum::Json(user))
+}
+
+// 退会API
+// DELETE /api/user/me
+pub async fn delete_me_handler<S: ServiceManager>(
+    State(AppState {
+        service,
+        session_config,
+        ..
+    }): State<AppState<S>>,
+    jar: SignedCookieJar,
+    AuthUser { id: user_id, .. }: AuthUser,
+) -> Result<(StatusCode, SignedCookieJar), Error> {
+    service.account_service().delete(&user_id).await?;
+
+    Ok((
+        StatusCode::NO_CONTENT,
+        jar.remove(session_config.removal_cookie()),
+    ))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct PutThemeRequest {
+    dark_mode: Option<bool>,
+    accent_color: Option<String>,
+    background_image_url: Option<String>,
+    font: Option<String>,
+    chat_color_scheme: Option<String>,
+}
+
+// テーマ更新API
+// PUT /api/user/me/theme
+pub async fn put_me_theme_handler<S: ServiceManager>(
+    State(AppState { service, .. }): State<AppState<S>>,
+    AuthUser { id: user_id, .. }: AuthUser,
+    axum::Json(req): axum::Json<PutThemeRequest>,
+) -> Result<axum::Json<ThemeResponse>, Error> {
+    let theme_model = service
+        .theme_service()
+        .update(
+            &user_id,
+            &UpdateTheme {
+                dark_mode: req.dark_mode,
+                accent_color: req.accent_color,
+                background_image_url: req.background_image_url,
+                font: req.font,
+                chat_color_scheme: req.chat_color_scheme,
+            },
+        )
+        .await?;
+
+    Ok(axum::Json(theme_model.into()))
+}
+
 // ユーザ詳細API
 // GET /api/user/:username
 pub async fn get_user_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((username,)): Path<(String,)>,
 ) -> Result<axum::Json<UserResponse>, Error> {
-    verify_user_session(&jar).await?;
-
     let user_model = service
         .user_service()
         .find_by_name(&username)
@@ -130,11 +205,9 @@ pub async fn get_user_handler<S: ServiceManager>(
 
 pub async fn get_user_statistics_handler<S: ServiceManager>(
     State(AppState { service, .. }): State<AppState<S>>,
-    jar: SignedCookieJar,
+    _: AuthUser,
     Path((username,)): Path<(String,)>,
 ) -> Result<axum::Json<UserStatistics>, Error> {
-    verify_user_session(&jar).await?;
-
     let user = service
         .user_service()
         .find_by_name(&username)
@@ -0,0 +1,279 @@
+use crate::error::Error;
+use crate::state::AppState;
+use crate::{
+    DEFAULT_SESSION_ID_KEY, DEFAULT_USERNAME_KEY, DEFAULT_USER_ID_KEY, DEFUALT_SESSION_EXPIRES_KEY,
+};
+use async_session::{CookieStore, SessionStore};
+use axum::extract::State;
+use axum::http::header::SET_COOKIE;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::cookie::{Cookie, SameSite};
+use axum_extra::extract::SignedCookieJar;
+use chrono::Utc;
+use isupipe_core::models::session::{Session, SessionId};
+use isupipe_core::models::user::{UserId, UserName};
+use isupipe_core::services::manager::ServiceManager;
+use isupipe_core::services::session_service::SessionService;
+
+const SESSION_LIFETIME_ENV_KEY: &str = "ISUCON13_SESSION_LIFETIME";
+const SESSION_RENEWAL_WINDOW_ENV_KEY: &str = "ISUCON13_SESSION_RENEWAL_WINDOW";
+const SESSION_COOKIE_DOMAIN_ENV_KEY: &str = "ISUCON13_SESSION_COOKIE_DOMAIN";
+const SESSION_COOKIE_SECURE_ENV_KEY: &str = "ISUCON13_SESSION_COOKIE_SECURE";
+const SESSION_COOKIE_SAMESITE_ENV_KEY: &str = "ISUCON13_SESSION_COOKIE_SAMESITE";
+const DEFAULT_SESSION_LIFETIME_SECS: i64 = 60 * 60;
+const DEFAULT_SESSION_COOKIE_DOMAIN: &str = "u.isucon.dev";
+
+/// セッションの有効期間と Cookie の属性
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+    pub lifetime: chrono::Duration,
+    /// 残り時間がこれを下回ったセッションはリクエストのたびに延長する。None なら延長しない
+    pub renewal_window: Option<chrono::Duration>,
+    pub cookie_domain: String,
+    pub cookie_secure: bool,
+    pub cookie_same_site: Option<SameSite>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            lifetime: chrono::Duration::seconds(DEFAULT_SESSION_LIFETIME_SECS),
+            renewal_window: None,
+            cookie_domain: DEFAULT_SESSION_COOKIE_DOMAIN.to_owned(),
+            cookie_secure: false,
+            cookie_same_site: None,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// 環境変数から組み立てる。期間は秒で指定し、延長の猶予に 0 を指定すると延長しない
+    pub fn from_env() -> Result<Self, Error> {
+        let default = Self::default();
+
+        let lifetime = match env_var(SESSION_LIFETIME_ENV_KEY) {
+            Some(value) => parse_seconds(SESSION_LIFETIME_ENV_KEY, &value)?,
+            None => default.lifetime,
+        };
+        if lifetime <= chrono::Duration::zero() {
+            return Err(config_error(SESSION_LIFETIME_ENV_KEY, "must be positive"));
+        }
+
+        let renewal_window = match env_var(SESSION_RENEWAL_WINDOW_ENV_KEY) {
+            Some(value) => Some(parse_seconds(SESSION_RENEWAL_WINDOW_ENV_KEY, &value)?)
+                .filter(|window| *window > chrono::Duration::zero()),
+            None => default.renewal_window,
+        };
+        if renewal_window.is_some_and(|window| window > lifetime) {
+            return Err(config_error(
+                SESSION_RENEWAL_WINDOW_ENV_KEY,
+                "must not exceed the session lifetime",
+            ));
+        }
+
+        let cookie_secure = match env_var(SESSION_COOKIE_SECURE_ENV_KEY).as_deref() {
+            None | Some("0" | "false") => false,
+            Some("1" | "true") => true,
+            Some(value) => {
+                return Err(config_error(
+                    SESSION_COOKIE_SECURE_ENV_KEY,
+                    &format!("must be true or false: {}", value),
+                ))
+            }
+        };
+
+        let cookie_same_site = match env_var(SESSION_COOKIE_SAMESITE_ENV_KEY)
+            .map(|value| value.to_ascii_lowercase())
+            .as_deref()
+        {
+            None => None,
+            Some("lax") => Some(SameSite::Lax),
+            Some("strict") => Some(SameSite::Strict),
+            Some("none") => Some(SameSite::None),
+            Some(value) => {
+                return Err(config_error(
+                    SESSION_COOKIE_SAMESITE_ENV_KEY,
+                    &format!("must be one of lax, strict, none: {}", value),
+                ))
+            }
+        };
+        // ブラウザは Secure の無い SameSite=None の Cookie を受け付けない
+        if cookie_same_site == Some(SameSite::None) && !cookie_secure {
+            return Err(config_error(
+                SESSION_COOKIE_SAMESITE_ENV_KEY,
+                "none requires ISUCON13_SESSION_COOKIE_SECURE=true",
+            ));
+        }
+
+        Ok(Self {
+            lifetime,
+            renewal_window,
+            cookie_domain: env_var(SESSION_COOKIE_DOMAIN_ENV_KEY).unwrap_or(default.cookie_domain),
+            cookie_secure,
+            cookie_same_site,
+        })
+    }
+
+    /// セッションを保存した Cookie を作る
+    pub async fn session_cookie(
+        &self,
+        session: &Session,
+        username: &UserName,
+    ) -> Result<Cookie<'static>, Error> {
+        let mut sess = async_session::Session::new();
+        sess.insert(DEFAULT_SESSION_ID_KEY, session.id.inner())
+            .unwrap();
+        sess.insert(DEFAULT_USER_ID_KEY, &session.user_id).unwrap();
+        sess.insert(DEFAULT_USERNAME_KEY, username.inner()).unwrap();
+        sess.insert(DEFUALT_SESSION_EXPIRES_KEY, session.expires_at)
+            .unwrap();
+        let cookie_value = CookieStore::new()
+            .store_session(sess)
+            .await?
+            .ok_or(Error::SessionError)?;
+
+        let max_age = session.expires_at - Utc::now().timestamp();
+        let mut cookie = self.cookie(cookie_value);
+        cookie.set_max_age(time::Duration::seconds(max_age.max(0)));
+        Ok(cookie)
+    }
+
+    /// ログアウト時に Cookie を消すためのもの。domain と path をログイン時と揃える
+    pub fn removal_cookie(&self) -> Cookie<'static> {
+        self.cookie(String::new())
+    }
+
+    fn cookie(&self, value: String) -> Cookie<'static> {
+        let mut cookie = Cookie::new(DEFAULT_SESSION_ID_KEY, value);
+        cookie.set_domain(self.cookie_domain.clone());
+        cookie.set_path("/");
+        cookie.set_secure(self.cookie_secure);
+        cookie.set_same_site(self.cookie_same_site);
+        cookie
+    }
+}
+
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+fn parse_seconds(key: &str, value: &str) -> Result<chrono::Duration, Error> {
+    value
+        .parse()
+        .map(chrono::Duration::seconds)
+        .map_err(|_| config_error(key, &format!("must be seconds: {}", value)))
+}
+
+fn config_error(key: &str, message: &str) -> Error {
+    Error::InternalServerError(format!("invalid {}: {}", key, message))
+}
+
+/// 署名付き Cookie から取り出したセッション。サーバー側での失効や期限切れはまだ確認していない
+#[derive(Debug, Clone)]
+pub struct CookieSession {
+    pub id: SessionId,
+    pub user_id: UserId,
+    pub username: UserName,
+    pub expires_at: i64,
+}
+
+impl CookieSession {
+    /// Cookie やセッションがなければ 403、中身が壊れていれば 401 を返す
+    pub async fn load(jar: &SignedCookieJar) -> Result<Self, Error> {
+        let cookie = jar
+            .get(DEFAULT_SESSION_ID_KEY)
+            .ok_or(Error::Forbidden("".into()))?;
+        let sess = CookieStore::new()
+            .load_session(cookie.value().to_owned())
+            .await?
+            .ok_or(Error::Forbidden("".into()))?;
+
+        let expires_at: i64 = sess
+            .get(DEFUALT_SESSION_EXPIRES_KEY)
+            .ok_or(Error::Forbidden("".into()))?;
+        let user_id: i64 = sess
+            .get(DEFAULT_USER_ID_KEY)
+            .ok_or(Error::Unauthorized("invalid session".into()))?;
+        let username: String = sess
+            .get(DEFAULT_USERNAME_KEY)
+            .ok_or(Error::Unauthorized("invalid session".into()))?;
+        let session_id: String = sess
+            .get(DEFAULT_SESSION_ID_KEY)
+            .ok_or(Error::Unauthorized("invalid session".into()))?;
+
+        Ok(Self {
+            id: SessionId::new(session_id),
+            user_id: UserId::new(user_id),
+            username: UserName::new(username),
+            expires_at,
+        })
+    }
+}
+
+/// 有効期限が近いセッションを延長し、Cookie を再発行するミドルウェア
+/// ハンドラ自身が Cookie を設定した場合 (ログアウトなど) はそちらを優先する
+pub async fn renew_session<S: ServiceManager, B>(
+    State(state): State<AppState<S>>,
+    jar: SignedCookieJar,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let cookie = match renew(&state, &jar).await {
+        Ok(cookie) => cookie,
+        Err(e) => {
+            tracing::warn!("failed to renew session: {}", e);
+            None
+        }
+    };
+
+    let response = next.run(request).await;
+    match cookie {
+        Some(cookie) if !response.headers().contains_key(SET_COOKIE) => {
+            (jar.add(cookie), response).into_response()
+        }
+        _ => response,
+    }
+}
+
+async fn renew<S: ServiceManager>(
+    state: &AppState<S>,
+    jar: &SignedCookieJar,
+) -> Result<Option<Cookie<'static>>, Error> {
+    let config = &state.session_config;
+    let Some(renewal_window) = config.renewal_window else {
+        return Ok(None);
+    };
+    let Ok(cookie_session) = CookieSession::load(jar).await else {
+        return Ok(None);
+    };
+
+    let now = Utc::now();
+    let remaining = cookie_session.expires_at - now.timestamp();
+    if remaining < 0 || remaining > renewal_window.num_seconds() {
+        return Ok(None);
+    }
+
+    // 失効済みのセッションは延長しない
+    let session_service = state.service.session_service();
+    let Some(mut session) = session_service
+        .find_active(&cookie_session.id, now.timestamp())
+        .await?
+    else {
+        return Ok(None);
+    };
+    if session.user_id != cookie_session.user_id {
+        return Ok(None);
+    }
+
+    session.expires_at = (now + config.lifetime).timestamp();
+    session_service
+        .renew(&session.id, session.expires_at)
+        .await?;
+
+    let cookie = config
+        .session_cookie(&session, &cookie_session.username)
+        .await?;
+    Ok(Some(cookie))
+}
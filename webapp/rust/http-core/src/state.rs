@@ -1,3 +1,8 @@
+use crate::client_ip::TrustedProxies;
+use crate::compat::BenchCompat;
+use crate::hub::LivestreamEventHub;
+use crate::session::SessionConfig;
+use isupipe_core::commands::pdnsutil_command::SubdomainRecordConfig;
 use isupipe_core::services::manager::ServiceManager;
 use std::sync::Arc;
 
@@ -5,7 +10,11 @@ use std::sync::Arc;
 pub struct AppState<S: ServiceManager> {
     pub service: S,
     pub key: axum_extra::extract::cookie::Key,
-    pub powerdns_subdomain_address: Arc<String>,
+    pub session_config: Arc<SessionConfig>,
+    pub subdomain_record: Arc<SubdomainRecordConfig>,
+    pub livestream_hub: Arc<LivestreamEventHub>,
+    pub trusted_proxies: Arc<TrustedProxies>,
+    pub bench_compat: Arc<BenchCompat>,
 }
 impl<S: ServiceManager> axum::extract::FromRef<AppState<S>> for axum_extra::extract::cookie::Key {
     fn from_ref(state: &AppState<S>) -> Self {
@@ -0,0 +1,54 @@
+use crate::caches::in_memory_cache::InMemoryCacheInfra;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::tag::{Tag, TagId};
+use isupipe_core::models::theme::Theme;
+use isupipe_core::models::user::{User, UserId};
+use isupipe_core::ng_word_matcher::NgWordMatcher;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub mod in_memory_cache;
+
+const USER_CACHE_CAPACITY: usize = 10_000;
+const TAG_CACHE_CAPACITY: usize = 1_000;
+const LIVESTREAM_CACHE_CAPACITY: usize = 10_000;
+const CACHE_TTL: Duration = Duration::from_secs(300);
+/// 他のホストで登録されたNGワードが反映されるまでの最大の時間
+const NG_WORD_MATCHER_CACHE_TTL: Duration = Duration::from_secs(10);
+
+pub type UserCacheInfra = InMemoryCacheInfra<UserId, User>;
+pub type ThemeCacheInfra = InMemoryCacheInfra<UserId, Theme>;
+pub type TagCacheInfra = InMemoryCacheInfra<TagId, Tag>;
+pub type IconHashCacheInfra = InMemoryCacheInfra<UserId, Option<String>>;
+pub type NgWordMatcherCacheInfra = InMemoryCacheInfra<LivestreamId, Arc<NgWordMatcher>>;
+
+/// サービス間で共有するキャッシュ
+#[derive(Clone)]
+pub struct CacheManagerInfra {
+    pub user_cache: UserCacheInfra,
+    pub theme_cache: ThemeCacheInfra,
+    pub tag_cache: TagCacheInfra,
+    pub icon_hash_cache: IconHashCacheInfra,
+    pub ng_word_matcher_cache: NgWordMatcherCacheInfra,
+}
+
+impl CacheManagerInfra {
+    pub fn new() -> Self {
+        Self {
+            user_cache: InMemoryCacheInfra::new(USER_CACHE_CAPACITY, CACHE_TTL),
+            theme_cache: InMemoryCacheInfra::new(USER_CACHE_CAPACITY, CACHE_TTL),
+            tag_cache: InMemoryCacheInfra::new(TAG_CACHE_CAPACITY, CACHE_TTL),
+            icon_hash_cache: InMemoryCacheInfra::new(USER_CACHE_CAPACITY, CACHE_TTL),
+            ng_word_matcher_cache: InMemoryCacheInfra::new(
+                LIVESTREAM_CACHE_CAPACITY,
+                NG_WORD_MATCHER_CACHE_TTL,
+            ),
+        }
+    }
+}
+
+impl Default for CacheManagerInfra {
+    fn default() -> Self {
+        Self::new()
+    }
+}
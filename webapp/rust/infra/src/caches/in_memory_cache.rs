@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod get;
+#[cfg(test)]
+mod remove;
+
+use hashlink::LruCache;
+use isupipe_core::caches::Cache;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 件数の上限と有効期限を持つプロセス内キャッシュ
+/// clone したものは同じ領域を共有する
+pub struct InMemoryCacheInfra<K: Hash + Eq, V> {
+    entries: Arc<Mutex<LruCache<K, (V, Instant)>>>,
+    ttl: Duration,
+}
+
+impl<K: Hash + Eq, V> InMemoryCacheInfra<K, V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+            ttl,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Clone for InMemoryCacheInfra<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            ttl: self.ttl,
+        }
+    }
+}
+
+impl<K, V> Cache<K, V> for InMemoryCacheInfra<K, V>
+where
+    K: Hash + Eq + Send,
+    V: Clone + Send,
+{
+    fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn set(&self, key: K, value: V) {
+        let expires_at = Instant::now() + self.ttl;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key, (value, expires_at));
+    }
+
+    fn remove(&self, key: &K) {
+        self.entries.lock().unwrap().remove(key);
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,23 @@
+use crate::caches::in_memory_cache::InMemoryCacheInfra;
+use isupipe_core::caches::Cache;
+use std::time::Duration;
+
+#[test]
+fn evicts_least_recently_used() {
+    let cache = InMemoryCacheInfra::new(2, Duration::from_secs(60));
+    cache.set(1, "a");
+    cache.set(2, "b");
+    assert_eq!(cache.get(&1), Some("a"));
+
+    cache.set(3, "c");
+    assert_eq!(cache.get(&1), Some("a"));
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.get(&3), Some("c"));
+}
+
+#[test]
+fn expired_case() {
+    let cache = InMemoryCacheInfra::new(2, Duration::ZERO);
+    cache.set(1, "a");
+    assert_eq!(cache.get(&1), None);
+}
@@ -0,0 +1,14 @@
+use crate::caches::in_memory_cache::InMemoryCacheInfra;
+use isupipe_core::caches::Cache;
+use std::time::Duration;
+
+#[test]
+fn shared_between_clones() {
+    let cache = InMemoryCacheInfra::new(2, Duration::from_secs(60));
+    let cloned = cache.clone();
+    cache.set(1, "a");
+    assert_eq!(cloned.get(&1), Some("a"));
+
+    cloned.remove(&1);
+    assert_eq!(cache.get(&1), None);
+}
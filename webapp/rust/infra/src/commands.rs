@@ -1,2 +1,4 @@
 pub mod initialize_command;
+pub mod pdnsutil_cli_command;
 pub mod pdnsutil_command;
+pub mod powerdns_api_command;
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use isupipe_core::commands::pdnsutil_command::{DnsRecord, PDNSUtilCommand, SubdomainRecordConfig};
+use isupipe_core::commands::{CommandError, CommandOutput, CommandResult};
+
+#[cfg(test)]
+mod parse_list_zone;
+
+/// DNSサーバと同じホストで pdnsutil を起動してレコードを操作する
+#[derive(Clone)]
+pub struct PDNSUtilCliCommandInfra {
+    record: SubdomainRecordConfig,
+}
+
+impl PDNSUtilCliCommandInfra {
+    pub fn new(record: SubdomainRecordConfig) -> Self {
+        Self { record }
+    }
+
+    /// add-record と replace-rrset の引数
+    async fn run_rrset(
+        &self,
+        subcommand: &str,
+        name: &str,
+        content: &str,
+    ) -> CommandResult<CommandOutput> {
+        let ttl = self.record.ttl().to_string();
+        self.run(&[
+            subcommand,
+            self.record.zone(),
+            name,
+            self.record.record_type().as_str(),
+            &ttl,
+            content,
+        ])
+        .await
+    }
+
+    async fn run(&self, args: &[&str]) -> CommandResult<CommandOutput> {
+        let output = tokio::process::Command::new("pdnsutil")
+            .args(args)
+            .output()
+            .await?;
+        Ok(CommandOutput {
+            success: output.status.success(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+}
+
+#[async_trait]
+impl PDNSUtilCommand for PDNSUtilCliCommandInfra {
+    async fn add_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput> {
+        self.run_rrset("add-record", name, content).await
+    }
+
+    async fn delete_record(&self, name: &str) -> CommandResult<CommandOutput> {
+        self.run(&[
+            "delete-rrset",
+            self.record.zone(),
+            name,
+            self.record.record_type().as_str(),
+        ])
+        .await
+    }
+
+    async fn list_records(&self) -> CommandResult<Vec<DnsRecord>> {
+        let output = self.run(&["list-zone", self.record.zone()]).await?;
+        if !output.success {
+            return Err(CommandError::InvalidOutput(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+        parse_list_zone(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    async fn update_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput> {
+        self.run_rrset("replace-rrset", name, content).await
+    }
+}
+
+/// pdnsutil list-zone の出力を読む
+/// 各行は `名前 TTL IN 種類 内容` で、内容には空白が含まれることがある (SOAなど)
+pub fn parse_list_zone(output: &str) -> CommandResult<Vec<DnsRecord>> {
+    let mut records = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        // $ORIGIN などのディレクティブは読み飛ばす
+        if line.is_empty() || line.starts_with('$') {
+            continue;
+        }
+
+        let invalid = || CommandError::InvalidOutput(line.to_owned());
+        let mut head = [""; 4];
+        let mut rest = line;
+        for field in &mut head {
+            let (value, remaining) = rest.split_once(char::is_whitespace).ok_or_else(invalid)?;
+            *field = value;
+            rest = remaining.trim_start();
+        }
+        let [name, ttl, _class, record_type] = head;
+        let ttl = ttl.parse().map_err(|_| invalid())?;
+
+        records.push(DnsRecord {
+            name: name.trim_end_matches('.').to_owned(),
+            record_type: record_type.to_owned(),
+            ttl,
+            content: rest.to_owned(),
+        });
+    }
+
+    Ok(records)
+}
@@ -0,0 +1,40 @@
+use crate::commands::pdnsutil_cli_command::parse_list_zone;
+use isupipe_core::commands::pdnsutil_command::DnsRecord;
+use isupipe_core::commands::CommandError;
+
+#[test]
+fn parse() {
+    let output = "$ORIGIN .\n\
+        u.isucon.dev\t3600\tIN\tSOA\tns1.u.isucon.dev. admin.u.isucon.dev. 1 10800 3600 604800 3600\n\
+        pipe.u.isucon.dev\t0\tIN\tA\t127.0.0.1\n\
+        \n";
+
+    let records = parse_list_zone(output).unwrap();
+    assert_eq!(
+        records,
+        vec![
+            DnsRecord {
+                name: "u.isucon.dev".to_owned(),
+                record_type: "SOA".to_owned(),
+                ttl: 3600,
+                content: "ns1.u.isucon.dev. admin.u.isucon.dev. 1 10800 3600 604800 3600"
+                    .to_owned(),
+            },
+            DnsRecord {
+                name: "pipe.u.isucon.dev".to_owned(),
+                record_type: "A".to_owned(),
+                ttl: 0,
+                content: "127.0.0.1".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_invalid_line() {
+    let result = parse_list_zone("pipe.u.isucon.dev\t0\tIN\tA\n");
+    assert!(matches!(result, Err(CommandError::InvalidOutput(_))));
+
+    let result = parse_list_zone("pipe.u.isucon.dev\tttl\tIN\tA\t127.0.0.1\n");
+    assert!(matches!(result, Err(CommandError::InvalidOutput(_))));
+}
@@ -1,30 +1,63 @@
+use crate::commands::pdnsutil_cli_command::PDNSUtilCliCommandInfra;
+use crate::commands::powerdns_api_command::{PowerDNSApiCommandInfra, PowerDNSApiConfig};
 use async_trait::async_trait;
-use isupipe_core::commands::pdnsutil_command::PDNSUtilCommand;
-use isupipe_core::commands::{CommandOutput, CommandResult};
+use isupipe_core::commands::pdnsutil_command::{DnsRecord, PDNSUtilCommand, SubdomainRecordConfig};
+use isupipe_core::commands::{CommandError, CommandOutput, CommandResult};
 
+const PDNS_BACKEND_ENV_KEY: &str = "ISUCON13_PDNS_BACKEND";
+
+/// 設定で選択するDNSレコードの操作方法
 #[derive(Clone)]
-pub struct PDNSUtilCommandInfra {}
+pub enum PDNSUtilCommandInfra {
+    Cli(PDNSUtilCliCommandInfra),
+    Api(Box<PowerDNSApiCommandInfra>),
+}
+
+impl PDNSUtilCommandInfra {
+    /// 環境変数から組み立てる。pdnsutil (デフォルト) か api を選ぶ
+    pub fn from_env(record: SubdomainRecordConfig) -> CommandResult<Self> {
+        let kind = std::env::var(PDNS_BACKEND_ENV_KEY).unwrap_or_default();
+        match kind.as_str() {
+            "" | "pdnsutil" => Ok(Self::Cli(PDNSUtilCliCommandInfra::new(record))),
+            "api" => {
+                let command = PowerDNSApiCommandInfra::new(PowerDNSApiConfig::from_env()?, record)?;
+                Ok(Self::Api(Box::new(command)))
+            }
+            _ => Err(CommandError::ConfigError(format!(
+                "{} must be one of pdnsutil, api: {}",
+                PDNS_BACKEND_ENV_KEY, kind
+            ))),
+        }
+    }
+}
 
 #[async_trait]
 impl PDNSUtilCommand for PDNSUtilCommandInfra {
-    async fn add_record(
-        &self,
-        name: &str,
-        powerdns_subdomain_address: &str,
-    ) -> CommandResult<CommandOutput> {
-        let output = tokio::process::Command::new("pdnsutil")
-            .arg("add-record")
-            .arg("u.isucon.dev")
-            .arg(name)
-            .arg("A")
-            .arg("0")
-            .arg(powerdns_subdomain_address)
-            .output()
-            .await?;
-        Ok(CommandOutput {
-            success: output.status.success(),
-            stdout: output.stdout,
-            stderr: output.stderr,
-        })
+    async fn add_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput> {
+        match self {
+            Self::Cli(command) => command.add_record(name, content).await,
+            Self::Api(command) => command.add_record(name, content).await,
+        }
+    }
+
+    async fn delete_record(&self, name: &str) -> CommandResult<CommandOutput> {
+        match self {
+            Self::Cli(command) => command.delete_record(name).await,
+            Self::Api(command) => command.delete_record(name).await,
+        }
+    }
+
+    async fn list_records(&self) -> CommandResult<Vec<DnsRecord>> {
+        match self {
+            Self::Cli(command) => command.list_records().await,
+            Self::Api(command) => command.list_records().await,
+        }
+    }
+
+    async fn update_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput> {
+        match self {
+            Self::Cli(command) => command.update_record(name, content).await,
+            Self::Api(command) => command.update_record(name, content).await,
+        }
     }
 }
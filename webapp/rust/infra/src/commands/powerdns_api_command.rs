@@ -0,0 +1,286 @@
+use async_trait::async_trait;
+use isupipe_core::commands::pdnsutil_command::{DnsRecord, PDNSUtilCommand, SubdomainRecordConfig};
+use isupipe_core::commands::{CommandError, CommandOutput, CommandResult};
+use reqwest::{Method, Url};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[cfg(test)]
+mod add_record;
+#[cfg(test)]
+mod delete_record;
+#[cfg(test)]
+mod list_records;
+#[cfg(test)]
+mod new;
+#[cfg(test)]
+mod stand_in;
+#[cfg(test)]
+mod update_record;
+
+const PDNS_API_URL_ENV_KEY: &str = "ISUCON13_PDNS_API_URL";
+const PDNS_API_KEY_ENV_KEY: &str = "ISUCON13_PDNS_API_KEY";
+const PDNS_SERVER_ID_ENV_KEY: &str = "ISUCON13_PDNS_SERVER_ID";
+const PDNS_API_TIMEOUT_ENV_KEY: &str = "ISUCON13_PDNS_API_TIMEOUT_MS";
+const DEFAULT_PDNS_API_URL: &str = "http://127.0.0.1:8081";
+const DEFAULT_PDNS_SERVER_ID: &str = "localhost";
+const DEFAULT_PDNS_API_TIMEOUT: Duration = Duration::from_secs(3);
+/// 接続だけならこれより長くはかからない。timeout が短ければそちらに合わせる
+const MAX_PDNS_API_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct PowerDNSApiConfig {
+    /// 例: http://127.0.0.1:8081
+    pub api_url: String,
+    pub api_key: String,
+    pub server_id: String,
+    /// 1回のリクエストの応答を待つ時間。ユーザー登録はトランザクションを開いたまま待つので短くする
+    pub timeout: Duration,
+}
+
+impl PowerDNSApiConfig {
+    /// ISUCON13_PDNS_API_TIMEOUT_MS はミリ秒で指定する
+    pub fn from_env() -> CommandResult<Self> {
+        let api_key = std::env::var(PDNS_API_KEY_ENV_KEY).map_err(|_| {
+            CommandError::ConfigError(format!("environ {} must be provided", PDNS_API_KEY_ENV_KEY))
+        })?;
+        let timeout = match std::env::var(PDNS_API_TIMEOUT_ENV_KEY) {
+            Ok(value) => value
+                .parse()
+                .ok()
+                .filter(|ms| *ms > 0)
+                .map(Duration::from_millis)
+                .ok_or_else(|| {
+                    CommandError::ConfigError(format!(
+                        "{} must be positive milliseconds: {}",
+                        PDNS_API_TIMEOUT_ENV_KEY, value
+                    ))
+                })?,
+            Err(_) => DEFAULT_PDNS_API_TIMEOUT,
+        };
+
+        Ok(Self {
+            api_url: std::env::var(PDNS_API_URL_ENV_KEY)
+                .unwrap_or_else(|_| DEFAULT_PDNS_API_URL.to_owned()),
+            api_key,
+            server_id: std::env::var(PDNS_SERVER_ID_ENV_KEY)
+                .unwrap_or_else(|_| DEFAULT_PDNS_SERVER_ID.to_owned()),
+            timeout,
+        })
+    }
+}
+
+/// PowerDNS Authoritative の HTTP API でレコードを操作する
+/// DNSサーバと別のホストでも動かせ、プロセスの起動もいらない
+#[derive(Clone)]
+pub struct PowerDNSApiCommandInfra {
+    client: reqwest::Client,
+    zone_url: Url,
+    api_key: String,
+    record: SubdomainRecordConfig,
+}
+
+impl PowerDNSApiCommandInfra {
+    pub fn new(config: PowerDNSApiConfig, record: SubdomainRecordConfig) -> CommandResult<Self> {
+        let invalid_url = |reason: String| {
+            CommandError::ConfigError(format!(
+                "invalid PowerDNS API URL {}: {}",
+                config.api_url, reason
+            ))
+        };
+        let mut zone_url = Url::parse(&config.api_url).map_err(|e| invalid_url(e.to_string()))?;
+        if zone_url.host_str().is_none() {
+            return Err(invalid_url("host is missing".to_owned()));
+        }
+
+        zone_url
+            .path_segments_mut()
+            .map_err(|_| invalid_url("cannot be a base".to_owned()))?
+            .pop_if_empty()
+            .extend(["api", "v1", "servers", &config.server_id, "zones"])
+            .push(&format!("{}.", record.zone()));
+
+        // 応答しないAPIを待ち続けて、登録のトランザクションを開いたままにしない
+        let client = reqwest::Client::builder()
+            .connect_timeout(config.timeout.min(MAX_PDNS_API_CONNECT_TIMEOUT))
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| CommandError::ConfigError(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            zone_url,
+            api_key: config.api_key,
+            record,
+        })
+    }
+
+    fn fqdn(&self, name: &str) -> String {
+        format!("{}.{}.", name, self.record.zone())
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        body: Option<Vec<u8>>,
+    ) -> CommandResult<reqwest::Response> {
+        let mut request = self
+            .client
+            .request(method, self.zone_url.clone())
+            .header("x-api-key", &self.api_key);
+        if let Some(body) = body {
+            request = request
+                .header("content-type", "application/json")
+                .body(body);
+        }
+        request
+            .send()
+            .await
+            .map_err(|e| CommandError::RequestError(e.to_string()))
+    }
+
+    async fn patch(&self, rrset: RRSetChange<'_>) -> CommandResult<CommandOutput> {
+        let body = serde_json::to_vec(&ZonePatch {
+            rrsets: vec![rrset],
+        })
+        .map_err(|e| CommandError::RequestError(e.to_string()))?;
+        let response = self.send(Method::PATCH, Some(body)).await?;
+
+        // pdnsutil と同じく、失敗はエラーではなく出力として返す
+        let success = response.status().is_success();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| CommandError::RequestError(e.to_string()))?
+            .to_vec();
+        Ok(if success {
+            CommandOutput {
+                success,
+                stdout: body,
+                stderr: Vec::new(),
+            }
+        } else {
+            CommandOutput {
+                success,
+                stdout: Vec::new(),
+                stderr: body,
+            }
+        })
+    }
+
+    /// API に追記の操作はないので、追加も置き換えで行う
+    fn replace<'a>(&'a self, name: &str, content: &'a str) -> RRSetChange<'a> {
+        RRSetChange {
+            name: self.fqdn(name),
+            record_type: self.record.record_type().as_str(),
+            ttl: Some(self.record.ttl()),
+            changetype: "REPLACE",
+            records: Some(vec![RecordBody {
+                content,
+                disabled: false,
+            }]),
+        }
+    }
+}
+
+#[async_trait]
+impl PDNSUtilCommand for PowerDNSApiCommandInfra {
+    async fn add_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput> {
+        self.patch(self.replace(name, content)).await
+    }
+
+    async fn delete_record(&self, name: &str) -> CommandResult<CommandOutput> {
+        self.patch(RRSetChange {
+            name: self.fqdn(name),
+            record_type: self.record.record_type().as_str(),
+            ttl: None,
+            changetype: "DELETE",
+            records: None,
+        })
+        .await
+    }
+
+    async fn list_records(&self) -> CommandResult<Vec<DnsRecord>> {
+        let response = self.send(Method::GET, None).await?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| CommandError::RequestError(e.to_string()))?;
+        if !status.is_success() {
+            return Err(CommandError::RequestError(format!(
+                "unexpected status {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )));
+        }
+
+        let zone: Zone = serde_json::from_slice(&body)
+            .map_err(|e| CommandError::InvalidOutput(e.to_string()))?;
+        Ok(zone
+            .rrsets
+            .into_iter()
+            .flat_map(|rrset| {
+                let name = rrset.name.trim_end_matches('.').to_owned();
+                rrset
+                    .records
+                    .into_iter()
+                    .filter(|record| !record.disabled)
+                    .map(move |record| DnsRecord {
+                        name: name.clone(),
+                        record_type: rrset.record_type.clone(),
+                        ttl: rrset.ttl,
+                        content: record.content,
+                    })
+            })
+            .collect())
+    }
+
+    async fn update_record(&self, name: &str, content: &str) -> CommandResult<CommandOutput> {
+        self.patch(self.replace(name, content)).await
+    }
+}
+
+#[derive(Serialize)]
+struct ZonePatch<'a> {
+    rrsets: Vec<RRSetChange<'a>>,
+}
+
+#[derive(Serialize)]
+struct RRSetChange<'a> {
+    name: String,
+    #[serde(rename = "type")]
+    record_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u32>,
+    changetype: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    records: Option<Vec<RecordBody<'a>>>,
+}
+
+#[derive(Serialize)]
+struct RecordBody<'a> {
+    content: &'a str,
+    disabled: bool,
+}
+
+#[derive(Deserialize)]
+struct Zone {
+    rrsets: Vec<RRSet>,
+}
+
+#[derive(Deserialize)]
+struct RRSet {
+    name: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    ttl: u32,
+    records: Vec<Record>,
+}
+
+#[derive(Deserialize)]
+struct Record {
+    content: String,
+    #[serde(default)]
+    disabled: bool,
+}
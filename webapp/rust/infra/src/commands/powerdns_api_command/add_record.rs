@@ -0,0 +1,61 @@
+use crate::commands::powerdns_api_command::stand_in::{self, API_KEY, SERVER_ID};
+use crate::commands::powerdns_api_command::{PowerDNSApiCommandInfra, PowerDNSApiConfig};
+use isupipe_core::commands::pdnsutil_command::{
+    PDNSUtilCommand, RecordType, SubdomainRecordConfig,
+};
+use std::time::Duration;
+
+fn config(api_url: String) -> PowerDNSApiConfig {
+    PowerDNSApiConfig {
+        api_url,
+        api_key: API_KEY.to_owned(),
+        server_id: SERVER_ID.to_owned(),
+        timeout: Duration::from_secs(3),
+    }
+}
+
+#[tokio::test]
+async fn add_record() {
+    let (api_url, rrsets) = stand_in::spawn();
+    let command = PowerDNSApiCommandInfra::new(config(api_url), stand_in::record()).unwrap();
+
+    let output = command.add_record("test001", "127.0.0.1").await.unwrap();
+    assert!(output.success);
+
+    let saved = rrsets
+        .lock()
+        .unwrap()
+        .get(&("test001.u.isucon.dev.".to_owned(), "A".to_owned()))
+        .cloned();
+    assert_eq!(saved, Some((0, vec!["127.0.0.1".to_owned()])));
+}
+
+#[tokio::test]
+async fn add_record_rejected() {
+    let (api_url, rrsets) = stand_in::spawn();
+    let mut config = config(api_url);
+    config.api_key = "unknown".to_owned();
+    let command = PowerDNSApiCommandInfra::new(config, stand_in::record()).unwrap();
+
+    let output = command.add_record("test001", "127.0.0.1").await.unwrap();
+    assert!(!output.success);
+    assert_eq!(output.stderr, b"Unauthorized");
+    assert!(rrsets.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn add_record_aaaa() {
+    let (api_url, rrsets) = stand_in::spawn();
+    let record = SubdomainRecordConfig::new("u.isucon.dev.", RecordType::Aaaa, 300, "::1").unwrap();
+    let command = PowerDNSApiCommandInfra::new(config(format!("{}/", api_url)), record).unwrap();
+
+    let output = command.add_record("test001", "::1").await.unwrap();
+    assert!(output.success);
+
+    let saved = rrsets
+        .lock()
+        .unwrap()
+        .get(&("test001.u.isucon.dev.".to_owned(), "AAAA".to_owned()))
+        .cloned();
+    assert_eq!(saved, Some((300, vec!["::1".to_owned()])));
+}
@@ -0,0 +1,87 @@
+use crate::commands::powerdns_api_command::stand_in::{self, API_KEY, SERVER_ID, ZONE};
+use crate::commands::powerdns_api_command::{PowerDNSApiCommandInfra, PowerDNSApiConfig};
+use isupipe_core::commands::pdnsutil_command::{
+    PDNSUtilCommand, RecordType, SubdomainRecordConfig,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn delete_record() {
+    let (api_url, rrsets) = stand_in::spawn();
+    let command = PowerDNSApiCommandInfra::new(
+        PowerDNSApiConfig {
+            api_url,
+            api_key: API_KEY.to_owned(),
+            server_id: SERVER_ID.to_owned(),
+            timeout: Duration::from_secs(3),
+        },
+        stand_in::record(),
+    )
+    .unwrap();
+    command.add_record("test001", "127.0.0.1").await.unwrap();
+    command.add_record("test002", "127.0.0.1").await.unwrap();
+
+    let output = command.delete_record("test001").await.unwrap();
+    assert!(output.success);
+
+    let names: Vec<String> = rrsets
+        .lock()
+        .unwrap()
+        .keys()
+        .map(|(name, _)| name.clone())
+        .collect();
+    assert_eq!(names, vec!["test002.u.isucon.dev.".to_owned()]);
+}
+
+#[tokio::test]
+async fn delete_record_unknown_server() {
+    let (api_url, _) = stand_in::spawn();
+    let command = PowerDNSApiCommandInfra::new(
+        PowerDNSApiConfig {
+            api_url,
+            api_key: API_KEY.to_owned(),
+            server_id: "unknown".to_owned(),
+            timeout: Duration::from_secs(3),
+        },
+        stand_in::record(),
+    )
+    .unwrap();
+
+    let output = command.delete_record("test001").await.unwrap();
+    assert!(!output.success);
+}
+
+#[tokio::test]
+async fn delete_record_configured_type() {
+    let (api_url, rrsets) = stand_in::spawn();
+    let record = SubdomainRecordConfig::new(ZONE, RecordType::Cname, 0, "lb.example.com").unwrap();
+    let command = PowerDNSApiCommandInfra::new(
+        PowerDNSApiConfig {
+            api_url,
+            api_key: API_KEY.to_owned(),
+            server_id: SERVER_ID.to_owned(),
+            timeout: Duration::from_secs(3),
+        },
+        record,
+    )
+    .unwrap();
+    command
+        .add_record("test001", "lb.example.com.")
+        .await
+        .unwrap();
+    rrsets.lock().unwrap().insert(
+        ("test001.u.isucon.dev.".to_owned(), "A".to_owned()),
+        (0, vec!["127.0.0.1".to_owned()]),
+    );
+
+    let output = command.delete_record("test001").await.unwrap();
+    assert!(output.success);
+
+    let types: Vec<String> = rrsets
+        .lock()
+        .unwrap()
+        .keys()
+        .map(|(_, record_type)| record_type.clone())
+        .collect();
+    assert_eq!(types, vec!["A".to_owned()]);
+}
@@ -0,0 +1,54 @@
+use crate::commands::powerdns_api_command::stand_in::{self, API_KEY, SERVER_ID};
+use crate::commands::powerdns_api_command::{PowerDNSApiCommandInfra, PowerDNSApiConfig};
+use isupipe_core::commands::pdnsutil_command::{DnsRecord, PDNSUtilCommand};
+use isupipe_core::commands::CommandError;
+use std::time::Duration;
+
+fn config(api_url: String) -> PowerDNSApiConfig {
+    PowerDNSApiConfig {
+        api_url,
+        api_key: API_KEY.to_owned(),
+        server_id: SERVER_ID.to_owned(),
+        timeout: Duration::from_secs(3),
+    }
+}
+
+#[tokio::test]
+async fn list_records() {
+    let (api_url, rrsets) = stand_in::spawn();
+    rrsets.lock().unwrap().insert(
+        ("test001.u.isucon.dev.".to_owned(), "A".to_owned()),
+        (0, vec!["127.0.0.1".to_owned(), "127.0.0.2".to_owned()]),
+    );
+    let command = PowerDNSApiCommandInfra::new(config(api_url), stand_in::record()).unwrap();
+
+    let records = command.list_records().await.unwrap();
+    assert_eq!(
+        records,
+        vec![
+            DnsRecord {
+                name: "test001.u.isucon.dev".to_owned(),
+                record_type: "A".to_owned(),
+                ttl: 0,
+                content: "127.0.0.1".to_owned(),
+            },
+            DnsRecord {
+                name: "test001.u.isucon.dev".to_owned(),
+                record_type: "A".to_owned(),
+                ttl: 0,
+                content: "127.0.0.2".to_owned(),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn list_records_rejected() {
+    let (api_url, _) = stand_in::spawn();
+    let mut config = config(api_url);
+    config.api_key = "unknown".to_owned();
+    let command = PowerDNSApiCommandInfra::new(config, stand_in::record()).unwrap();
+
+    let result = command.list_records().await;
+    assert!(matches!(result, Err(CommandError::RequestError(_))));
+}
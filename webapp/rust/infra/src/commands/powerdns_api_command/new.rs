@@ -0,0 +1,22 @@
+use crate::commands::powerdns_api_command::stand_in::{self, API_KEY, SERVER_ID};
+use crate::commands::powerdns_api_command::{PowerDNSApiCommandInfra, PowerDNSApiConfig};
+use isupipe_core::commands::pdnsutil_command::PDNSUtilCommand;
+use isupipe_core::commands::CommandError;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn timeout_case() {
+    let api_url = stand_in::spawn_unresponsive();
+    let config = PowerDNSApiConfig {
+        api_url,
+        api_key: API_KEY.to_owned(),
+        server_id: SERVER_ID.to_owned(),
+        timeout: Duration::from_millis(200),
+    };
+    let command = PowerDNSApiCommandInfra::new(config, stand_in::record()).unwrap();
+
+    let started_at = Instant::now();
+    let result = command.add_record("test001", "127.0.0.1").await;
+    assert!(matches!(result, Err(CommandError::RequestError(_))));
+    assert!(started_at.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,136 @@
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use isupipe_core::commands::pdnsutil_command::{RecordType, SubdomainRecordConfig};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub const API_KEY: &str = "test-api-key";
+pub const SERVER_ID: &str = "localhost";
+pub const ZONE: &str = "u.isucon.dev";
+
+/// 127.0.0.1 を指す TTL 0 の A レコード
+pub fn record() -> SubdomainRecordConfig {
+    SubdomainRecordConfig::new(ZONE, RecordType::A, 0, "127.0.0.1").unwrap()
+}
+
+/// (名前, 種類) ごとの (TTL, 内容)。名前は末尾にドットが付いた完全修飾名
+pub type RRSets = Arc<Mutex<BTreeMap<(String, String), (u32, Vec<String>)>>>;
+
+/// テスト用の PowerDNS API サーバを起動し、URLと保存されたレコードを返す
+/// ゾーンの取得と PATCH による REPLACE/DELETE だけに応える
+pub fn spawn() -> (String, RRSets) {
+    let rrsets = RRSets::default();
+    let app = axum::Router::new()
+        .route(
+            "/api/v1/servers/:server_id/zones/:zone",
+            axum::routing::get(get_zone).patch(patch_zone),
+        )
+        .with_state(rrsets.clone());
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = axum::Server::from_tcp(listener)
+        .unwrap()
+        .serve(app.into_make_service());
+    tokio::spawn(server);
+
+    (format!("http://{}", addr), rrsets)
+}
+
+fn verify(headers: &HeaderMap, server_id: &str, zone: &str) -> Result<(), (StatusCode, String)> {
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if api_key != API_KEY {
+        return Err((StatusCode::UNAUTHORIZED, "Unauthorized".to_owned()));
+    }
+    if server_id != SERVER_ID || zone != format!("{}.", ZONE) {
+        return Err((StatusCode::NOT_FOUND, "Not Found".to_owned()));
+    }
+    Ok(())
+}
+
+async fn get_zone(
+    State(rrsets): State<RRSets>,
+    Path((server_id, zone)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    verify(&headers, &server_id, &zone)?;
+    let rrsets: Vec<Value> = rrsets
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|((name, record_type), (ttl, contents))| {
+            let records: Vec<Value> = contents
+                .iter()
+                .map(|content| json!({ "content": content, "disabled": false }))
+                .collect();
+            json!({
+                "name": name,
+                "type": record_type,
+                "ttl": ttl,
+                "records": records,
+                "comments": [],
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({ "id": zone, "name": zone, "rrsets": rrsets })))
+}
+
+async fn patch_zone(
+    State(rrsets): State<RRSets>,
+    Path((server_id, zone)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    verify(&headers, &server_id, &zone)?;
+    let bad_request = || (StatusCode::UNPROCESSABLE_ENTITY, "invalid rrset".to_owned());
+
+    let mut rrsets = rrsets.lock().unwrap();
+    for change in body["rrsets"].as_array().ok_or_else(bad_request)? {
+        let name = change["name"].as_str().ok_or_else(bad_request)?;
+        let record_type = change["type"].as_str().ok_or_else(bad_request)?;
+        if !name.ends_with(&format!(".{}.", ZONE)) {
+            return Err(bad_request());
+        }
+        let key = (name.to_owned(), record_type.to_owned());
+        match change["changetype"].as_str() {
+            Some("REPLACE") => {
+                let ttl = change["ttl"].as_u64().ok_or_else(bad_request)?;
+                let contents = change["records"]
+                    .as_array()
+                    .ok_or_else(bad_request)?
+                    .iter()
+                    .map(|record| record["content"].as_str().map(str::to_owned))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(bad_request)?;
+                rrsets.insert(key, (ttl as u32, contents));
+            }
+            Some("DELETE") => {
+                rrsets.remove(&key);
+            }
+            _ => return Err(bad_request()),
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 接続は受け付けるが応答を返さないサーバを起動し、URLを返す
+pub fn spawn_unresponsive() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        // 接続を閉じずに持ち続ける
+        let mut streams = Vec::new();
+        for stream in listener.incoming() {
+            streams.push(stream);
+        }
+    });
+
+    format!("http://{}", addr)
+}
@@ -0,0 +1,36 @@
+use crate::commands::powerdns_api_command::stand_in::{self, API_KEY, SERVER_ID};
+use crate::commands::powerdns_api_command::{PowerDNSApiCommandInfra, PowerDNSApiConfig};
+use isupipe_core::commands::pdnsutil_command::PDNSUtilCommand;
+use std::time::Duration;
+
+#[tokio::test]
+async fn update_record() {
+    let (api_url, rrsets) = stand_in::spawn();
+    rrsets.lock().unwrap().insert(
+        ("test001.u.isucon.dev.".to_owned(), "A".to_owned()),
+        (3600, vec!["127.0.0.1".to_owned(), "127.0.0.2".to_owned()]),
+    );
+    let command = PowerDNSApiCommandInfra::new(
+        PowerDNSApiConfig {
+            api_url,
+            api_key: API_KEY.to_owned(),
+            server_id: SERVER_ID.to_owned(),
+            timeout: Duration::from_secs(3),
+        },
+        stand_in::record(),
+    )
+    .unwrap();
+
+    let output = command
+        .update_record("test001", "192.168.0.1")
+        .await
+        .unwrap();
+    assert!(output.success);
+
+    let saved = rrsets
+        .lock()
+        .unwrap()
+        .get(&("test001.u.isucon.dev.".to_owned(), "A".to_owned()))
+        .cloned();
+    assert_eq!(saved, Some((0, vec!["192.168.0.1".to_owned()])));
+}
@@ -1,3 +1,6 @@
+pub mod caches;
 pub mod commands;
+pub mod notifiers;
 pub mod repos;
 pub mod services;
+pub mod storages;
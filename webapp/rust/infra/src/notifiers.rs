@@ -0,0 +1,58 @@
+use crate::notifiers::file_password_reset_notifier::FilePasswordResetNotifierInfra;
+use async_trait::async_trait;
+use isupipe_core::models::user::User;
+use isupipe_core::notifiers::password_reset_notifier::PasswordResetNotifier;
+use isupipe_core::notifiers::{NotifierError, NotifierResult};
+
+pub mod file_password_reset_notifier;
+
+const PASSWORD_RESET_NOTIFIER_ENV_KEY: &str = "ISUCON13_PASSWORD_RESET_NOTIFIER";
+const PASSWORD_RESET_NOTIFIER_FILE_ENV_KEY: &str = "ISUCON13_PASSWORD_RESET_NOTIFIER_FILE";
+const DEFAULT_PASSWORD_RESET_NOTIFIER_FILE: &str = "../password-reset-tokens.log";
+
+/// 設定で選択するパスワード再設定トークンの通知先
+/// メール送信などはまだ無いので、手元で確認するための実装だけを用意している
+#[derive(Clone)]
+pub enum PasswordResetNotifierInfra {
+    /// 発行したことだけをアプリケーションのログに出力する。トークン自体はログに残さない
+    Log,
+    /// ファイルに追記する
+    File(FilePasswordResetNotifierInfra),
+}
+
+impl PasswordResetNotifierInfra {
+    /// 環境変数から通知先を組み立てる。未指定ならログに出力する
+    pub fn from_env() -> NotifierResult<Self> {
+        let kind = std::env::var(PASSWORD_RESET_NOTIFIER_ENV_KEY).unwrap_or_default();
+        match kind.as_str() {
+            "" | "log" => Ok(Self::Log),
+            "file" => {
+                let path = std::env::var(PASSWORD_RESET_NOTIFIER_FILE_ENV_KEY)
+                    .unwrap_or_else(|_| DEFAULT_PASSWORD_RESET_NOTIFIER_FILE.to_owned());
+                Ok(Self::File(FilePasswordResetNotifierInfra::new(path)))
+            }
+            _ => Err(NotifierError::ConfigError(format!(
+                "{} must be one of log, file: {}",
+                PASSWORD_RESET_NOTIFIER_ENV_KEY, kind
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetNotifier for PasswordResetNotifierInfra {
+    async fn notify(&self, user: &User, token: &str, expires_at: i64) -> NotifierResult<()> {
+        match self {
+            Self::Log => {
+                tracing::info!(
+                    "password reset token issued for {} (expires at {}); set {}=file to receive tokens",
+                    user.name.inner(),
+                    expires_at,
+                    PASSWORD_RESET_NOTIFIER_ENV_KEY
+                );
+                Ok(())
+            }
+            Self::File(notifier) => notifier.notify(user, token, expires_at).await,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use isupipe_core::models::user::User;
+use isupipe_core::notifiers::password_reset_notifier::PasswordResetNotifier;
+use isupipe_core::notifiers::NotifierResult;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+#[cfg(test)]
+mod notify;
+
+/// トークンを1行ずつファイルに追記する。ローカルでの動作確認用
+/// 行の形式は `<expires_at>\t<username>\t<token>`
+#[derive(Clone)]
+pub struct FilePasswordResetNotifierInfra {
+    path: Arc<PathBuf>,
+}
+
+impl FilePasswordResetNotifierInfra {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Arc::new(path.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetNotifier for FilePasswordResetNotifierInfra {
+    async fn notify(&self, user: &User, token: &str, expires_at: i64) -> NotifierResult<()> {
+        let line = format!("{}\t{}\t{}\n", expires_at, user.name.inner(), token);
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path.as_ref())
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::notifiers::file_password_reset_notifier::FilePasswordResetNotifierInfra;
+use fake::{Fake, Faker};
+use isupipe_core::models::user::{User, UserName};
+use isupipe_core::notifiers::password_reset_notifier::PasswordResetNotifier;
+
+#[tokio::test]
+async fn appended_case() {
+    let path =
+        std::env::temp_dir().join(format!("isupipe-password-reset-{}.log", std::process::id()));
+    let notifier = FilePasswordResetNotifierInfra::new(&path);
+
+    let mut user: User = Faker.fake();
+    user.name = UserName::new("alice".to_owned());
+    notifier.notify(&user, "token1", 100).await.unwrap();
+    notifier.notify(&user, "token2", 200).await.unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, "100\talice\ttoken1\n200\talice\ttoken2\n");
+
+    std::fs::remove_file(path).unwrap();
+}
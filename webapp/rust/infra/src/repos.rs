@@ -1,3 +1,4 @@
+pub mod account_repository;
 pub mod icon_repository;
 pub mod livestream_comment_report_repository;
 pub mod livestream_comment_repository;
@@ -5,6 +6,7 @@ pub mod livestream_repository;
 pub mod livestream_tag_repository;
 pub mod livestream_viewers_history_repository;
 pub mod ng_word_repository;
+pub mod password_reset_token_repository;
 pub mod reaction_repository;
 pub mod reservation_slot_repository;
 pub mod tag_repository;
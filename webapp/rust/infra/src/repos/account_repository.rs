@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod delete_all_by_user_id;
+
+use async_trait::async_trait;
+use isupipe_core::db::DBConn;
+use isupipe_core::models::user::UserId;
+use isupipe_core::repos::account_repository::AccountRepository;
+
+#[derive(Clone)]
+pub struct AccountRepositoryInfra {}
+
+/// 削除するテーブルと、そのクエリに渡す user_id の個数
+/// 配信に付いたデータを先に消してから livestreams を消す
+const DELETE_QUERIES: &[(&str, usize)] = &[
+    // 配信が押さえていた予約枠を空ける
+    (
+        r#"
+UPDATE reservation_slots
+INNER JOIN (
+  SELECT reservation_slots.id, COUNT(*) AS freed
+  FROM reservation_slots
+  INNER JOIN livestreams
+    ON reservation_slots.start_at >= livestreams.start_at
+    AND reservation_slots.end_at <= livestreams.end_at
+  WHERE livestreams.user_id = ?
+  GROUP BY reservation_slots.id
+) AS freed_slots ON reservation_slots.id = freed_slots.id
+SET reservation_slots.slot = reservation_slots.slot + freed_slots.freed
+        "#,
+        1,
+    ),
+    (
+        r#"
+DELETE FROM livecomment_reports
+WHERE user_id = ?
+  OR livestream_id IN (SELECT id FROM livestreams WHERE user_id = ?)
+  OR livecomment_id IN (SELECT id FROM livecomments WHERE user_id = ?)
+        "#,
+        3,
+    ),
+    (
+        "DELETE FROM livecomments WHERE user_id = ? OR livestream_id IN (SELECT id FROM livestreams WHERE user_id = ?)",
+        2,
+    ),
+    (
+        "DELETE FROM reactions WHERE user_id = ? OR livestream_id IN (SELECT id FROM livestreams WHERE user_id = ?)",
+        2,
+    ),
+    (
+        "DELETE FROM livestream_viewers_history WHERE user_id = ? OR livestream_id IN (SELECT id FROM livestreams WHERE user_id = ?)",
+        2,
+    ),
+    (
+        "DELETE FROM ng_words WHERE user_id = ? OR livestream_id IN (SELECT id FROM livestreams WHERE user_id = ?)",
+        2,
+    ),
+    (
+        "DELETE FROM livestream_tags WHERE livestream_id IN (SELECT id FROM livestreams WHERE user_id = ?)",
+        1,
+    ),
+    ("DELETE FROM livestreams WHERE user_id = ?", 1),
+    ("DELETE FROM icons WHERE user_id = ?", 1),
+    ("DELETE FROM icon_thumbnails WHERE user_id = ?", 1),
+    ("DELETE FROM themes WHERE user_id = ?", 1),
+    ("DELETE FROM password_reset_tokens WHERE user_id = ?", 1),
+    ("DELETE FROM sessions WHERE user_id = ?", 1),
+    // IP ごとの記録は他のユーザーと共有しているので残す
+    (
+        "DELETE FROM login_attempts WHERE attempt_key = (SELECT CONCAT('user:', name) FROM users WHERE id = ?)",
+        1,
+    ),
+    ("DELETE FROM users WHERE id = ?", 1),
+];
+
+#[async_trait]
+impl AccountRepository for AccountRepositoryInfra {
+    async fn delete_all_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+    ) -> isupipe_core::repos::Result<()> {
+        for (sql, binds) in DELETE_QUERIES {
+            let mut query = sqlx::query(sql);
+            for _ in 0..*binds {
+                query = query.bind(user_id);
+            }
+            query.execute(&mut *conn).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use crate::repos::account_repository::AccountRepositoryInfra;
+use crate::repos::theme_repository::ThemeRepositoryInfra;
+use crate::repos::user_repository::UserRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::user::CreateUser;
+use isupipe_core::repos::account_repository::AccountRepository;
+use isupipe_core::repos::theme_repository::ThemeRepository;
+use isupipe_core::repos::user_repository::UserRepository;
+
+#[tokio::test]
+async fn deleted_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let user_repo = UserRepositoryInfra {};
+    let user: CreateUser = Faker.fake();
+    let user_id = user_repo
+        .create(&mut tx, &user, &user.password)
+        .await
+        .unwrap();
+    ThemeRepositoryInfra {}
+        .create(&mut tx, &user_id, false)
+        .await
+        .unwrap();
+
+    // 他のユーザーが配信に付けたコメントも消える
+    let livestream_id = sqlx::query(
+        "INSERT INTO livestreams (user_id, title, description, playlist_url, thumbnail_url, start_at, end_at) VALUES (?, '', '', '', '', 100, 300)",
+    )
+    .bind(&user_id)
+    .execute(&mut *tx)
+    .await
+    .unwrap()
+    .last_insert_id();
+    let other: CreateUser = Faker.fake();
+    let other_id = user_repo
+        .create(&mut tx, &other, &other.password)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO livecomments (user_id, livestream_id, comment, tip, created_at) VALUES (?, ?, 'hi', 0, 0)",
+    )
+    .bind(&other_id)
+    .bind(livestream_id)
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+
+    // 配信が押さえていた枠と、配信と重ならない枠
+    let mut slot_ids = Vec::new();
+    for (start_at, end_at) in [(100, 200), (200, 300), (300, 400)] {
+        let slot_id =
+            sqlx::query("INSERT INTO reservation_slots (slot, start_at, end_at) VALUES (5, ?, ?)")
+                .bind(start_at)
+                .bind(end_at)
+                .execute(&mut *tx)
+                .await
+                .unwrap()
+                .last_insert_id();
+        slot_ids.push(slot_id);
+    }
+    sqlx::query("INSERT INTO sessions (id, user_id, expires_at) VALUES (?, ?, 0)")
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&user_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO login_attempts (attempt_key, failures, last_failed_at, locked_until) VALUES (?, 1, 0, 0)",
+    )
+    .bind(format!("user:{}", user.name))
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+
+    let repo = AccountRepositoryInfra {};
+    repo.delete_all_by_user_id(&mut tx, &user_id).await.unwrap();
+
+    assert!(user_repo.find(&mut tx, &user_id).await.unwrap().is_none());
+    let (comments,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM livecomments WHERE livestream_id = ?")
+            .bind(livestream_id)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+    assert_eq!(comments, 0);
+    let (themes,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM themes WHERE user_id = ?")
+        .bind(&user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(themes, 0);
+    let (sessions,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sessions WHERE user_id = ?")
+        .bind(&user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(sessions, 0);
+    let (attempts,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM login_attempts WHERE attempt_key = ?")
+            .bind(format!("user:{}", user.name))
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+    assert_eq!(attempts, 0);
+
+    // 予約枠は配信と重なる分だけ空く
+    let mut slots = Vec::new();
+    for slot_id in &slot_ids {
+        let (slot,): (i64,) = sqlx::query_as("SELECT slot FROM reservation_slots WHERE id = ?")
+            .bind(slot_id)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        slots.push(slot);
+    }
+    assert_eq!(slots, vec![6, 6, 5]);
+
+    // 他のユーザーは残る
+    assert!(user_repo.find(&mut tx, &other_id).await.unwrap().is_some());
+}
@@ -1,9 +1,14 @@
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
-use isupipe_core::models::icon::CreateIcon;
+use isupipe_core::models::icon::{
+    CreateIcon, CreateIconThumbnail, Icon, IconBlob, IconHash, IconId, StoredIconImage,
+};
 use isupipe_core::models::user::UserId;
 use isupipe_core::repos::icon_repository::IconRepository;
 
+#[cfg(test)]
+mod update_hash_if_empty;
+
 #[derive(Clone)]
 pub struct IconRepositoryInfra {}
 
@@ -13,9 +18,9 @@ impl IconRepository for IconRepositoryInfra {
         &self,
         conn: &mut DBConn,
         user_id: &UserId,
-    ) -> isupipe_core::repos::Result<Option<Vec<u8>>> {
-        let image: Option<Vec<u8>> =
-            sqlx::query_scalar("SELECT image FROM icons WHERE user_id = ?")
+    ) -> isupipe_core::repos::Result<Option<StoredIconImage>> {
+        let image: Option<StoredIconImage> =
+            sqlx::query_as("SELECT mime_type, hash, image FROM icons WHERE user_id = ?")
                 .bind(user_id)
                 .fetch_optional(conn)
                 .await?;
@@ -23,21 +28,178 @@ impl IconRepository for IconRepositoryInfra {
         Ok(image)
     }
 
+    async fn find_thumbnail_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+        size: u32,
+    ) -> isupipe_core::repos::Result<Option<StoredIconImage>> {
+        let image: Option<StoredIconImage> = sqlx::query_as(
+            "SELECT mime_type, hash, image FROM icon_thumbnails WHERE user_id = ? AND size = ?",
+        )
+        .bind(user_id)
+        .bind(size)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(image)
+    }
+
+    async fn find_blobs_limit(
+        &self,
+        conn: &mut DBConn,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<IconBlob>> {
+        let blobs: Vec<IconBlob> = sqlx::query_as(
+            "SELECT id, hash, image FROM icons WHERE image IS NOT NULL ORDER BY id LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(blobs)
+    }
+
+    async fn find_thumbnail_blobs_limit(
+        &self,
+        conn: &mut DBConn,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<IconBlob>> {
+        let blobs: Vec<IconBlob> = sqlx::query_as(
+            "SELECT id, hash, image FROM icon_thumbnails WHERE image IS NOT NULL ORDER BY id LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(blobs)
+    }
+
+    async fn clear_blob(
+        &self,
+        conn: &mut DBConn,
+        id: i64,
+        hash: &str,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query("UPDATE icons SET image = NULL, hash = ? WHERE id = ?")
+            .bind(hash)
+            .bind(id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn clear_thumbnail_blob(
+        &self,
+        conn: &mut DBConn,
+        id: i64,
+        hash: &str,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query("UPDATE icon_thumbnails SET image = NULL, hash = ? WHERE id = ?")
+            .bind(hash)
+            .bind(id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find_many_by_user_ids(
+        &self,
+        conn: &mut DBConn,
+        user_ids: &[UserId],
+    ) -> isupipe_core::repos::Result<Vec<Icon>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder =
+            sqlx::query_builder::QueryBuilder::new("SELECT * FROM icons WHERE user_id IN (");
+        let mut separated = query_builder.separated(", ");
+        for id in user_ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let icons: Vec<Icon> = query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(icons)
+    }
+
+    async fn find_many_hashes_by_user_ids(
+        &self,
+        conn: &mut DBConn,
+        user_ids: &[UserId],
+    ) -> isupipe_core::repos::Result<Vec<IconHash>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder = sqlx::query_builder::QueryBuilder::new(
+            "SELECT user_id, hash FROM icons WHERE user_id IN (",
+        );
+        let mut separated = query_builder.separated(", ");
+        for id in user_ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let icon_hashes: Vec<IconHash> = query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(icon_hashes)
+    }
+
+    async fn update_hash_if_empty(
+        &self,
+        conn: &mut DBConn,
+        id: &IconId,
+        hash: &str,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query("UPDATE icons SET hash = ? WHERE id = ? AND hash = ''")
+            .bind(hash)
+            .bind(id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
     async fn create(
         &self,
         conn: &mut DBConn,
         icon: &CreateIcon,
     ) -> isupipe_core::repos::Result<i64> {
-        let rs = sqlx::query("INSERT INTO icons (user_id, image) VALUES (?, ?)")
-            .bind(&icon.user_id)
-            .bind(&icon.image)
-            .execute(conn)
-            .await?;
+        let rs =
+            sqlx::query("INSERT INTO icons (user_id, image, hash, mime_type) VALUES (?, ?, ?, ?)")
+                .bind(&icon.user_id)
+                .bind(&icon.image)
+                .bind(&icon.hash)
+                .bind(&icon.mime_type)
+                .execute(conn)
+                .await?;
         let icon_id = rs.last_insert_id() as i64;
 
         Ok(icon_id)
     }
 
+    async fn create_thumbnail(
+        &self,
+        conn: &mut DBConn,
+        thumbnail: &CreateIconThumbnail,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query(
+            "INSERT INTO icon_thumbnails (user_id, size, mime_type, image, hash) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&thumbnail.user_id)
+        .bind(thumbnail.size)
+        .bind(&thumbnail.mime_type)
+        .bind(&thumbnail.image)
+        .bind(&thumbnail.hash)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
     async fn delete_by_user_id(
         &self,
         conn: &mut DBConn,
@@ -50,4 +212,17 @@ impl IconRepository for IconRepositoryInfra {
 
         Ok(())
     }
+
+    async fn delete_thumbnails_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query("DELETE FROM icon_thumbnails WHERE user_id = ?")
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,48 @@
+use crate::repos::icon_repository::IconRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::icon::{CreateIcon, IconId};
+use isupipe_core::models::user::UserId;
+use isupipe_core::repos::icon_repository::IconRepository;
+
+#[tokio::test]
+async fn only_empty_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = IconRepositoryInfra {};
+    let mut user_ids = Vec::new();
+    let mut icon_ids = Vec::new();
+    // 他の実装で保存された行と、アップロード時にハッシュを保存した行
+    for hash in ["", "uploaded"] {
+        let user_id: UserId = Faker.fake();
+        let id = repo
+            .create(
+                &mut tx,
+                &CreateIcon {
+                    user_id: user_id.clone(),
+                    image: Some(vec![1, 2, 3]),
+                    hash: hash.to_owned(),
+                    mime_type: "image/jpeg".to_owned(),
+                },
+            )
+            .await
+            .unwrap();
+        user_ids.push(user_id);
+        icon_ids.push(IconId::new(id));
+    }
+
+    for id in &icon_ids {
+        repo.update_hash_if_empty(&mut tx, id, "computed")
+            .await
+            .unwrap();
+    }
+
+    let mut got = repo
+        .find_many_hashes_by_user_ids(&mut tx, &user_ids)
+        .await
+        .unwrap();
+    got.sort_by_key(|icon| user_ids.iter().position(|id| id == &icon.user_id));
+    let hashes: Vec<String> = got.into_iter().map(|icon| icon.hash).collect();
+    assert_eq!(hashes, vec!["computed", "uploaded"]);
+}
@@ -1,5 +1,13 @@
+#[cfg(test)]
+mod delete_many_by_livestream_id;
+#[cfg(test)]
+mod find_all_by_livestream_id_after_id_limit;
+#[cfg(test)]
+mod find_all_by_livestream_id_before_cursor;
+
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
+use isupipe_core::models::cursor::Cursor;
 use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::livestream_comment::{
     CreateLivestreamComment, LivestreamComment, LivestreamCommentId,
@@ -32,33 +40,29 @@ impl LivestreamCommentRepository for LivestreamCommentRepositoryInfra {
         Ok(LivestreamCommentId::new(comment_id))
     }
 
-    async fn remove_if_match_ng_word(
+    async fn delete_many_by_livestream_id(
         &self,
         conn: &mut DBConn,
-        comment: &LivestreamComment,
-        ng_word: &str,
-    ) -> isupipe_core::repos::Result<()> {
-        let query = r#"
-        DELETE FROM livecomments
-        WHERE
-        id = ? AND
-        livestream_id = ? AND
-        (SELECT COUNT(*)
-        FROM
-        (SELECT ? AS text) AS texts
-        INNER JOIN
-        (SELECT CONCAT('%', ?, '%')	AS pattern) AS patterns
-        ON texts.text LIKE patterns.pattern) >= 1
-        "#;
-        sqlx::query(query)
-            .bind(&comment.id)
-            .bind(&comment.livestream_id)
-            .bind(&comment.comment)
-            .bind(ng_word)
-            .execute(conn)
-            .await?;
+        livestream_id: &LivestreamId,
+        comment_ids: &[LivestreamCommentId],
+    ) -> isupipe_core::repos::Result<u64> {
+        if comment_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut query_builder = sqlx::query_builder::QueryBuilder::new(
+            "DELETE FROM livecomments WHERE livestream_id = ",
+        );
+        query_builder.push_bind(livestream_id);
+        query_builder.push(" AND id IN (");
+        let mut separated = query_builder.separated(", ");
+        for id in comment_ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let result = query_builder.build().execute(conn).await?;
 
-        Ok(())
+        Ok(result.rows_affected())
     }
 
     async fn find(
@@ -74,15 +78,26 @@ impl LivestreamCommentRepository for LivestreamCommentRepositoryInfra {
         Ok(comment)
     }
 
-    async fn find_all(
+    async fn find_many(
         &self,
         conn: &mut DBConn,
+        comment_ids: &[LivestreamCommentId],
     ) -> isupipe_core::repos::Result<Vec<LivestreamComment>> {
-        let livecomments: Vec<LivestreamComment> = sqlx::query_as("SELECT * FROM livecomments")
-            .fetch_all(conn)
-            .await?;
+        if comment_ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        Ok(livecomments)
+        let mut query_builder =
+            sqlx::query_builder::QueryBuilder::new("SELECT * FROM livecomments WHERE id IN (");
+        let mut separated = query_builder.separated(", ");
+        for id in comment_ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let comments: Vec<LivestreamComment> =
+            query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(comments)
     }
 
     async fn find_all_by_livestream_id(
@@ -122,7 +137,7 @@ impl LivestreamCommentRepository for LivestreamCommentRepositoryInfra {
         limit: i64,
     ) -> isupipe_core::repos::Result<Vec<LivestreamComment>> {
         let query =
-            "SELECT * FROM livecomments WHERE livestream_id = ? ORDER BY created_at DESC LIMIT ?"
+            "SELECT * FROM livecomments WHERE livestream_id = ? ORDER BY created_at DESC, id DESC LIMIT ?"
                 .to_owned();
 
         let comments: Vec<LivestreamComment> = sqlx::query_as(&query)
@@ -134,6 +149,75 @@ impl LivestreamCommentRepository for LivestreamCommentRepositoryInfra {
         Ok(comments)
     }
 
+    async fn find_all_by_livestream_id_after_id_limit(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        comment_id: &LivestreamCommentId,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<LivestreamComment>> {
+        let comments: Vec<LivestreamComment> = sqlx::query_as(
+            "SELECT * FROM livecomments WHERE livestream_id = ? AND id > ? ORDER BY id ASC LIMIT ?",
+        )
+        .bind(livestream_id)
+        .bind(comment_id)
+        .bind(limit)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(comments)
+    }
+
+    async fn find_all_by_livestream_id_before_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<LivestreamComment>> {
+        let query = r#"
+        SELECT * FROM livecomments
+        WHERE livestream_id = ? AND (created_at < ? OR (created_at = ? AND id < ?))
+        ORDER BY created_at DESC, id DESC
+        LIMIT ?
+        "#;
+        let comments: Vec<LivestreamComment> = sqlx::query_as(query)
+            .bind(livestream_id)
+            .bind(cursor.created_at)
+            .bind(cursor.created_at)
+            .bind(cursor.id)
+            .bind(limit)
+            .fetch_all(conn)
+            .await?;
+
+        Ok(comments)
+    }
+
+    async fn find_all_by_livestream_id_after_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<LivestreamComment>> {
+        let query = r#"
+        SELECT * FROM livecomments
+        WHERE livestream_id = ? AND (created_at > ? OR (created_at = ? AND id > ?))
+        ORDER BY created_at ASC, id ASC
+        LIMIT ?
+        "#;
+        let comments: Vec<LivestreamComment> = sqlx::query_as(query)
+            .bind(livestream_id)
+            .bind(cursor.created_at)
+            .bind(cursor.created_at)
+            .bind(cursor.id)
+            .bind(limit)
+            .fetch_all(conn)
+            .await?;
+
+        Ok(comments)
+    }
+
     async fn get_sum_tip(&self, conn: &mut DBConn) -> isupipe_core::repos::Result<i64> {
         let total_tip = sqlx::query_scalar("SELECT IFNULL(SUM(tip), 0) FROM livecomments")
             .fetch_one(conn)
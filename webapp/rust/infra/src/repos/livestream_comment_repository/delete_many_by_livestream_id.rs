@@ -0,0 +1,69 @@
+use crate::repos::livestream_comment_repository::LivestreamCommentRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::livestream_comment::CreateLivestreamComment;
+use isupipe_core::repos::livestream_comment_repository::LivestreamCommentRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = LivestreamCommentRepositoryInfra {};
+
+    let livestream_id: LivestreamId = Faker.fake();
+    let mut comment_ids = Vec::new();
+    for _ in 0..3 {
+        let mut comment: CreateLivestreamComment = Faker.fake();
+        comment.livestream_id = livestream_id.clone();
+        comment_ids.push(repo.create(&mut tx, &comment).await.unwrap());
+    }
+    let mut other_comment: CreateLivestreamComment = Faker.fake();
+    other_comment.livestream_id = LivestreamId::new(livestream_id.inner().wrapping_add(1));
+    let other_id = repo.create(&mut tx, &other_comment).await.unwrap();
+
+    // 別のライブ配信のコメントは消さない
+    let deleted = repo
+        .delete_many_by_livestream_id(
+            &mut tx,
+            &livestream_id,
+            &[
+                comment_ids[0].clone(),
+                comment_ids[2].clone(),
+                other_id.clone(),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(deleted, 2);
+
+    let remaining = repo
+        .find_many(
+            &mut tx,
+            &[
+                comment_ids[0].clone(),
+                comment_ids[1].clone(),
+                other_id.clone(),
+            ],
+        )
+        .await
+        .unwrap();
+    let remaining_ids: Vec<_> = remaining.into_iter().map(|c| c.id).collect();
+    assert_eq!(remaining_ids.len(), 2);
+    assert!(remaining_ids.contains(&comment_ids[1]));
+    assert!(remaining_ids.contains(&other_id));
+}
+
+#[tokio::test]
+async fn empty_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = LivestreamCommentRepositoryInfra {};
+    let deleted = repo
+        .delete_many_by_livestream_id(&mut tx, &Faker.fake(), &[])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 0);
+}
@@ -0,0 +1,44 @@
+use crate::repos::livestream_comment_repository::LivestreamCommentRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::livestream_comment::{CreateLivestreamComment, LivestreamCommentId};
+use isupipe_core::repos::livestream_comment_repository::LivestreamCommentRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = LivestreamCommentRepositoryInfra {};
+
+    let livestream_id: LivestreamId = Faker.fake();
+    let mut comment_ids = Vec::new();
+    for _ in 0..5 {
+        let mut comment: CreateLivestreamComment = Faker.fake();
+        comment.livestream_id = livestream_id.clone();
+        comment_ids.push(repo.create(&mut tx, &comment).await.unwrap());
+    }
+    let mut other_comment: CreateLivestreamComment = Faker.fake();
+    other_comment.livestream_id = LivestreamId::new(livestream_id.inner().wrapping_add(1));
+    repo.create(&mut tx, &other_comment).await.unwrap();
+
+    let first = repo
+        .find_all_by_livestream_id_after_id_limit(
+            &mut tx,
+            &livestream_id,
+            &LivestreamCommentId::new(0),
+            2,
+        )
+        .await
+        .unwrap();
+    let first_ids: Vec<_> = first.into_iter().map(|c| c.id).collect();
+    assert_eq!(first_ids, comment_ids[..2]);
+
+    let rest = repo
+        .find_all_by_livestream_id_after_id_limit(&mut tx, &livestream_id, &comment_ids[1], 10)
+        .await
+        .unwrap();
+    let rest_ids: Vec<_> = rest.into_iter().map(|c| c.id).collect();
+    assert_eq!(rest_ids, comment_ids[2..]);
+}
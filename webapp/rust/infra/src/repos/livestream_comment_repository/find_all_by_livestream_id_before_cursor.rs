@@ -0,0 +1,40 @@
+use crate::repos::livestream_comment_repository::LivestreamCommentRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::cursor::Cursor;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::livestream_comment::CreateLivestreamComment;
+use isupipe_core::repos::livestream_comment_repository::LivestreamCommentRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = LivestreamCommentRepositoryInfra {};
+
+    // created_atが同じコメントはidで順序が決まる
+    let livestream_id: LivestreamId = Faker.fake();
+    let mut comment_ids = Vec::new();
+    for created_at in [100, 200, 200, 300] {
+        let mut comment: CreateLivestreamComment = Faker.fake();
+        comment.livestream_id = livestream_id.clone();
+        comment.created_at = created_at;
+        comment_ids.push(repo.create(&mut tx, &comment).await.unwrap());
+    }
+
+    let cursor = Cursor {
+        created_at: 200,
+        id: *comment_ids[2].inner(),
+    };
+    let got = repo
+        .find_all_by_livestream_id_before_cursor(&mut tx, &livestream_id, &cursor, 10)
+        .await
+        .unwrap();
+
+    let got_ids: Vec<_> = got.into_iter().map(|c| c.id).collect();
+    assert_eq!(
+        got_ids,
+        vec![comment_ids[1].clone(), comment_ids[0].clone()]
+    );
+}
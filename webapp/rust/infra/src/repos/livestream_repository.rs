@@ -1,3 +1,8 @@
+#[cfg(test)]
+mod find_all_by_id_gt_order_by_id_asc_limit;
+#[cfg(test)]
+mod find_all_by_id_lt_order_by_id_desc_limit;
+
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
 use isupipe_core::models::livestream::{CreateLivestream, Livestream, LivestreamId};
@@ -63,6 +68,59 @@ impl LivestreamRepository for LivestreamRepositoryInfra {
         Ok(livestreams)
     }
 
+    async fn find_all_by_id_lt_order_by_id_desc_limit(
+        &self,
+        conn: &mut DBConn,
+        id: &LivestreamId,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<Livestream>> {
+        let livestreams: Vec<Livestream> =
+            sqlx::query_as("SELECT * FROM livestreams WHERE id < ? ORDER BY id DESC LIMIT ?")
+                .bind(id)
+                .bind(limit)
+                .fetch_all(conn)
+                .await?;
+
+        Ok(livestreams)
+    }
+
+    async fn find_all_by_id_gt_order_by_id_asc_limit(
+        &self,
+        conn: &mut DBConn,
+        id: &LivestreamId,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<Livestream>> {
+        let livestreams: Vec<Livestream> =
+            sqlx::query_as("SELECT * FROM livestreams WHERE id > ? ORDER BY id ASC LIMIT ?")
+                .bind(id)
+                .bind(limit)
+                .fetch_all(conn)
+                .await?;
+
+        Ok(livestreams)
+    }
+
+    async fn find_many(
+        &self,
+        conn: &mut DBConn,
+        ids: &[LivestreamId],
+    ) -> isupipe_core::repos::Result<Vec<Livestream>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder =
+            sqlx::query_builder::QueryBuilder::new("SELECT * FROM livestreams WHERE id IN (");
+        let mut separated = query_builder.separated(", ");
+        for id in ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let livestreams: Vec<Livestream> = query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(livestreams)
+    }
+
     async fn find_all_by_user_id(
         &self,
         conn: &mut DBConn,
@@ -0,0 +1,43 @@
+use crate::repos::livestream_repository::LivestreamRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::livestream::{CreateLivestream, LivestreamId};
+use isupipe_core::repos::livestream_repository::LivestreamRepository;
+
+fn livestream() -> CreateLivestream {
+    CreateLivestream {
+        user_id: Faker.fake(),
+        title: "title".to_owned(),
+        description: "description".to_owned(),
+        playlist_url: "https://example.com/playlist.m3u8".to_owned(),
+        thumbnail_url: "https://example.com/thumbnail.jpg".to_owned(),
+        start_at: 0,
+        end_at: 0,
+    }
+}
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = LivestreamRepositoryInfra {};
+    let mut ids: Vec<LivestreamId> = Vec::new();
+    for _ in 0..3 {
+        ids.push(repo.create(&mut tx, &livestream()).await.unwrap());
+    }
+
+    // カーソルに近いものから古い順に返す
+    let got = repo
+        .find_all_by_id_gt_order_by_id_asc_limit(&mut tx, &ids[0], 2)
+        .await
+        .unwrap();
+    let got_ids: Vec<_> = got.into_iter().map(|l| l.id).collect();
+    assert_eq!(got_ids, vec![ids[1].clone(), ids[2].clone()]);
+
+    let got = repo
+        .find_all_by_id_gt_order_by_id_asc_limit(&mut tx, &ids[2], 10)
+        .await
+        .unwrap();
+    assert!(got.is_empty());
+}
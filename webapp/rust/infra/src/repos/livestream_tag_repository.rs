@@ -1,10 +1,14 @@
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
+use isupipe_core::models::cursor::PageCursor;
 use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::livestream_tag::LivestreamTag;
 use isupipe_core::models::tag::TagId;
 use isupipe_core::repos::livestream_tag_repository::LivestreamTagRepository;
 
+#[cfg(test)]
+mod find_livestream_ids_page_by_tag_ids;
+
 #[derive(Clone)]
 pub struct LivestreamTagRepositoryInfra {}
 
@@ -39,6 +43,29 @@ impl LivestreamTagRepository for LivestreamTagRepositoryInfra {
         Ok(livestream_tag_models)
     }
 
+    async fn find_many_by_livestream_ids(
+        &self,
+        conn: &mut DBConn,
+        livestream_ids: &[LivestreamId],
+    ) -> isupipe_core::repos::Result<Vec<LivestreamTag>> {
+        if livestream_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder = sqlx::query_builder::QueryBuilder::new(
+            "SELECT * FROM livestream_tags WHERE livestream_id IN (",
+        );
+        let mut separated = query_builder.separated(", ");
+        for id in livestream_ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let livestream_tag_models: Vec<LivestreamTag> =
+            query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(livestream_tag_models)
+    }
+
     async fn find_all_by_tag_ids(
         &self,
         conn: &mut DBConn,
@@ -57,4 +84,50 @@ impl LivestreamTagRepository for LivestreamTagRepositoryInfra {
 
         Ok(livestreams)
     }
+
+    async fn find_livestream_ids_page_by_tag_ids(
+        &self,
+        conn: &mut DBConn,
+        tag_ids: &[TagId],
+        cursor: Option<PageCursor<LivestreamId>>,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<LivestreamId>> {
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder = sqlx::query_builder::QueryBuilder::new(
+            "SELECT DISTINCT livestream_id FROM livestream_tags WHERE tag_id IN (",
+        );
+        let mut separated = query_builder.separated(", ");
+        for tag_id in tag_ids {
+            separated.push_bind(tag_id);
+        }
+        separated.push_unseparated(")");
+        // After はカーソルに近い方から取るため昇順で取得して並べ直す
+        let ascending = matches!(cursor, Some(PageCursor::After(_)));
+        match &cursor {
+            None => {}
+            Some(PageCursor::Before(id)) => {
+                query_builder.push(" AND livestream_id < ").push_bind(id);
+            }
+            Some(PageCursor::After(id)) => {
+                query_builder.push(" AND livestream_id > ").push_bind(id);
+            }
+        }
+        query_builder
+            .push(if ascending {
+                " ORDER BY livestream_id ASC LIMIT "
+            } else {
+                " ORDER BY livestream_id DESC LIMIT "
+            })
+            .push_bind(limit);
+        let mut livestream_ids: Vec<LivestreamId> =
+            query_builder.build_query_scalar().fetch_all(conn).await?;
+        if ascending {
+            livestream_ids.reverse();
+        }
+
+        Ok(livestream_ids)
+    }
 }
@@ -0,0 +1,63 @@
+use crate::repos::livestream_tag_repository::LivestreamTagRepositoryInfra;
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::cursor::PageCursor;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::tag::TagId;
+use isupipe_core::repos::livestream_tag_repository::LivestreamTagRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = LivestreamTagRepositoryInfra {};
+    // どの配信にも付いていないタグID
+    let tag_ids = vec![TagId::new(1_000_001), TagId::new(1_000_002)];
+    let livestream_ids: Vec<LivestreamId> = (1..=4).map(LivestreamId::new).collect();
+    for livestream_id in &livestream_ids {
+        for tag_id in &tag_ids {
+            repo.insert(&mut tx, livestream_id, tag_id).await.unwrap();
+        }
+    }
+
+    // 複数のタグが付いた配信も1度だけ返す
+    let got = repo
+        .find_livestream_ids_page_by_tag_ids(&mut tx, &tag_ids, None, 3)
+        .await
+        .unwrap();
+    assert_eq!(
+        got,
+        vec![
+            livestream_ids[3].clone(),
+            livestream_ids[2].clone(),
+            livestream_ids[1].clone()
+        ]
+    );
+
+    let cursor = PageCursor::Before(livestream_ids[2].clone());
+    let got = repo
+        .find_livestream_ids_page_by_tag_ids(&mut tx, &tag_ids, Some(cursor), 3)
+        .await
+        .unwrap();
+    assert_eq!(
+        got,
+        vec![livestream_ids[1].clone(), livestream_ids[0].clone()]
+    );
+
+    // After でも新しい順に並べて返す
+    let cursor = PageCursor::After(livestream_ids[0].clone());
+    let got = repo
+        .find_livestream_ids_page_by_tag_ids(&mut tx, &tag_ids, Some(cursor), 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        got,
+        vec![livestream_ids[2].clone(), livestream_ids[1].clone()]
+    );
+
+    let got = repo
+        .find_livestream_ids_page_by_tag_ids(&mut tx, &[], None, 3)
+        .await
+        .unwrap();
+    assert!(got.is_empty());
+}
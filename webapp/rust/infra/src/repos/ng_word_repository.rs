@@ -16,11 +16,12 @@ impl NgWordRepository for NgWordRepositoryInfra {
         ng_word: &CreateNgWord,
     ) -> isupipe_core::repos::Result<NgWordId> {
         let rs = sqlx::query(
-            "INSERT INTO ng_words(user_id, livestream_id, word, created_at) VALUES (?, ?, ?, ?)",
+            "INSERT INTO ng_words(user_id, livestream_id, word, match_mode, created_at) VALUES (?, ?, ?, ?, ?)",
         )
         .bind(&ng_word.user_id)
         .bind(&ng_word.livestream_id)
         .bind(&ng_word.word)
+        .bind(ng_word.match_mode.as_str())
         .bind(ng_word.created_at)
         .execute(conn)
         .await?;
@@ -73,7 +74,7 @@ impl NgWordRepository for NgWordRepositoryInfra {
         user_id: &UserId,
     ) -> isupipe_core::repos::Result<Vec<NgWord>> {
         let ng_words: Vec<NgWord> =
-            sqlx::query_as("SELECT id, user_id, livestream_id, word FROM ng_words WHERE user_id = ? AND livestream_id = ?")
+            sqlx::query_as("SELECT id, user_id, livestream_id, word, match_mode FROM ng_words WHERE user_id = ? AND livestream_id = ?")
                 .bind(livestream_id)
                 .bind(user_id)
                 .fetch_all(conn)
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod find_by_token_hash;
+#[cfg(test)]
+mod find_latest_by_user_id;
+#[cfg(test)]
+mod mark_used;
+
+use async_trait::async_trait;
+use isupipe_core::db::DBConn;
+use isupipe_core::models::password_reset_token::{
+    CreatePasswordResetToken, PasswordResetToken, PasswordResetTokenId,
+};
+use isupipe_core::models::user::UserId;
+use isupipe_core::repos::password_reset_token_repository::PasswordResetTokenRepository;
+
+#[derive(Clone)]
+pub struct PasswordResetTokenRepositoryInfra {}
+
+#[async_trait]
+impl PasswordResetTokenRepository for PasswordResetTokenRepositoryInfra {
+    async fn create(
+        &self,
+        conn: &mut DBConn,
+        token: &CreatePasswordResetToken,
+    ) -> isupipe_core::repos::Result<PasswordResetTokenId> {
+        let result = sqlx::query(
+            "INSERT INTO password_reset_tokens (user_id, token_hash, expires_at, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&token.user_id)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .bind(token.created_at)
+        .execute(conn)
+        .await?;
+
+        Ok(PasswordResetTokenId::new(result.last_insert_id() as i64))
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        conn: &mut DBConn,
+        token_hash: &str,
+    ) -> isupipe_core::repos::Result<Option<PasswordResetToken>> {
+        let token = sqlx::query_as("SELECT * FROM password_reset_tokens WHERE token_hash = ?")
+            .bind(token_hash)
+            .fetch_optional(conn)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn mark_used(
+        &self,
+        conn: &mut DBConn,
+        id: &PasswordResetTokenId,
+        used_at: i64,
+    ) -> isupipe_core::repos::Result<bool> {
+        let result = sqlx::query(
+            "UPDATE password_reset_tokens SET used_at = ? WHERE id = ? AND used_at IS NULL",
+        )
+        .bind(used_at)
+        .bind(id)
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn find_latest_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+    ) -> isupipe_core::repos::Result<Option<PasswordResetToken>> {
+        let token = sqlx::query_as(
+            "SELECT * FROM password_reset_tokens WHERE user_id = ? ORDER BY id DESC LIMIT 1",
+        )
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn delete_unused_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = ? AND used_at IS NULL")
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::repos::password_reset_token_repository::PasswordResetTokenRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::password_reset_token::CreatePasswordResetToken;
+use isupipe_core::repos::password_reset_token_repository::PasswordResetTokenRepository;
+
+#[tokio::test]
+async fn found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = PasswordResetTokenRepositoryInfra {};
+    let token = CreatePasswordResetToken {
+        user_id: Faker.fake(),
+        token_hash: uuid::Uuid::new_v4().simple().to_string(),
+        expires_at: Faker.fake(),
+        created_at: Faker.fake(),
+    };
+    let id = repo.create(&mut tx, &token).await.unwrap();
+
+    let got = repo
+        .find_by_token_hash(&mut tx, &token.token_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.id, id);
+    assert_eq!(got.user_id, token.user_id);
+    assert_eq!(got.expires_at, token.expires_at);
+    assert_eq!(got.created_at, token.created_at);
+    assert_eq!(got.used_at, None);
+}
+
+#[tokio::test]
+async fn not_found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = PasswordResetTokenRepositoryInfra {};
+    let got = repo.find_by_token_hash(&mut tx, "not-exist").await.unwrap();
+    assert!(got.is_none());
+}
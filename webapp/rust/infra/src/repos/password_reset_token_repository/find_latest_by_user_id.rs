@@ -0,0 +1,36 @@
+use crate::repos::password_reset_token_repository::PasswordResetTokenRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::password_reset_token::CreatePasswordResetToken;
+use isupipe_core::models::user::UserId;
+use isupipe_core::repos::password_reset_token_repository::PasswordResetTokenRepository;
+
+#[tokio::test]
+async fn latest_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = PasswordResetTokenRepositoryInfra {};
+    let user_id: UserId = Faker.fake();
+    assert!(repo
+        .find_latest_by_user_id(&mut tx, &user_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let mut ids = Vec::new();
+    for created_at in [100, 200] {
+        let mut token: CreatePasswordResetToken = Faker.fake();
+        token.user_id = user_id.clone();
+        token.created_at = created_at;
+        ids.push(repo.create(&mut tx, &token).await.unwrap());
+    }
+
+    let got = repo
+        .find_latest_by_user_id(&mut tx, &user_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.id, ids[1]);
+    assert_eq!(got.created_at, 200);
+}
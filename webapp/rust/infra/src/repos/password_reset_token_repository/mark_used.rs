@@ -0,0 +1,25 @@
+use crate::repos::password_reset_token_repository::PasswordResetTokenRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::password_reset_token::CreatePasswordResetToken;
+use isupipe_core::repos::password_reset_token_repository::PasswordResetTokenRepository;
+
+#[tokio::test]
+async fn single_use_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = PasswordResetTokenRepositoryInfra {};
+    let token: CreatePasswordResetToken = Faker.fake();
+    let id = repo.create(&mut tx, &token).await.unwrap();
+
+    assert!(repo.mark_used(&mut tx, &id, 100).await.unwrap());
+    assert!(!repo.mark_used(&mut tx, &id, 200).await.unwrap());
+
+    let got = repo
+        .find_by_token_hash(&mut tx, &token.token_hash)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.used_at, Some(100));
+}
@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod create;
+#[cfg(test)]
+mod find_all_by_livestream_id_after_cursor;
+#[cfg(test)]
+mod find_all_by_livestream_id_before_cursor;
 
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
+use isupipe_core::models::cursor::Cursor;
 use isupipe_core::models::livestream::LivestreamId;
 use isupipe_core::models::reaction::{CreateReaction, Reaction, ReactionId};
 use isupipe_core::models::user::{UserId, UserName};
@@ -127,7 +132,7 @@ impl ReactionRepository for ReactionRepositoryInfra {
         limit: i64,
     ) -> isupipe_core::repos::Result<Vec<Reaction>> {
         let reaction_models: Vec<Reaction> = sqlx::query_as(
-            "SELECT * FROM reactions WHERE livestream_id = ? ORDER BY created_at DESC LIMIT ?",
+            "SELECT * FROM reactions WHERE livestream_id = ? ORDER BY created_at DESC, id DESC LIMIT ?",
         )
         .bind(livestream_id)
         .bind(limit)
@@ -136,4 +141,54 @@ impl ReactionRepository for ReactionRepositoryInfra {
 
         Ok(reaction_models)
     }
+
+    async fn find_all_by_livestream_id_before_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<Reaction>> {
+        let query = r#"
+        SELECT * FROM reactions
+        WHERE livestream_id = ? AND (created_at < ? OR (created_at = ? AND id < ?))
+        ORDER BY created_at DESC, id DESC
+        LIMIT ?
+        "#;
+        let reaction_models: Vec<Reaction> = sqlx::query_as(query)
+            .bind(livestream_id)
+            .bind(cursor.created_at)
+            .bind(cursor.created_at)
+            .bind(cursor.id)
+            .bind(limit)
+            .fetch_all(conn)
+            .await?;
+
+        Ok(reaction_models)
+    }
+
+    async fn find_all_by_livestream_id_after_cursor(
+        &self,
+        conn: &mut DBConn,
+        livestream_id: &LivestreamId,
+        cursor: &Cursor,
+        limit: i64,
+    ) -> isupipe_core::repos::Result<Vec<Reaction>> {
+        let query = r#"
+        SELECT * FROM reactions
+        WHERE livestream_id = ? AND (created_at > ? OR (created_at = ? AND id > ?))
+        ORDER BY created_at ASC, id ASC
+        LIMIT ?
+        "#;
+        let reaction_models: Vec<Reaction> = sqlx::query_as(query)
+            .bind(livestream_id)
+            .bind(cursor.created_at)
+            .bind(cursor.created_at)
+            .bind(cursor.id)
+            .bind(limit)
+            .fetch_all(conn)
+            .await?;
+
+        Ok(reaction_models)
+    }
 }
@@ -0,0 +1,46 @@
+use crate::repos::reaction_repository::ReactionRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::cursor::Cursor;
+use isupipe_core::models::livestream::LivestreamId;
+use isupipe_core::models::reaction::CreateReaction;
+use isupipe_core::repos::reaction_repository::ReactionRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = ReactionRepositoryInfra {};
+
+    // created_atが同じリアクションはidで順序が決まる
+    let livestream_id: LivestreamId = Faker.fake();
+    let mut reaction_ids = Vec::new();
+    for created_at in [100, 200, 200, 300] {
+        let mut reaction: CreateReaction = Faker.fake();
+        reaction.livestream_id = livestream_id.clone();
+        reaction.created_at = created_at;
+        reaction_ids.push(repo.create(&mut tx, &reaction).await.unwrap());
+    }
+
+    let cursor = Cursor {
+        created_at: 200,
+        id: *reaction_ids[2].inner(),
+    };
+    let got = repo
+        .find_all_by_livestream_id_before_cursor(&mut tx, &livestream_id, &cursor, 10)
+        .await
+        .unwrap();
+    let got_ids: Vec<_> = got.into_iter().map(|r| r.id).collect();
+    assert_eq!(
+        got_ids,
+        vec![reaction_ids[1].clone(), reaction_ids[0].clone()]
+    );
+
+    let got = repo
+        .find_all_by_livestream_id_before_cursor(&mut tx, &livestream_id, &cursor, 1)
+        .await
+        .unwrap();
+    let got_ids: Vec<_> = got.into_iter().map(|r| r.id).collect();
+    assert_eq!(got_ids, vec![reaction_ids[1].clone()]);
+}
@@ -4,6 +4,8 @@ mod find;
 mod find_all;
 #[cfg(test)]
 mod find_ids_by_name;
+#[cfg(test)]
+mod find_many;
 
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
@@ -31,6 +33,27 @@ impl TagRepository for TagRepositoryInfra {
         Ok(tag_models)
     }
 
+    async fn find_many(
+        &self,
+        conn: &mut DBConn,
+        ids: &[TagId],
+    ) -> isupipe_core::repos::Result<Vec<Tag>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder =
+            sqlx::query_builder::QueryBuilder::new("SELECT * FROM tags WHERE id IN (");
+        let mut separated = query_builder.separated(", ");
+        for id in ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let tag_models: Vec<Tag> = query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(tag_models)
+    }
+
     async fn find_ids_by_name(
         &self,
         conn: &mut DBConn,
@@ -0,0 +1,29 @@
+use crate::repos::tag_repository::TagRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::tag::Tag;
+use isupipe_core::repos::tag_repository::TagRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = TagRepositoryInfra {};
+    let tag: Tag = Faker.fake();
+
+    sqlx::query("INSERT INTO tags (id, name) VALUES (?, ?)")
+        .bind(&tag.id)
+        .bind(&tag.name)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let got = repo
+        .find_many(&mut tx, std::slice::from_ref(&tag.id))
+        .await
+        .unwrap();
+    assert_eq!(got.len(), 1);
+    assert_eq!(got[0].id, tag.id);
+    assert_eq!(got[0].name, tag.name);
+}
@@ -2,10 +2,14 @@
 mod create;
 #[cfg(test)]
 mod find_by_user_id;
+#[cfg(test)]
+mod find_many_by_user_ids;
+#[cfg(test)]
+mod update;
 
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
-use isupipe_core::models::theme::Theme;
+use isupipe_core::models::theme::{Theme, UpdateTheme};
 use isupipe_core::models::user::UserId;
 use isupipe_core::repos::theme_repository::ThemeRepository;
 
@@ -31,19 +35,67 @@ impl ThemeRepository for ThemeRepositoryInfra {
         Ok(())
     }
 
-    async fn find_by_user_id(
+    async fn update(
         &self,
         conn: &mut DBConn,
         user_id: &UserId,
-    ) -> isupipe_core::repos::Result<Theme> {
-        let theme_model: Theme = sqlx::query_as!(
-            Theme,
-            "SELECT id, user_id, dark_mode as `dark_mode:bool` FROM themes WHERE user_id = ?",
-            user_id
+        theme: &UpdateTheme,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query(
+            r#"
+UPDATE themes SET
+  dark_mode = COALESCE(?, dark_mode),
+  accent_color = COALESCE(?, accent_color),
+  background_image_url = IF(? IS NULL, background_image_url, NULLIF(?, '')),
+  font = COALESCE(?, font),
+  chat_color_scheme = COALESCE(?, chat_color_scheme)
+WHERE user_id = ?
+            "#,
         )
-        .fetch_one(conn)
+        .bind(theme.dark_mode)
+        .bind(&theme.accent_color)
+        .bind(&theme.background_image_url)
+        .bind(&theme.background_image_url)
+        .bind(&theme.font)
+        .bind(&theme.chat_color_scheme)
+        .bind(user_id)
+        .execute(conn)
         .await?;
 
+        Ok(())
+    }
+
+    async fn find_by_user_id(
+        &self,
+        conn: &mut DBConn,
+        user_id: &UserId,
+    ) -> isupipe_core::repos::Result<Theme> {
+        let theme_model: Theme = sqlx::query_as("SELECT * FROM themes WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(conn)
+            .await?;
+
         Ok(theme_model)
     }
+
+    async fn find_many_by_user_ids(
+        &self,
+        conn: &mut DBConn,
+        user_ids: &[UserId],
+    ) -> isupipe_core::repos::Result<Vec<Theme>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder =
+            sqlx::query_builder::QueryBuilder::new("SELECT * FROM themes WHERE user_id IN (");
+        let mut separated = query_builder.separated(", ");
+        for id in user_ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let theme_models: Vec<Theme> = query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(theme_models)
+    }
 }
@@ -1,7 +1,9 @@
 use crate::repos::theme_repository::ThemeRepositoryInfra;
 use fake::{Fake, Faker};
 use isupipe_core::db::get_db_pool;
-use isupipe_core::models::theme::Theme;
+use isupipe_core::models::theme::{
+    Theme, DEFAULT_ACCENT_COLOR, DEFAULT_CHAT_COLOR_SCHEME, DEFAULT_FONT,
+};
 use isupipe_core::repos::theme_repository::ThemeRepository;
 
 #[tokio::test]
@@ -24,4 +26,8 @@ async fn success_case() {
 
     assert_eq!(theme.user_id, got.user_id);
     assert_eq!(theme.dark_mode, got.dark_mode);
+    assert_eq!(got.accent_color, DEFAULT_ACCENT_COLOR);
+    assert_eq!(got.background_image_url, None);
+    assert_eq!(got.font, DEFAULT_FONT);
+    assert_eq!(got.chat_color_scheme, DEFAULT_CHAT_COLOR_SCHEME);
 }
@@ -0,0 +1,30 @@
+use crate::repos::theme_repository::ThemeRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::theme::Theme;
+use isupipe_core::repos::theme_repository::ThemeRepository;
+
+#[tokio::test]
+async fn success_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = ThemeRepositoryInfra {};
+    let theme: Theme = Faker.fake();
+
+    sqlx::query("INSERT INTO themes (id, user_id, dark_mode) VALUES (?, ?, ?)")
+        .bind(&theme.id)
+        .bind(&theme.user_id)
+        .bind(theme.dark_mode)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let got = repo
+        .find_many_by_user_ids(&mut tx, std::slice::from_ref(&theme.user_id))
+        .await
+        .unwrap();
+    assert_eq!(got.len(), 1);
+    assert_eq!(got[0].id, theme.id);
+    assert_eq!(got[0].dark_mode, theme.dark_mode);
+}
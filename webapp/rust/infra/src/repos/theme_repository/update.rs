@@ -0,0 +1,57 @@
+use crate::repos::theme_repository::ThemeRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::theme::{UpdateTheme, DEFAULT_FONT};
+use isupipe_core::models::user::UserId;
+use isupipe_core::repos::theme_repository::ThemeRepository;
+
+#[tokio::test]
+async fn updated_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let user_id: UserId = Faker.fake();
+    let repo = ThemeRepositoryInfra {};
+    repo.create(&mut tx, &user_id, false).await.unwrap();
+
+    let update = UpdateTheme {
+        dark_mode: Some(true),
+        accent_color: Some("#123abc".to_owned()),
+        background_image_url: Some("https://example.com/bg.png".to_owned()),
+        font: None,
+        chat_color_scheme: Some("high-contrast".to_owned()),
+    };
+    repo.update(&mut tx, &user_id, &update).await.unwrap();
+
+    let got = repo.find_by_user_id(&mut tx, &user_id).await.unwrap();
+    assert!(got.dark_mode);
+    assert_eq!(Some(got.accent_color), update.accent_color);
+    assert_eq!(got.background_image_url, update.background_image_url);
+    assert_eq!(got.font, DEFAULT_FONT);
+    assert_eq!(Some(got.chat_color_scheme), update.chat_color_scheme);
+}
+
+#[tokio::test]
+async fn clear_background_image_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let user_id: UserId = Faker.fake();
+    let repo = ThemeRepositoryInfra {};
+    repo.create(&mut tx, &user_id, false).await.unwrap();
+
+    let update = UpdateTheme {
+        background_image_url: Some("https://example.com/bg.png".to_owned()),
+        ..Default::default()
+    };
+    repo.update(&mut tx, &user_id, &update).await.unwrap();
+
+    let update = UpdateTheme {
+        background_image_url: Some(String::new()),
+        ..Default::default()
+    };
+    repo.update(&mut tx, &user_id, &update).await.unwrap();
+
+    let got = repo.find_by_user_id(&mut tx, &user_id).await.unwrap();
+    assert_eq!(got.background_image_url, None);
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod create;
 #[cfg(test)]
+mod exists_by_name_ignore_case;
+#[cfg(test)]
 mod find;
 #[cfg(test)]
 mod find_all;
@@ -8,10 +10,16 @@ mod find_all;
 mod find_by_name;
 #[cfg(test)]
 mod find_id_by_name;
+#[cfg(test)]
+mod find_many;
+#[cfg(test)]
+mod update;
+#[cfg(test)]
+mod update_password;
 
 use async_trait::async_trait;
 use isupipe_core::db::DBConn;
-use isupipe_core::models::user::{CreateUser, User, UserId};
+use isupipe_core::models::user::{CreateUser, UpdateUser, User, UserId};
 use isupipe_core::repos::user_repository::UserRepository;
 
 #[derive(Clone)]
@@ -23,15 +31,14 @@ impl UserRepository for UserRepositoryInfra {
         &self,
         conn: &mut DBConn,
         user: &CreateUser,
+        hashed_password: &str,
     ) -> isupipe_core::repos::Result<UserId> {
-        let hashed_password = self.hash_password(&user.password)?;
-
         let result = sqlx::query!(
             "INSERT INTO users (name, display_name, description, password) VALUES(?, ?, ?, ?)",
             &user.name,
             &user.display_name,
             &user.description,
-            &hashed_password,
+            hashed_password,
         )
         .execute(conn)
         .await?;
@@ -41,6 +48,39 @@ impl UserRepository for UserRepositoryInfra {
         Ok(UserId::new(user_id))
     }
 
+    async fn update(
+        &self,
+        conn: &mut DBConn,
+        id: &UserId,
+        user: &UpdateUser,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query(
+            "UPDATE users SET display_name = COALESCE(?, display_name), description = COALESCE(?, description) WHERE id = ?",
+        )
+        .bind(&user.display_name)
+        .bind(&user.description)
+        .bind(id)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_password(
+        &self,
+        conn: &mut DBConn,
+        id: &UserId,
+        hashed_password: &str,
+    ) -> isupipe_core::repos::Result<()> {
+        sqlx::query("UPDATE users SET password = ? WHERE id = ?")
+            .bind(hashed_password)
+            .bind(id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
     async fn find(
         &self,
         conn: &mut DBConn,
@@ -64,6 +104,28 @@ impl UserRepository for UserRepositoryInfra {
         Ok(users)
     }
 
+    async fn find_many(
+        &self,
+        conn: &mut DBConn,
+        ids: &[UserId],
+    ) -> isupipe_core::repos::Result<Vec<User>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query_builder = sqlx::query_builder::QueryBuilder::new(
+            "SELECT id, name, display_name, description, password FROM users WHERE id IN (",
+        );
+        let mut separated = query_builder.separated(", ");
+        for id in ids {
+            separated.push_bind(id);
+        }
+        separated.push_unseparated(")");
+        let users: Vec<User> = query_builder.build_query_as().fetch_all(conn).await?;
+
+        Ok(users)
+    }
+
     async fn find_id_by_name(
         &self,
         conn: &mut DBConn,
@@ -88,4 +150,18 @@ impl UserRepository for UserRepositoryInfra {
 
         Ok(user_model)
     }
+
+    async fn exists_by_name_ignore_case(
+        &self,
+        conn: &mut DBConn,
+        name: &str,
+    ) -> isupipe_core::repos::Result<bool> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE LOWER(name) = LOWER(?)")
+                .bind(name)
+                .fetch_one(conn)
+                .await?;
+
+        Ok(count > 0)
+    }
 }
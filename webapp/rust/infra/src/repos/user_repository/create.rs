@@ -13,7 +13,7 @@ async fn success_case() {
     let user: CreateUser = Faker.fake();
 
     let repo = UserRepositoryInfra {};
-    let user_id = repo.create(&mut tx, &user).await.unwrap();
+    let user_id = repo.create(&mut tx, &user, &user.password).await.unwrap();
 
     let conn = tx.acquire().await.unwrap();
     let got: User = sqlx::query_as("SELECT * FROM users where id = ?")
@@ -25,4 +25,5 @@ async fn success_case() {
     assert_eq!(got.name.inner(), &user.name);
     assert_eq!(got.description, Some(user.description));
     assert_eq!(got.display_name, Some(user.display_name));
+    assert_eq!(got.hashed_password, Some(user.password));
 }
@@ -0,0 +1,38 @@
+use crate::repos::user_repository::UserRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::user::CreateUser;
+use isupipe_core::repos::user_repository::UserRepository;
+
+#[tokio::test]
+async fn not_found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+    let name: String = Faker.fake();
+    assert!(!repo
+        .exists_by_name_ignore_case(&mut tx, &name)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+    let mut user: CreateUser = Faker.fake();
+    user.name = "CaseUser".to_owned();
+    repo.create(&mut tx, &user, &user.password).await.unwrap();
+
+    assert!(repo
+        .exists_by_name_ignore_case(&mut tx, "caseuser")
+        .await
+        .unwrap());
+    assert!(repo
+        .exists_by_name_ignore_case(&mut tx, "CASEUSER")
+        .await
+        .unwrap());
+}
@@ -0,0 +1,40 @@
+use crate::repos::user_repository::UserRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::user::{CreateUser, UserId};
+use isupipe_core::repos::user_repository::UserRepository;
+
+#[tokio::test]
+async fn found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+
+    let mut user_ids = Vec::new();
+    for _ in 0..3 {
+        let user: CreateUser = Faker.fake();
+        user_ids.push(repo.create(&mut tx, &user, &user.password).await.unwrap());
+    }
+
+    let mut got: Vec<UserId> = repo
+        .find_many(&mut tx, &user_ids[..2])
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|u| u.id)
+        .collect();
+    got.sort_by_key(|id| *id.inner());
+    assert_eq!(got, user_ids[..2]);
+}
+
+#[tokio::test]
+async fn empty_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+
+    let got = repo.find_many(&mut tx, &[]).await.unwrap();
+    assert!(got.is_empty());
+}
@@ -0,0 +1,45 @@
+use crate::repos::user_repository::UserRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::user::{CreateUser, UpdateUser};
+use isupipe_core::repos::user_repository::UserRepository;
+
+#[tokio::test]
+async fn updated_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+    let user: CreateUser = Faker.fake();
+    let user_id = repo.create(&mut tx, &user, &user.password).await.unwrap();
+
+    let update: UpdateUser = UpdateUser {
+        display_name: Some(Faker.fake()),
+        description: Some(Faker.fake()),
+    };
+    repo.update(&mut tx, &user_id, &update).await.unwrap();
+
+    let got = repo.find(&mut tx, &user_id).await.unwrap().unwrap();
+    assert_eq!(got.display_name, update.display_name);
+    assert_eq!(got.description, update.description);
+}
+
+#[tokio::test]
+async fn partial_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+    let user: CreateUser = Faker.fake();
+    let user_id = repo.create(&mut tx, &user, &user.password).await.unwrap();
+
+    let update = UpdateUser {
+        display_name: None,
+        description: Some(Faker.fake()),
+    };
+    repo.update(&mut tx, &user_id, &update).await.unwrap();
+
+    let got = repo.find(&mut tx, &user_id).await.unwrap().unwrap();
+    assert_eq!(got.display_name, Some(user.display_name));
+    assert_eq!(got.description, update.description);
+}
@@ -0,0 +1,24 @@
+use crate::repos::user_repository::UserRepositoryInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::user::CreateUser;
+use isupipe_core::repos::user_repository::UserRepository;
+
+#[tokio::test]
+async fn updated_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let mut tx = db_pool.begin().await.unwrap();
+
+    let repo = UserRepositoryInfra {};
+    let user: CreateUser = Faker.fake();
+    let user_id = repo.create(&mut tx, &user, &user.password).await.unwrap();
+
+    let new_password: String = Faker.fake();
+    let hashed_password = repo.hash_password(&new_password).unwrap();
+    repo.update_password(&mut tx, &user_id, &hashed_password)
+        .await
+        .unwrap();
+
+    let got = repo.find(&mut tx, &user_id).await.unwrap().unwrap();
+    assert_eq!(got.hashed_password, Some(hashed_password));
+}
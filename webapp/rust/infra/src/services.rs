@@ -1,3 +1,5 @@
+pub mod account_service;
+pub mod dns_record_service;
 pub mod icon_service;
 pub mod initialize_service;
 pub mod livestream_comment_report_service;
@@ -6,9 +8,12 @@ pub mod livestream_service;
 pub mod livestream_statistics_service;
 pub mod livestream_tag_service;
 pub mod livestream_viewers_history_service;
+pub mod login_throttle_service;
 pub mod manager;
 pub mod ng_word_service;
+pub mod password_service;
 pub mod reaction_service;
+pub mod session_service;
 pub mod tag_service;
 pub mod theme_service;
 pub mod user_service;
@@ -0,0 +1,95 @@
+use crate::caches::{CacheManagerInfra, IconHashCacheInfra, ThemeCacheInfra, UserCacheInfra};
+use crate::commands::pdnsutil_command::PDNSUtilCommandInfra;
+use crate::repos::account_repository::AccountRepositoryInfra;
+use crate::repos::user_repository::UserRepositoryInfra;
+use isupipe_core::caches::{HaveIconHashCache, HaveThemeCache, HaveUserCache};
+use isupipe_core::commands::pdnsutil_command::HavePDNSUtilCommand;
+use isupipe_core::db::{DBPool, HaveDBPool};
+use isupipe_core::repos::account_repository::HaveAccountRepository;
+use isupipe_core::repos::user_repository::HaveUserRepository;
+use isupipe_core::services::account_service::AccountServiceImpl;
+
+#[derive(Clone)]
+pub struct AccountServiceInfra {
+    db_pool: DBPool,
+    user_repo: UserRepositoryInfra,
+    account_repo: AccountRepositoryInfra,
+    pdnsutil_command: PDNSUtilCommandInfra,
+    user_cache: UserCacheInfra,
+    theme_cache: ThemeCacheInfra,
+    icon_hash_cache: IconHashCacheInfra,
+}
+
+impl AccountServiceInfra {
+    pub fn new(
+        db_pool: DBPool,
+        caches: CacheManagerInfra,
+        pdnsutil_command: PDNSUtilCommandInfra,
+    ) -> Self {
+        Self {
+            db_pool,
+            user_repo: UserRepositoryInfra {},
+            account_repo: AccountRepositoryInfra {},
+            pdnsutil_command,
+            user_cache: caches.user_cache,
+            theme_cache: caches.theme_cache,
+            icon_hash_cache: caches.icon_hash_cache,
+        }
+    }
+}
+
+impl HaveDBPool for AccountServiceInfra {
+    fn get_db_pool(&self) -> &DBPool {
+        &self.db_pool
+    }
+}
+
+impl HaveUserRepository for AccountServiceInfra {
+    type Repo = UserRepositoryInfra;
+
+    fn user_repo(&self) -> &Self::Repo {
+        &self.user_repo
+    }
+}
+
+impl HaveAccountRepository for AccountServiceInfra {
+    type Repo = AccountRepositoryInfra;
+
+    fn account_repo(&self) -> &Self::Repo {
+        &self.account_repo
+    }
+}
+
+impl HavePDNSUtilCommand for AccountServiceInfra {
+    type Command = PDNSUtilCommandInfra;
+
+    fn pdnsutil_command(&self) -> &Self::Command {
+        &self.pdnsutil_command
+    }
+}
+
+impl HaveUserCache for AccountServiceInfra {
+    type Cache = UserCacheInfra;
+
+    fn user_cache(&self) -> &Self::Cache {
+        &self.user_cache
+    }
+}
+
+impl HaveThemeCache for AccountServiceInfra {
+    type Cache = ThemeCacheInfra;
+
+    fn theme_cache(&self) -> &Self::Cache {
+        &self.theme_cache
+    }
+}
+
+impl HaveIconHashCache for AccountServiceInfra {
+    type Cache = IconHashCacheInfra;
+
+    fn icon_hash_cache(&self) -> &Self::Cache {
+        &self.icon_hash_cache
+    }
+}
+
+impl AccountServiceImpl for AccountServiceInfra {}
@@ -0,0 +1,72 @@
+use crate::commands::pdnsutil_command::PDNSUtilCommandInfra;
+use crate::repos::user_repository::UserRepositoryInfra;
+use isupipe_core::commands::pdnsutil_command::{
+    HavePDNSUtilCommand, HaveSubdomainRecordConfig, SubdomainRecordConfig,
+};
+use isupipe_core::db::{DBPool, HaveDBPool};
+use isupipe_core::repos::user_repository::HaveUserRepository;
+use isupipe_core::services::dns_record_service::DnsRecordServiceImpl;
+use isupipe_core::validation::username::{HaveUsernamePolicy, UsernamePolicy};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct DnsRecordServiceInfra {
+    db_pool: DBPool,
+    user_repo: UserRepositoryInfra,
+    pdnsutil_command: PDNSUtilCommandInfra,
+    subdomain_record: SubdomainRecordConfig,
+    username_policy: Arc<UsernamePolicy>,
+}
+
+impl DnsRecordServiceInfra {
+    pub fn new(
+        db_pool: DBPool,
+        pdnsutil_command: PDNSUtilCommandInfra,
+        subdomain_record: SubdomainRecordConfig,
+        username_policy: Arc<UsernamePolicy>,
+    ) -> Self {
+        Self {
+            db_pool,
+            user_repo: UserRepositoryInfra {},
+            pdnsutil_command,
+            subdomain_record,
+            username_policy,
+        }
+    }
+}
+
+impl HaveDBPool for DnsRecordServiceInfra {
+    fn get_db_pool(&self) -> &DBPool {
+        &self.db_pool
+    }
+}
+
+impl HaveUserRepository for DnsRecordServiceInfra {
+    type Repo = UserRepositoryInfra;
+
+    fn user_repo(&self) -> &Self::Repo {
+        &self.user_repo
+    }
+}
+
+impl HavePDNSUtilCommand for DnsRecordServiceInfra {
+    type Command = PDNSUtilCommandInfra;
+
+    fn pdnsutil_command(&self) -> &Self::Command {
+        &self.pdnsutil_command
+    }
+}
+
+impl HaveSubdomainRecordConfig for DnsRecordServiceInfra {
+    fn subdomain_record(&self) -> &SubdomainRecordConfig {
+        &self.subdomain_record
+    }
+}
+
+impl HaveUsernamePolicy for DnsRecordServiceInfra {
+    fn username_policy(&self) -> &UsernamePolicy {
+        &self.username_policy
+    }
+}
+
+impl DnsRecordServiceImpl for DnsRecordServiceInfra {}
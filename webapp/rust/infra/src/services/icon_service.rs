@@ -1,23 +1,35 @@
+use crate::caches::{CacheManagerInfra, IconHashCacheInfra};
 use crate::repos::icon_repository::IconRepositoryInfra;
 use crate::repos::user_repository::UserRepositoryInfra;
+use crate::storages::IconStorageInfra;
+use isupipe_core::caches::HaveIconHashCache;
 use isupipe_core::db::{DBPool, HaveDBPool};
 use isupipe_core::repos::icon_repository::HaveIconRepository;
 use isupipe_core::repos::user_repository::HaveUserRepository;
 use isupipe_core::services::icon_service::IconServiceImpl;
+use isupipe_core::storages::icon_storage::HaveIconStorage;
 
 #[derive(Clone)]
 pub struct IconServiceInfra {
     db_pool: DBPool,
     icon_repo: IconRepositoryInfra,
     user_repo: UserRepositoryInfra,
+    icon_hash_cache: IconHashCacheInfra,
+    icon_storage: Option<IconStorageInfra>,
 }
 
 impl IconServiceInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(
+        db_pool: DBPool,
+        caches: CacheManagerInfra,
+        icon_storage: Option<IconStorageInfra>,
+    ) -> Self {
         Self {
             db_pool,
             icon_repo: IconRepositoryInfra {},
             user_repo: UserRepositoryInfra {},
+            icon_hash_cache: caches.icon_hash_cache,
+            icon_storage,
         }
     }
 }
@@ -44,4 +56,20 @@ impl HaveUserRepository for IconServiceInfra {
     }
 }
 
+impl HaveIconHashCache for IconServiceInfra {
+    type Cache = IconHashCacheInfra;
+
+    fn icon_hash_cache(&self) -> &Self::Cache {
+        &self.icon_hash_cache
+    }
+}
+
+impl HaveIconStorage for IconServiceInfra {
+    type Storage = IconStorageInfra;
+
+    fn icon_storage(&self) -> Option<&Self::Storage> {
+        self.icon_storage.as_ref()
+    }
+}
+
 impl IconServiceImpl for IconServiceInfra {}
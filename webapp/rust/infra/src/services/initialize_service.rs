@@ -1,16 +1,33 @@
+use crate::caches::{
+    CacheManagerInfra, IconHashCacheInfra, NgWordMatcherCacheInfra, TagCacheInfra, ThemeCacheInfra,
+    UserCacheInfra,
+};
 use crate::commands::initialize_command::InitializeCommandInfra;
+use isupipe_core::caches::{
+    HaveIconHashCache, HaveNgWordMatcherCache, HaveTagCache, HaveThemeCache, HaveUserCache,
+};
 use isupipe_core::commands::initialize_command::HaveInitializeCommand;
 use isupipe_core::services::initialize_service::InitializeServiceImpl;
 
 #[derive(Clone)]
 pub struct InitializeServiceInfra {
     initialize_command: InitializeCommandInfra,
+    user_cache: UserCacheInfra,
+    theme_cache: ThemeCacheInfra,
+    tag_cache: TagCacheInfra,
+    icon_hash_cache: IconHashCacheInfra,
+    ng_word_matcher_cache: NgWordMatcherCacheInfra,
 }
 
 impl InitializeServiceInfra {
-    pub fn new() -> Self {
+    pub fn new(caches: CacheManagerInfra) -> Self {
         Self {
             initialize_command: InitializeCommandInfra {},
+            user_cache: caches.user_cache,
+            theme_cache: caches.theme_cache,
+            tag_cache: caches.tag_cache,
+            icon_hash_cache: caches.icon_hash_cache,
+            ng_word_matcher_cache: caches.ng_word_matcher_cache,
         }
     }
 }
@@ -23,4 +40,44 @@ impl HaveInitializeCommand for InitializeServiceInfra {
     }
 }
 
+impl HaveUserCache for InitializeServiceInfra {
+    type Cache = UserCacheInfra;
+
+    fn user_cache(&self) -> &Self::Cache {
+        &self.user_cache
+    }
+}
+
+impl HaveThemeCache for InitializeServiceInfra {
+    type Cache = ThemeCacheInfra;
+
+    fn theme_cache(&self) -> &Self::Cache {
+        &self.theme_cache
+    }
+}
+
+impl HaveTagCache for InitializeServiceInfra {
+    type Cache = TagCacheInfra;
+
+    fn tag_cache(&self) -> &Self::Cache {
+        &self.tag_cache
+    }
+}
+
+impl HaveIconHashCache for InitializeServiceInfra {
+    type Cache = IconHashCacheInfra;
+
+    fn icon_hash_cache(&self) -> &Self::Cache {
+        &self.icon_hash_cache
+    }
+}
+
+impl HaveNgWordMatcherCache for InitializeServiceInfra {
+    type Cache = NgWordMatcherCacheInfra;
+
+    fn ng_word_matcher_cache(&self) -> &Self::Cache {
+        &self.ng_word_matcher_cache
+    }
+}
+
 impl InitializeServiceImpl for InitializeServiceInfra {}
@@ -1,5 +1,7 @@
+use crate::caches::{CacheManagerInfra, NgWordMatcherCacheInfra};
 use crate::repos::livestream_comment_repository::LivestreamCommentRepositoryInfra;
 use crate::repos::ng_word_repository::NgWordRepositoryInfra;
+use isupipe_core::caches::HaveNgWordMatcherCache;
 use isupipe_core::db::{DBPool, HaveDBPool};
 use isupipe_core::repos::livestream_comment_repository::HaveLivestreamCommentRepository;
 use isupipe_core::repos::ng_word_repository::HaveNgWordRepository;
@@ -10,14 +12,16 @@ pub struct LivestreamCommentServiceInfra {
     db_pool: DBPool,
     livestream_comment_repo: LivestreamCommentRepositoryInfra,
     ng_word_repo: NgWordRepositoryInfra,
+    ng_word_matcher_cache: NgWordMatcherCacheInfra,
 }
 
 impl LivestreamCommentServiceInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(db_pool: DBPool, caches: CacheManagerInfra) -> Self {
         Self {
             db_pool,
             livestream_comment_repo: LivestreamCommentRepositoryInfra {},
             ng_word_repo: NgWordRepositoryInfra {},
+            ng_word_matcher_cache: caches.ng_word_matcher_cache,
         }
     }
 }
@@ -44,4 +48,12 @@ impl HaveNgWordRepository for LivestreamCommentServiceInfra {
     }
 }
 
+impl HaveNgWordMatcherCache for LivestreamCommentServiceInfra {
+    type Cache = NgWordMatcherCacheInfra;
+
+    fn ng_word_matcher_cache(&self) -> &Self::Cache {
+        &self.ng_word_matcher_cache
+    }
+}
+
 impl LivestreamCommentServiceImpl for LivestreamCommentServiceInfra {}
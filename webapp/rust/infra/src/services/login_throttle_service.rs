@@ -0,0 +1,27 @@
+use crate::storages::mysql_login_attempt_store::MySqlLoginAttemptStoreInfra;
+use isupipe_core::db::DBPool;
+use isupipe_core::services::login_throttle_service::LoginThrottleServiceImpl;
+use isupipe_core::storages::login_attempt_store::HaveLoginAttemptStore;
+
+#[derive(Clone)]
+pub struct LoginThrottleServiceInfra {
+    login_attempt_store: MySqlLoginAttemptStoreInfra,
+}
+
+impl LoginThrottleServiceInfra {
+    pub fn new(db_pool: DBPool) -> Self {
+        Self {
+            login_attempt_store: MySqlLoginAttemptStoreInfra::new(db_pool),
+        }
+    }
+}
+
+impl HaveLoginAttemptStore for LoginThrottleServiceInfra {
+    type Store = MySqlLoginAttemptStoreInfra;
+
+    fn login_attempt_store(&self) -> &Self::Store {
+        &self.login_attempt_store
+    }
+}
+
+impl LoginThrottleServiceImpl for LoginThrottleServiceInfra {}
@@ -1,3 +1,7 @@
+use crate::caches::CacheManagerInfra;
+use crate::commands::pdnsutil_command::PDNSUtilCommandInfra;
+use crate::notifiers::PasswordResetNotifierInfra;
+use crate::services::account_service::AccountServiceInfra;
 use crate::services::icon_service::IconServiceInfra;
 use crate::services::initialize_service::InitializeServiceInfra;
 use crate::services::livestream_comment_report_service::LivestreamCommentReportServiceInfra;
@@ -6,13 +10,18 @@ use crate::services::livestream_service::LivestreamServiceInfra;
 use crate::services::livestream_statistics_service::LivestreamStatisticsServiceInfra;
 use crate::services::livestream_tag_service::LivestreamTagServiceInfra;
 use crate::services::livestream_viewers_history_service::LivestreamViewersHistoryServiceInfra;
+use crate::services::login_throttle_service::LoginThrottleServiceInfra;
 use crate::services::ng_word_service::NgWordServiceInfra;
+use crate::services::password_service::PasswordServiceInfra;
 use crate::services::reaction_service::ReactionServiceInfra;
+use crate::services::session_service::SessionServiceInfra;
 use crate::services::tag_service::TagServiceInfra;
 use crate::services::theme_service::ThemeServiceInfra;
 use crate::services::user_service::UserServiceInfra;
 use crate::services::user_statistics_service::UserStatisticsServiceInfra;
+use crate::storages::IconStorageInfra;
 use isupipe_core::db::DBPool;
+use isupipe_core::services::account_service::HaveAccountService;
 use isupipe_core::services::icon_service::HaveIconService;
 use isupipe_core::services::initialize_service::HaveInitializeService;
 use isupipe_core::services::livestream_comment_report_service::HaveLivestreamCommentReportService;
@@ -21,16 +30,22 @@ use isupipe_core::services::livestream_service::HaveLivestreamService;
 use isupipe_core::services::livestream_statistics_service::HaveLivestreamStatisticsService;
 use isupipe_core::services::livestream_tag_service::HaveLivestreamTagService;
 use isupipe_core::services::livestream_viewers_history_service::HaveLivestreamViewersHistoryService;
+use isupipe_core::services::login_throttle_service::HaveLoginThrottleService;
 use isupipe_core::services::manager::ServiceManager;
 use isupipe_core::services::ng_word_service::HaveNgWordService;
+use isupipe_core::services::password_service::HavePasswordService;
 use isupipe_core::services::reaction_service::HaveReactionService;
+use isupipe_core::services::session_service::HaveSessionService;
 use isupipe_core::services::tag_service::HaveTagService;
 use isupipe_core::services::theme_service::HaveThemeService;
 use isupipe_core::services::user_service::HaveUserService;
 use isupipe_core::services::user_statistics_service::HaveUserStatisticsService;
+use isupipe_core::validation::username::UsernamePolicy;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct ServiceManagerInfra {
+    account_service: AccountServiceInfra,
     icon_service: IconServiceInfra,
     initialize_service: InitializeServiceInfra,
     livestream_comment_report_service: LivestreamCommentReportServiceInfra,
@@ -39,8 +54,11 @@ pub struct ServiceManagerInfra {
     livestream_statistics_service: LivestreamStatisticsServiceInfra,
     livestream_tag_service: LivestreamTagServiceInfra,
     livestream_viewers_history_service: LivestreamViewersHistoryServiceInfra,
+    login_throttle_service: LoginThrottleServiceInfra,
     ng_word_service: NgWordServiceInfra,
+    password_service: PasswordServiceInfra,
     reaction_service: ReactionServiceInfra,
+    session_service: SessionServiceInfra,
     tag_service: TagServiceInfra,
     theme_service: ThemeServiceInfra,
     user_service: UserServiceInfra,
@@ -48,11 +66,26 @@ pub struct ServiceManagerInfra {
 }
 
 impl ServiceManagerInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(
+        db_pool: DBPool,
+        icon_storage: Option<IconStorageInfra>,
+        password_reset_notifier: PasswordResetNotifierInfra,
+        pdnsutil_command: PDNSUtilCommandInfra,
+        username_policy: UsernamePolicy,
+    ) -> Self {
+        let caches = CacheManagerInfra::new();
         Self {
-            icon_service: IconServiceInfra::new(db_pool.clone()),
-            initialize_service: InitializeServiceInfra::new(),
-            livestream_comment_service: LivestreamCommentServiceInfra::new(db_pool.clone()),
+            account_service: AccountServiceInfra::new(
+                db_pool.clone(),
+                caches.clone(),
+                pdnsutil_command.clone(),
+            ),
+            icon_service: IconServiceInfra::new(db_pool.clone(), caches.clone(), icon_storage),
+            initialize_service: InitializeServiceInfra::new(caches.clone()),
+            livestream_comment_service: LivestreamCommentServiceInfra::new(
+                db_pool.clone(),
+                caches.clone(),
+            ),
             livestream_comment_report_service: LivestreamCommentReportServiceInfra::new(
                 db_pool.clone(),
             ),
@@ -62,11 +95,23 @@ impl ServiceManagerInfra {
             livestream_viewers_history_service: LivestreamViewersHistoryServiceInfra::new(
                 db_pool.clone(),
             ),
-            ng_word_service: NgWordServiceInfra::new(db_pool.clone()),
+            login_throttle_service: LoginThrottleServiceInfra::new(db_pool.clone()),
+            ng_word_service: NgWordServiceInfra::new(db_pool.clone(), caches.clone()),
+            password_service: PasswordServiceInfra::new(
+                db_pool.clone(),
+                caches.clone(),
+                password_reset_notifier,
+            ),
             reaction_service: ReactionServiceInfra::new(db_pool.clone()),
-            tag_service: TagServiceInfra::new(db_pool.clone()),
-            theme_service: ThemeServiceInfra::new(db_pool.clone()),
-            user_service: UserServiceInfra::new(db_pool.clone()),
+            session_service: SessionServiceInfra::new(db_pool.clone()),
+            tag_service: TagServiceInfra::new(db_pool.clone(), caches.clone()),
+            theme_service: ThemeServiceInfra::new(db_pool.clone(), caches.clone()),
+            user_service: UserServiceInfra::new(
+                db_pool.clone(),
+                caches.clone(),
+                pdnsutil_command,
+                Arc::new(username_policy),
+            ),
             user_statistics_service: UserStatisticsServiceInfra::new(db_pool.clone()),
         }
     }
@@ -184,4 +229,36 @@ impl HaveLivestreamStatisticsService for ServiceManagerInfra {
     }
 }
 
+impl HaveSessionService for ServiceManagerInfra {
+    type Service = SessionServiceInfra;
+
+    fn session_service(&self) -> &Self::Service {
+        &self.session_service
+    }
+}
+
+impl HaveLoginThrottleService for ServiceManagerInfra {
+    type Service = LoginThrottleServiceInfra;
+
+    fn login_throttle_service(&self) -> &Self::Service {
+        &self.login_throttle_service
+    }
+}
+
+impl HavePasswordService for ServiceManagerInfra {
+    type Service = PasswordServiceInfra;
+
+    fn password_service(&self) -> &Self::Service {
+        &self.password_service
+    }
+}
+
+impl HaveAccountService for ServiceManagerInfra {
+    type Service = AccountServiceInfra;
+
+    fn account_service(&self) -> &Self::Service {
+        &self.account_service
+    }
+}
+
 impl ServiceManager for ServiceManagerInfra {}
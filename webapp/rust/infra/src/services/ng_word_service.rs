@@ -1,23 +1,32 @@
+use crate::caches::{CacheManagerInfra, NgWordMatcherCacheInfra};
 use crate::repos::livestream_comment_repository::LivestreamCommentRepositoryInfra;
 use crate::repos::ng_word_repository::NgWordRepositoryInfra;
+use isupipe_core::caches::HaveNgWordMatcherCache;
 use isupipe_core::db::{DBPool, HaveDBPool};
 use isupipe_core::repos::livestream_comment_repository::HaveLivestreamCommentRepository;
 use isupipe_core::repos::ng_word_repository::HaveNgWordRepository;
 use isupipe_core::services::ng_word_service::NgWordServiceImpl;
+use isupipe_core::storages::ng_word_purge_job_store::{
+    HaveNgWordPurgeJobStore, InMemoryNgWordPurgeJobStore,
+};
 
 #[derive(Clone)]
 pub struct NgWordServiceInfra {
     db_pool: DBPool,
     ng_word_repo: NgWordRepositoryInfra,
     livestream_comment_repo: LivestreamCommentRepositoryInfra,
+    ng_word_matcher_cache: NgWordMatcherCacheInfra,
+    ng_word_purge_job_store: InMemoryNgWordPurgeJobStore,
 }
 
 impl NgWordServiceInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(db_pool: DBPool, caches: CacheManagerInfra) -> Self {
         Self {
             db_pool,
             ng_word_repo: NgWordRepositoryInfra {},
             livestream_comment_repo: LivestreamCommentRepositoryInfra {},
+            ng_word_matcher_cache: caches.ng_word_matcher_cache,
+            ng_word_purge_job_store: InMemoryNgWordPurgeJobStore::default(),
         }
     }
 }
@@ -44,4 +53,20 @@ impl HaveLivestreamCommentRepository for NgWordServiceInfra {
     }
 }
 
+impl HaveNgWordMatcherCache for NgWordServiceInfra {
+    type Cache = NgWordMatcherCacheInfra;
+
+    fn ng_word_matcher_cache(&self) -> &Self::Cache {
+        &self.ng_word_matcher_cache
+    }
+}
+
+impl HaveNgWordPurgeJobStore for NgWordServiceInfra {
+    type Store = InMemoryNgWordPurgeJobStore;
+
+    fn ng_word_purge_job_store(&self) -> &Self::Store {
+        &self.ng_word_purge_job_store
+    }
+}
+
 impl NgWordServiceImpl for NgWordServiceInfra {}
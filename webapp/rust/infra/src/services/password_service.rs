@@ -0,0 +1,87 @@
+use crate::caches::{CacheManagerInfra, UserCacheInfra};
+use crate::notifiers::PasswordResetNotifierInfra;
+use crate::repos::password_reset_token_repository::PasswordResetTokenRepositoryInfra;
+use crate::repos::user_repository::UserRepositoryInfra;
+use crate::storages::mysql_session_store::MySqlSessionStoreInfra;
+use isupipe_core::caches::HaveUserCache;
+use isupipe_core::db::{DBPool, HaveDBPool};
+use isupipe_core::notifiers::password_reset_notifier::HavePasswordResetNotifier;
+use isupipe_core::repos::password_reset_token_repository::HavePasswordResetTokenRepository;
+use isupipe_core::repos::user_repository::HaveUserRepository;
+use isupipe_core::services::password_service::PasswordServiceImpl;
+use isupipe_core::storages::session_store::HaveSessionStore;
+
+#[derive(Clone)]
+pub struct PasswordServiceInfra {
+    db_pool: DBPool,
+    user_repo: UserRepositoryInfra,
+    password_reset_token_repo: PasswordResetTokenRepositoryInfra,
+    password_reset_notifier: PasswordResetNotifierInfra,
+    user_cache: UserCacheInfra,
+    session_store: MySqlSessionStoreInfra,
+}
+
+impl PasswordServiceInfra {
+    pub fn new(
+        db_pool: DBPool,
+        caches: CacheManagerInfra,
+        password_reset_notifier: PasswordResetNotifierInfra,
+    ) -> Self {
+        Self {
+            session_store: MySqlSessionStoreInfra::new(db_pool.clone()),
+            db_pool,
+            user_repo: UserRepositoryInfra {},
+            password_reset_token_repo: PasswordResetTokenRepositoryInfra {},
+            password_reset_notifier,
+            user_cache: caches.user_cache,
+        }
+    }
+}
+
+impl HaveDBPool for PasswordServiceInfra {
+    fn get_db_pool(&self) -> &DBPool {
+        &self.db_pool
+    }
+}
+
+impl HaveUserRepository for PasswordServiceInfra {
+    type Repo = UserRepositoryInfra;
+
+    fn user_repo(&self) -> &Self::Repo {
+        &self.user_repo
+    }
+}
+
+impl HavePasswordResetTokenRepository for PasswordServiceInfra {
+    type Repo = PasswordResetTokenRepositoryInfra;
+
+    fn password_reset_token_repo(&self) -> &Self::Repo {
+        &self.password_reset_token_repo
+    }
+}
+
+impl HavePasswordResetNotifier for PasswordServiceInfra {
+    type Notifier = PasswordResetNotifierInfra;
+
+    fn password_reset_notifier(&self) -> &Self::Notifier {
+        &self.password_reset_notifier
+    }
+}
+
+impl HaveUserCache for PasswordServiceInfra {
+    type Cache = UserCacheInfra;
+
+    fn user_cache(&self) -> &Self::Cache {
+        &self.user_cache
+    }
+}
+
+impl HaveSessionStore for PasswordServiceInfra {
+    type Store = MySqlSessionStoreInfra;
+
+    fn session_store(&self) -> &Self::Store {
+        &self.session_store
+    }
+}
+
+impl PasswordServiceImpl for PasswordServiceInfra {}
@@ -0,0 +1,27 @@
+use crate::storages::mysql_session_store::MySqlSessionStoreInfra;
+use isupipe_core::db::DBPool;
+use isupipe_core::services::session_service::SessionServiceImpl;
+use isupipe_core::storages::session_store::HaveSessionStore;
+
+#[derive(Clone)]
+pub struct SessionServiceInfra {
+    session_store: MySqlSessionStoreInfra,
+}
+
+impl SessionServiceInfra {
+    pub fn new(db_pool: DBPool) -> Self {
+        Self {
+            session_store: MySqlSessionStoreInfra::new(db_pool),
+        }
+    }
+}
+
+impl HaveSessionStore for SessionServiceInfra {
+    type Store = MySqlSessionStoreInfra;
+
+    fn session_store(&self) -> &Self::Store {
+        &self.session_store
+    }
+}
+
+impl SessionServiceImpl for SessionServiceInfra {}
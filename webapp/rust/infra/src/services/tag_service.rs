@@ -1,4 +1,6 @@
+use crate::caches::{CacheManagerInfra, TagCacheInfra};
 use crate::repos::tag_repository::TagRepositoryInfra;
+use isupipe_core::caches::HaveTagCache;
 use isupipe_core::db::{DBPool, HaveDBPool};
 use isupipe_core::repos::tag_repository::HaveTagRepository;
 use isupipe_core::services::tag_service::TagServiceImpl;
@@ -7,13 +9,15 @@ use isupipe_core::services::tag_service::TagServiceImpl;
 pub struct TagServiceInfra {
     db_pool: DBPool,
     tag_repo: TagRepositoryInfra,
+    tag_cache: TagCacheInfra,
 }
 
 impl TagServiceInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(db_pool: DBPool, caches: CacheManagerInfra) -> Self {
         Self {
             db_pool,
             tag_repo: TagRepositoryInfra {},
+            tag_cache: caches.tag_cache,
         }
     }
 }
@@ -32,4 +36,12 @@ impl HaveTagRepository for TagServiceInfra {
     }
 }
 
+impl HaveTagCache for TagServiceInfra {
+    type Cache = TagCacheInfra;
+
+    fn tag_cache(&self) -> &Self::Cache {
+        &self.tag_cache
+    }
+}
+
 impl TagServiceImpl for TagServiceInfra {}
@@ -1,4 +1,6 @@
+use crate::caches::{CacheManagerInfra, ThemeCacheInfra};
 use crate::repos::theme_repository::ThemeRepositoryInfra;
+use isupipe_core::caches::HaveThemeCache;
 use isupipe_core::db::{DBPool, HaveDBPool};
 use isupipe_core::repos::theme_repository::HaveThemeRepository;
 use isupipe_core::services::theme_service::ThemeServiceImpl;
@@ -7,13 +9,15 @@ use isupipe_core::services::theme_service::ThemeServiceImpl;
 pub struct ThemeServiceInfra {
     db_pool: DBPool,
     theme_repo: ThemeRepositoryInfra,
+    theme_cache: ThemeCacheInfra,
 }
 
 impl ThemeServiceInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(db_pool: DBPool, caches: CacheManagerInfra) -> Self {
         Self {
             db_pool,
             theme_repo: ThemeRepositoryInfra {},
+            theme_cache: caches.theme_cache,
         }
     }
 }
@@ -32,4 +36,12 @@ impl HaveThemeRepository for ThemeServiceInfra {
     }
 }
 
+impl HaveThemeCache for ThemeServiceInfra {
+    type Cache = ThemeCacheInfra;
+
+    fn theme_cache(&self) -> &Self::Cache {
+        &self.theme_cache
+    }
+}
+
 impl ThemeServiceImpl for ThemeServiceInfra {}
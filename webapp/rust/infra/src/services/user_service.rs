@@ -1,11 +1,15 @@
+use crate::caches::{CacheManagerInfra, IconHashCacheInfra, ThemeCacheInfra, UserCacheInfra};
 use crate::commands::pdnsutil_command::PDNSUtilCommandInfra;
 use crate::repos::theme_repository::ThemeRepositoryInfra;
 use crate::repos::user_repository::UserRepositoryInfra;
+use isupipe_core::caches::{HaveIconHashCache, HaveThemeCache, HaveUserCache};
 use isupipe_core::commands::pdnsutil_command::HavePDNSUtilCommand;
 use isupipe_core::db::{DBPool, HaveDBPool};
 use isupipe_core::repos::theme_repository::HaveThemeRepository;
 use isupipe_core::repos::user_repository::HaveUserRepository;
 use isupipe_core::services::user_service::UserServiceImpl;
+use isupipe_core::validation::username::{HaveUsernamePolicy, UsernamePolicy};
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct UserServiceInfra {
@@ -13,15 +17,28 @@ pub struct UserServiceInfra {
     user_repo: UserRepositoryInfra,
     theme_repo: ThemeRepositoryInfra,
     pdnsutil_command: PDNSUtilCommandInfra,
+    user_cache: UserCacheInfra,
+    theme_cache: ThemeCacheInfra,
+    icon_hash_cache: IconHashCacheInfra,
+    username_policy: Arc<UsernamePolicy>,
 }
 
 impl UserServiceInfra {
-    pub fn new(db_pool: DBPool) -> Self {
+    pub fn new(
+        db_pool: DBPool,
+        caches: CacheManagerInfra,
+        pdnsutil_command: PDNSUtilCommandInfra,
+        username_policy: Arc<UsernamePolicy>,
+    ) -> Self {
         Self {
             db_pool,
             user_repo: UserRepositoryInfra {},
             theme_repo: ThemeRepositoryInfra {},
-            pdnsutil_command: PDNSUtilCommandInfra {},
+            pdnsutil_command,
+            user_cache: caches.user_cache,
+            theme_cache: caches.theme_cache,
+            icon_hash_cache: caches.icon_hash_cache,
+            username_policy,
         }
     }
 }
@@ -56,4 +73,34 @@ impl HavePDNSUtilCommand for UserServiceInfra {
     }
 }
 
+impl HaveUserCache for UserServiceInfra {
+    type Cache = UserCacheInfra;
+
+    fn user_cache(&self) -> &Self::Cache {
+        &self.user_cache
+    }
+}
+
+impl HaveThemeCache for UserServiceInfra {
+    type Cache = ThemeCacheInfra;
+
+    fn theme_cache(&self) -> &Self::Cache {
+        &self.theme_cache
+    }
+}
+
+impl HaveIconHashCache for UserServiceInfra {
+    type Cache = IconHashCacheInfra;
+
+    fn icon_hash_cache(&self) -> &Self::Cache {
+        &self.icon_hash_cache
+    }
+}
+
+impl HaveUsernamePolicy for UserServiceInfra {
+    fn username_policy(&self) -> &UsernamePolicy {
+        &self.username_policy
+    }
+}
+
 impl UserServiceImpl for UserServiceInfra {}
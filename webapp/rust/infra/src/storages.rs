@@ -0,0 +1,71 @@
+use crate::storages::filesystem_icon_storage::FilesystemIconStorageInfra;
+use crate::storages::s3_icon_storage::{S3Config, S3IconStorageInfra};
+use async_trait::async_trait;
+use isupipe_core::storages::icon_storage::IconStorage;
+use isupipe_core::storages::{StorageError, StorageResult};
+
+pub mod filesystem_icon_storage;
+pub mod mysql_login_attempt_store;
+pub mod mysql_session_store;
+pub mod s3_icon_storage;
+
+const ICON_STORAGE_ENV_KEY: &str = "ISUCON13_ICON_STORAGE";
+const ICON_STORAGE_DIR_ENV_KEY: &str = "ISUCON13_ICON_STORAGE_DIR";
+const DEFAULT_ICON_STORAGE_DIR: &str = "../icons";
+
+/// 設定で選択するアイコン画像の保存先
+#[derive(Clone)]
+pub enum IconStorageInfra {
+    Filesystem(FilesystemIconStorageInfra),
+    S3(Box<S3IconStorageInfra>),
+}
+
+impl IconStorageInfra {
+    /// 環境変数から保存先を組み立てる。mysql (デフォルト) の場合は None
+    pub fn from_env() -> StorageResult<Option<Self>> {
+        let kind = std::env::var(ICON_STORAGE_ENV_KEY).unwrap_or_default();
+        match kind.as_str() {
+            "" | "mysql" => Ok(None),
+            "filesystem" => {
+                let dir = std::env::var(ICON_STORAGE_DIR_ENV_KEY)
+                    .unwrap_or_else(|_| DEFAULT_ICON_STORAGE_DIR.to_owned());
+                Ok(Some(Self::Filesystem(FilesystemIconStorageInfra::new(dir))))
+            }
+            "s3" => {
+                let storage = S3IconStorageInfra::new(S3Config::from_env()?)?;
+                Ok(Some(Self::S3(Box::new(storage))))
+            }
+            _ => Err(StorageError::ConfigError(format!(
+                "{} must be one of mysql, filesystem, s3: {}",
+                ICON_STORAGE_ENV_KEY, kind
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl IconStorage for IconStorageInfra {
+    async fn put(&self, key: &str, image: &[u8]) -> StorageResult<()> {
+        match self {
+            Self::Filesystem(storage) => storage.put(key, image).await,
+            Self::S3(storage) => storage.put(key, image).await,
+        }
+    }
+
+    async fn get(&self, key: &str) -> StorageResult<Option<Vec<u8>>> {
+        match self {
+            Self::Filesystem(storage) => storage.get(key).await,
+            Self::S3(storage) => storage.get(key).await,
+        }
+    }
+}
+
+/// キーはSHA-256の16進表記に限る。パスやURLにそのまま埋め込むため
+fn validate_key(key: &str) -> StorageResult<()> {
+    let valid = key.len() == 64 && key.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_owned()))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::storages::validate_key;
+use async_trait::async_trait;
+use isupipe_core::storages::icon_storage::IconStorage;
+use isupipe_core::storages::StorageResult;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(test)]
+mod get;
+#[cfg(test)]
+mod put;
+
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// ローカルのディレクトリに画像を保存する
+/// ファイル名は画像のハッシュで、ディレクトリが肥大化しないよう先頭2文字で分ける
+#[derive(Clone)]
+pub struct FilesystemIconStorageInfra {
+    root: PathBuf,
+}
+
+impl FilesystemIconStorageInfra {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(&key[..2]).join(key)
+    }
+}
+
+#[async_trait]
+impl IconStorage for FilesystemIconStorageInfra {
+    async fn put(&self, key: &str, image: &[u8]) -> StorageResult<()> {
+        validate_key(key)?;
+        let path = self.path(key);
+        // 同じキーなら同じ内容なので書き直さない
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(());
+        }
+
+        let dir = path.parent().expect("icon path always has a parent");
+        tokio::fs::create_dir_all(dir).await?;
+
+        // 書きかけのファイルを読まれないよう、一時ファイルに書いてから rename する
+        let tmp_path = dir.join(format!(
+            "{}.{}.{}.tmp",
+            key,
+            std::process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::write(&tmp_path, image).await?;
+        if let Err(e) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> StorageResult<Option<Vec<u8>>> {
+        validate_key(key)?;
+        match tokio::fs::read(self.path(key)).await {
+            Ok(image) => Ok(Some(image)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::storages::filesystem_icon_storage::FilesystemIconStorageInfra;
+use isupipe_core::services::icon_service::icon_hash;
+use isupipe_core::storages::icon_storage::IconStorage;
+
+#[tokio::test]
+async fn get() {
+    let root = std::env::temp_dir().join(format!("isupipe-icons-get-{}", std::process::id()));
+    let storage = FilesystemIconStorageInfra::new(&root);
+    let image = b"icon image".to_vec();
+    let key = icon_hash(&image);
+
+    assert_eq!(storage.get(&key).await.unwrap(), None);
+
+    storage.put(&key, &image).await.unwrap();
+    assert_eq!(storage.get(&key).await.unwrap(), Some(image));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
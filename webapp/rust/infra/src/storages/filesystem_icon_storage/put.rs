@@ -0,0 +1,31 @@
+use crate::storages::filesystem_icon_storage::FilesystemIconStorageInfra;
+use isupipe_core::services::icon_service::icon_hash;
+use isupipe_core::storages::icon_storage::IconStorage;
+use isupipe_core::storages::StorageError;
+
+#[tokio::test]
+async fn put_content_addressed() {
+    let root = std::env::temp_dir().join(format!("isupipe-icons-put-{}", std::process::id()));
+    let storage = FilesystemIconStorageInfra::new(&root);
+    let image = b"icon image".to_vec();
+    let key = icon_hash(&image);
+
+    storage.put(&key, &image).await.unwrap();
+    // 同じキーを再度保存してもエラーにならない
+    storage.put(&key, &image).await.unwrap();
+
+    let saved = std::fs::read(root.join(&key[..2]).join(&key)).unwrap();
+    assert_eq!(saved, image);
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn put_rejects_invalid_key() {
+    let root = std::env::temp_dir().join(format!("isupipe-icons-invalid-{}", std::process::id()));
+    let storage = FilesystemIconStorageInfra::new(&root);
+
+    let result = storage.put("../../etc/passwd", b"image").await;
+    assert!(matches!(result, Err(StorageError::InvalidKey(_))));
+    assert!(!root.exists());
+}
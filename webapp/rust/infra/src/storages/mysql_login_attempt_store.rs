@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use isupipe_core::db::DBPool;
+use isupipe_core::models::login_attempt::LoginAttempt;
+use isupipe_core::storages::login_attempt_store::LoginAttemptStore;
+use isupipe_core::storages::StorageResult;
+
+#[cfg(test)]
+mod delete_expired;
+#[cfg(test)]
+mod record_failure;
+
+/// login_attempts テーブルにログイン失敗回数を保存する
+#[derive(Clone)]
+pub struct MySqlLoginAttemptStoreInfra {
+    db_pool: DBPool,
+}
+
+impl MySqlLoginAttemptStoreInfra {
+    pub fn new(db_pool: DBPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl LoginAttemptStore for MySqlLoginAttemptStoreInfra {
+    async fn find(&self, key: &str) -> StorageResult<Option<LoginAttempt>> {
+        let attempt = sqlx::query_as("SELECT * FROM login_attempts WHERE attempt_key = ?")
+            .bind(key)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(attempt)
+    }
+
+    async fn record_failure(
+        &self,
+        key: &str,
+        now: i64,
+        window_start: i64,
+    ) -> StorageResult<LoginAttempt> {
+        let mut tx = self.db_pool.begin().await?;
+
+        // 同時に失敗しても取りこぼさないよう、行ロックを取ったまま数える
+        sqlx::query(
+            "INSERT INTO login_attempts (attempt_key, failures, last_failed_at, locked_until) VALUES (?, 1, ?, 0) \
+             ON DUPLICATE KEY UPDATE failures = IF(last_failed_at < ?, 1, failures + 1), last_failed_at = VALUES(last_failed_at)",
+        )
+        .bind(key)
+        .bind(now)
+        .bind(window_start)
+        .execute(&mut *tx)
+        .await?;
+        let attempt = sqlx::query_as("SELECT * FROM login_attempts WHERE attempt_key = ?")
+            .bind(key)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(attempt)
+    }
+
+    async fn lock(&self, key: &str, locked_until: i64) -> StorageResult<()> {
+        sqlx::query("UPDATE login_attempts SET locked_until = ? WHERE attempt_key = ?")
+            .bind(locked_until)
+            .bind(key)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> StorageResult<()> {
+        sqlx::query("DELETE FROM login_attempts WHERE attempt_key = ?")
+            .bind(key)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, last_failed_before: i64, now: i64) -> StorageResult<u64> {
+        let rs = sqlx::query(
+            "DELETE FROM login_attempts WHERE last_failed_at < ? AND locked_until <= ?",
+        )
+        .bind(last_failed_before)
+        .bind(now)
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(rs.rows_affected())
+    }
+}
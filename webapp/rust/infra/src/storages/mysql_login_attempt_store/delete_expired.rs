@@ -0,0 +1,25 @@
+use crate::storages::mysql_login_attempt_store::MySqlLoginAttemptStoreInfra;
+use isupipe_core::db::get_db_pool;
+use isupipe_core::storages::login_attempt_store::LoginAttemptStore;
+
+#[tokio::test]
+async fn expired_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlLoginAttemptStoreInfra::new(db_pool);
+    let expired = format!("user:{}", uuid::Uuid::new_v4());
+    let locked = format!("user:{}", uuid::Uuid::new_v4());
+    let recent = format!("user:{}", uuid::Uuid::new_v4());
+
+    store.record_failure(&expired, 100, 0).await.unwrap();
+    store.record_failure(&locked, 100, 0).await.unwrap();
+    store.lock(&locked, 2000).await.unwrap();
+    store.record_failure(&recent, 900, 0).await.unwrap();
+
+    store.delete_expired(500, 1000).await.unwrap();
+    assert_eq!(store.find(&expired).await.unwrap(), None);
+    assert!(store.find(&locked).await.unwrap().is_some());
+    assert!(store.find(&recent).await.unwrap().is_some());
+
+    store.delete(&locked).await.unwrap();
+    store.delete(&recent).await.unwrap();
+}
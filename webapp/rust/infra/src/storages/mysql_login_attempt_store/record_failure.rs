@@ -0,0 +1,32 @@
+use crate::storages::mysql_login_attempt_store::MySqlLoginAttemptStoreInfra;
+use isupipe_core::db::get_db_pool;
+use isupipe_core::storages::login_attempt_store::LoginAttemptStore;
+
+#[tokio::test]
+async fn counted_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlLoginAttemptStoreInfra::new(db_pool);
+    let key = format!("user:{}", uuid::Uuid::new_v4());
+
+    store.record_failure(&key, 100, 0).await.unwrap();
+    let attempt = store.record_failure(&key, 200, 0).await.unwrap();
+    assert_eq!(attempt.failures, 2);
+    assert_eq!(attempt.last_failed_at, 200);
+
+    store.delete(&key).await.unwrap();
+    assert_eq!(store.find(&key).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn window_expired_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlLoginAttemptStoreInfra::new(db_pool);
+    let key = format!("user:{}", uuid::Uuid::new_v4());
+
+    store.record_failure(&key, 100, 0).await.unwrap();
+    store.record_failure(&key, 100, 0).await.unwrap();
+    let attempt = store.record_failure(&key, 1000, 500).await.unwrap();
+    assert_eq!(attempt.failures, 1);
+
+    store.delete(&key).await.unwrap();
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use isupipe_core::db::DBPool;
+use isupipe_core::models::session::{Session, SessionId};
+use isupipe_core::models::user::UserId;
+use isupipe_core::storages::session_store::SessionStore;
+use isupipe_core::storages::StorageResult;
+
+#[cfg(test)]
+mod delete_by_user_id;
+#[cfg(test)]
+mod find;
+
+/// sessions テーブルにセッションを保存する
+#[derive(Clone)]
+pub struct MySqlSessionStoreInfra {
+    db_pool: DBPool,
+}
+
+impl MySqlSessionStoreInfra {
+    pub fn new(db_pool: DBPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl SessionStore for MySqlSessionStoreInfra {
+    async fn create(&self, session: &Session) -> StorageResult<()> {
+        sqlx::query("INSERT INTO sessions (id, user_id, expires_at) VALUES (?, ?, ?)")
+            .bind(&session.id)
+            .bind(&session.user_id)
+            .bind(session.expires_at)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: &SessionId) -> StorageResult<Option<Session>> {
+        let session = sqlx::query_as("SELECT * FROM sessions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(session)
+    }
+
+    async fn update_expires_at(&self, id: &SessionId, expires_at: i64) -> StorageResult<()> {
+        sqlx::query("UPDATE sessions SET expires_at = ? WHERE id = ?")
+            .bind(expires_at)
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &SessionId) -> StorageResult<()> {
+        sqlx::query("DELETE FROM sessions WHERE id = ?")
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_by_user_id(&self, user_id: &UserId) -> StorageResult<u64> {
+        let result = sqlx::query("DELETE FROM sessions WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_by_user_id_except(
+        &self,
+        user_id: &UserId,
+        keep: &SessionId,
+    ) -> StorageResult<u64> {
+        let result = sqlx::query("DELETE FROM sessions WHERE user_id = ? AND id <> ?")
+            .bind(user_id)
+            .bind(keep)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_expired(&self, now: i64) -> StorageResult<u64> {
+        let result = sqlx::query("DELETE FROM sessions WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
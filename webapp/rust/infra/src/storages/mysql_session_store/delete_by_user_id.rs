@@ -0,0 +1,59 @@
+use crate::storages::mysql_session_store::MySqlSessionStoreInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::session::{Session, SessionId};
+use isupipe_core::models::user::UserId;
+use isupipe_core::storages::session_store::SessionStore;
+
+#[tokio::test]
+async fn deleted_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlSessionStoreInfra::new(db_pool);
+
+    let user_id: UserId = Faker.fake();
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let session = Session {
+            id: SessionId::new(uuid::Uuid::new_v4().to_string()),
+            user_id: user_id.clone(),
+            expires_at: Faker.fake(),
+        };
+        store.create(&session).await.unwrap();
+        ids.push(session.id);
+    }
+
+    let count = store.delete_by_user_id(&user_id).await.unwrap();
+    assert_eq!(count, 2);
+
+    for id in ids {
+        assert_eq!(store.find(&id).await.unwrap(), None);
+    }
+}
+
+#[tokio::test]
+async fn except_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlSessionStoreInfra::new(db_pool);
+
+    let user_id: UserId = Faker.fake();
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let session = Session {
+            id: SessionId::new(uuid::Uuid::new_v4().to_string()),
+            user_id: user_id.clone(),
+            expires_at: Faker.fake(),
+        };
+        store.create(&session).await.unwrap();
+        ids.push(session.id);
+    }
+
+    let count = store
+        .delete_by_user_id_except(&user_id, &ids[0])
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    assert!(store.find(&ids[0]).await.unwrap().is_some());
+    assert_eq!(store.find(&ids[1]).await.unwrap(), None);
+    store.delete(&ids[0]).await.unwrap();
+}
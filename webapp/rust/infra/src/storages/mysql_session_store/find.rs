@@ -0,0 +1,32 @@
+use crate::storages::mysql_session_store::MySqlSessionStoreInfra;
+use fake::{Fake, Faker};
+use isupipe_core::db::get_db_pool;
+use isupipe_core::models::session::{Session, SessionId};
+use isupipe_core::storages::session_store::SessionStore;
+
+#[tokio::test]
+async fn found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlSessionStoreInfra::new(db_pool);
+
+    let mut session: Session = Faker.fake();
+    session.id = SessionId::new(uuid::Uuid::new_v4().to_string());
+    store.create(&session).await.unwrap();
+
+    let got = store.find(&session.id).await.unwrap();
+    assert_eq!(got, Some(session.clone()));
+
+    store.delete(&session.id).await.unwrap();
+    let got = store.find(&session.id).await.unwrap();
+    assert_eq!(got, None);
+}
+
+#[tokio::test]
+async fn not_found_case() {
+    let db_pool = get_db_pool().await.unwrap();
+    let store = MySqlSessionStoreInfra::new(db_pool);
+
+    let id = SessionId::new(uuid::Uuid::new_v4().to_string());
+    let got = store.find(&id).await.unwrap();
+    assert_eq!(got, None);
+}
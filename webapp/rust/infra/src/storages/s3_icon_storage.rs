@@ -0,0 +1,227 @@
+use crate::storages::validate_key;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use isupipe_core::storages::icon_storage::IconStorage;
+use isupipe_core::storages::{StorageError, StorageResult};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+mod get;
+#[cfg(test)]
+mod put;
+#[cfg(test)]
+mod sign;
+#[cfg(test)]
+mod stand_in;
+
+const S3_ENDPOINT_ENV_KEY: &str = "ISUCON13_ICON_S3_ENDPOINT";
+const S3_BUCKET_ENV_KEY: &str = "ISUCON13_ICON_S3_BUCKET";
+const S3_REGION_ENV_KEY: &str = "ISUCON13_ICON_S3_REGION";
+const S3_PREFIX_ENV_KEY: &str = "ISUCON13_ICON_S3_PREFIX";
+const S3_ACCESS_KEY_ID_ENV_KEY: &str = "ISUCON13_ICON_S3_ACCESS_KEY_ID";
+const S3_SECRET_ACCESS_KEY_ENV_KEY: &str = "ISUCON13_ICON_S3_SECRET_ACCESS_KEY";
+const DEFAULT_S3_REGION: &str = "us-east-1";
+
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    /// 例: https://s3.ap-northeast-1.amazonaws.com, http://127.0.0.1:9000
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    /// オブジェクトキーの前に付ける文字列。例: icons/
+    pub prefix: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl S3Config {
+    pub fn from_env() -> StorageResult<Self> {
+        fn required(key: &str) -> StorageResult<String> {
+            std::env::var(key)
+                .map_err(|_| StorageError::ConfigError(format!("environ {} must be provided", key)))
+        }
+
+        Ok(Self {
+            endpoint: required(S3_ENDPOINT_ENV_KEY)?,
+            bucket: required(S3_BUCKET_ENV_KEY)?,
+            region: std::env::var(S3_REGION_ENV_KEY)
+                .unwrap_or_else(|_| DEFAULT_S3_REGION.to_owned()),
+            prefix: std::env::var(S3_PREFIX_ENV_KEY).unwrap_or_default(),
+            access_key_id: required(S3_ACCESS_KEY_ID_ENV_KEY)?,
+            secret_access_key: required(S3_SECRET_ACCESS_KEY_ENV_KEY)?,
+        })
+    }
+}
+
+/// S3互換のオブジェクトストレージに画像を保存する
+/// MinIOなどでも使えるよう、パス形式のURLと署名バージョン4を使う
+#[derive(Clone)]
+pub struct S3IconStorageInfra {
+    client: reqwest::Client,
+    endpoint: Url,
+    config: S3Config,
+}
+
+impl S3IconStorageInfra {
+    pub fn new(config: S3Config) -> StorageResult<Self> {
+        let endpoint = Url::parse(&config.endpoint).map_err(|e| {
+            StorageError::ConfigError(format!("invalid S3 endpoint {}: {}", config.endpoint, e))
+        })?;
+        if endpoint.host_str().is_none() {
+            return Err(StorageError::ConfigError(format!(
+                "invalid S3 endpoint {}: host is missing",
+                config.endpoint
+            )));
+        }
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            config,
+        })
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+    ) -> StorageResult<reqwest::Response> {
+        validate_key(key)?;
+
+        let base_path = self.endpoint.path().trim_end_matches('/');
+        let path = format!(
+            "{}/{}/{}{}",
+            base_path, self.config.bucket, self.config.prefix, key
+        );
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+        // Url が正規化したパスを署名に使う
+        let canonical_uri = url.path().to_owned();
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_owned(),
+        };
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let mut headers = BTreeMap::new();
+        headers.insert("host", host);
+        headers.insert("x-amz-content-sha256", payload_hash.clone());
+        headers.insert("x-amz-date", amz_date.clone());
+        let authorization = sign(&SigningParams {
+            access_key_id: &self.config.access_key_id,
+            secret_access_key: &self.config.secret_access_key,
+            region: &self.config.region,
+            amz_date: &amz_date,
+            method: method.as_str(),
+            canonical_uri: &canonical_uri,
+            headers: &headers,
+            payload_hash: &payload_hash,
+        });
+
+        self.client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| StorageError::RequestError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl IconStorage for S3IconStorageInfra {
+    async fn put(&self, key: &str, image: &[u8]) -> StorageResult<()> {
+        let response = self.send(Method::PUT, key, image.to_vec()).await?;
+        ensure_success(response).await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> StorageResult<Option<Vec<u8>>> {
+        let response = self.send(Method::GET, key, Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let image = ensure_success(response)
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| StorageError::RequestError(e.to_string()))?;
+
+        Ok(Some(image.to_vec()))
+    }
+}
+
+async fn ensure_success(response: reqwest::Response) -> StorageResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(StorageError::RequestError(format!(
+        "unexpected status {}: {}",
+        status, body
+    )))
+}
+
+struct SigningParams<'a> {
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    region: &'a str,
+    /// YYYYMMDDTHHMMSSZ
+    amz_date: &'a str,
+    method: &'a str,
+    canonical_uri: &'a str,
+    /// 署名に含めるヘッダ。キーは小文字
+    headers: &'a BTreeMap<&'a str, String>,
+    payload_hash: &'a str,
+}
+
+/// 署名バージョン4の Authorization ヘッダを組み立てる
+/// クエリ文字列は使わないので、正規化クエリは常に空にする
+fn sign(params: &SigningParams) -> String {
+    let date = &params.amz_date[..8];
+    let scope = format!("{}/{}/s3/aws4_request", date, params.region);
+
+    let canonical_headers: String = params
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = params.headers.keys().copied().collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        params.method, params.canonical_uri, canonical_headers, signed_headers, params.payload_hash
+    );
+
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        params.amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [date, params.region, "s3", "aws4_request"].iter().fold(
+        format!("AWS4{}", params.secret_access_key).into_bytes(),
+        |key, data| hmac_sha256(&key, data.as_bytes()),
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        params.access_key_id, scope, signed_headers, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
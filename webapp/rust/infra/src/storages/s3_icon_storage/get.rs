@@ -0,0 +1,25 @@
+use crate::storages::s3_icon_storage::stand_in::{self, BUCKET};
+use crate::storages::s3_icon_storage::{S3Config, S3IconStorageInfra};
+use isupipe_core::services::icon_service::icon_hash;
+use isupipe_core::storages::icon_storage::IconStorage;
+
+#[tokio::test]
+async fn get() {
+    let (endpoint, _) = stand_in::spawn();
+    let storage = S3IconStorageInfra::new(S3Config {
+        endpoint,
+        bucket: BUCKET.to_owned(),
+        region: "us-east-1".to_owned(),
+        prefix: String::new(),
+        access_key_id: "test-access-key".to_owned(),
+        secret_access_key: "test-secret-key".to_owned(),
+    })
+    .unwrap();
+    let image = b"icon image".to_vec();
+    let key = icon_hash(&image);
+
+    assert_eq!(storage.get(&key).await.unwrap(), None);
+
+    storage.put(&key, &image).await.unwrap();
+    assert_eq!(storage.get(&key).await.unwrap(), Some(image));
+}
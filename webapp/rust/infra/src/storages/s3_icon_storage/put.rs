@@ -0,0 +1,45 @@
+use crate::storages::s3_icon_storage::stand_in::{self, BUCKET};
+use crate::storages::s3_icon_storage::{S3Config, S3IconStorageInfra};
+use isupipe_core::services::icon_service::icon_hash;
+use isupipe_core::storages::icon_storage::IconStorage;
+use isupipe_core::storages::StorageError;
+
+fn config(endpoint: String) -> S3Config {
+    S3Config {
+        endpoint,
+        bucket: BUCKET.to_owned(),
+        region: "us-east-1".to_owned(),
+        prefix: "icons/".to_owned(),
+        access_key_id: "test-access-key".to_owned(),
+        secret_access_key: "test-secret-key".to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn put() {
+    let (endpoint, objects) = stand_in::spawn();
+    let storage = S3IconStorageInfra::new(config(endpoint)).unwrap();
+    let image = b"icon image".to_vec();
+    let key = icon_hash(&image);
+
+    storage.put(&key, &image).await.unwrap();
+
+    let saved = objects
+        .lock()
+        .unwrap()
+        .get(&format!("icons/{}", key))
+        .cloned();
+    assert_eq!(saved, Some(image));
+}
+
+#[tokio::test]
+async fn put_rejected() {
+    let (endpoint, _) = stand_in::spawn();
+    let mut config = config(endpoint);
+    config.access_key_id = "unknown".to_owned();
+    let storage = S3IconStorageInfra::new(config).unwrap();
+    let image = b"icon image".to_vec();
+
+    let result = storage.put(&icon_hash(&image), &image).await;
+    assert!(matches!(result, Err(StorageError::RequestError(_))));
+}
@@ -0,0 +1,77 @@
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub const BUCKET: &str = "isupipe";
+
+pub type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// テスト用のS3互換サーバを起動し、エンドポイントと保存されたオブジェクトを返す
+/// 署名そのものは検証せず、必要なヘッダが揃っているかだけを確認する
+pub fn spawn() -> (String, Objects) {
+    let objects = Objects::default();
+    let app = axum::Router::new()
+        .route(
+            "/:bucket/*key",
+            axum::routing::put(put_object).get(get_object),
+        )
+        .with_state(objects.clone());
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = axum::Server::from_tcp(listener)
+        .unwrap()
+        .serve(app.into_make_service());
+    tokio::spawn(server);
+
+    (format!("http://{}", addr), objects)
+}
+
+fn verify(headers: &HeaderMap, bucket: &str, body: &[u8]) -> Result<(), StatusCode> {
+    let authorization = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let content_sha256 = headers
+        .get("x-amz-content-sha256")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let valid = bucket == BUCKET
+        && authorization.starts_with("AWS4-HMAC-SHA256 Credential=test-access-key/")
+        && headers.contains_key("x-amz-date")
+        && content_sha256 == hex::encode(Sha256::digest(body));
+    if valid {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+async fn put_object(
+    State(objects): State<Objects>,
+    Path((bucket, key)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> StatusCode {
+    if let Err(status) = verify(&headers, &bucket, &body) {
+        return status;
+    }
+    objects.lock().unwrap().insert(key, body.to_vec());
+    StatusCode::OK
+}
+
+async fn get_object(
+    State(objects): State<Objects>,
+    Path((bucket, key)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Vec<u8>, StatusCode> {
+    verify(&headers, &bucket, b"")?;
+    objects
+        .lock()
+        .unwrap()
+        .get(&key)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -7,21 +7,70 @@ CREATE TABLE `users` (
   `display_name` VARCHAR(255) NOT NULL,
   `password` VARCHAR(255) NOT NULL,
   `description` TEXT NOT NULL,
-  UNIQUE `uniq_user_name` (`name`)
+  UNIQUE `uniq_user_name` (`name`),
+  -- ユーザー名はDNSラベルになるので大文字小文字を区別せずに一意にする
+  UNIQUE `uniq_user_name_ci` ((LOWER(`name`)))
+) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+-- ログインセッション。行を削除するとそのセッションは失効する
+CREATE TABLE `sessions` (
+  `id` VARCHAR(36) NOT NULL PRIMARY KEY,
+  `user_id` BIGINT NOT NULL,
+  `expires_at` BIGINT NOT NULL,
+  INDEX `sessions_user_id` (`user_id`),
+  INDEX `sessions_expires_at` (`expires_at`)
+) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+-- ログイン失敗回数。キーは user:<ユーザー名> もしくは ip:<接続元IP>
+CREATE TABLE `login_attempts` (
+  `attempt_key` VARCHAR(255) NOT NULL PRIMARY KEY,
+  `failures` BIGINT NOT NULL,
+  `last_failed_at` BIGINT NOT NULL,
+  `locked_until` BIGINT NOT NULL
+) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+-- パスワード再設定トークン。トークンそのものではなく SHA-256 を保存する
+CREATE TABLE `password_reset_tokens` (
+  `id` BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  `user_id` BIGINT NOT NULL,
+  `token_hash` VARCHAR(64) NOT NULL,
+  `expires_at` BIGINT NOT NULL,
+  `used_at` BIGINT NULL,
+  `created_at` BIGINT NOT NULL DEFAULT 0,
+  UNIQUE `uniq_password_reset_token_hash` (`token_hash`),
+  INDEX `password_reset_tokens_user_id` (`user_id`)
 ) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
 
 -- プロフィール画像
 CREATE TABLE `icons` (
   `id` BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
   `user_id` BIGINT NOT NULL,
-  `image` LONGBLOB NOT NULL
+  -- 外部ストレージに移した画像は NULL にし、hash をキーにして取得する
+  `image` LONGBLOB NULL,
+  `hash` VARCHAR(64) NOT NULL DEFAULT '',
+  `mime_type` VARCHAR(32) NOT NULL DEFAULT 'image/jpeg'
+) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+-- プロフィール画像のサムネイル
+CREATE TABLE `icon_thumbnails` (
+  `id` BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  `user_id` BIGINT NOT NULL,
+  `size` INT NOT NULL,
+  `mime_type` VARCHAR(32) NOT NULL,
+  `image` LONGBLOB NULL,
+  `hash` VARCHAR(64) NOT NULL,
+  UNIQUE `uniq_icon_thumbnail` (`user_id`, `size`)
 ) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
 
 -- ユーザごとのカスタムテーマ
 CREATE TABLE `themes` (
   `id` BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
   `user_id` BIGINT NOT NULL,
-  `dark_mode` BOOLEAN NOT NULL
+  `dark_mode` BOOLEAN NOT NULL,
+  `accent_color` VARCHAR(7) NOT NULL DEFAULT '#ff9900',
+  `background_image_url` VARCHAR(2048) DEFAULT NULL,
+  `font` VARCHAR(32) NOT NULL DEFAULT 'default',
+  `chat_color_scheme` VARCHAR(32) NOT NULL DEFAULT 'default'
 ) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
 
 -- ライブ配信
@@ -75,6 +124,7 @@ CREATE TABLE `livecomments` (
   `tip` BIGINT NOT NULL DEFAULT 0,
   `created_at` BIGINT NOT NULL
 ) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+CREATE INDEX livecomments_livestream_id_created_at ON livecomments(`livestream_id`, `created_at`, `id`);
 
 -- ユーザからのライブコメントのスパム報告
 CREATE TABLE `livecomment_reports` (
@@ -91,9 +141,12 @@ CREATE TABLE `ng_words` (
   `user_id` BIGINT NOT NULL,
   `livestream_id` BIGINT NOT NULL,
   `word` VARCHAR(255) NOT NULL,
+  -- substring, whole_word, case_insensitive, nfkc, regex のいずれか
+  `match_mode` VARCHAR(32) NOT NULL DEFAULT 'substring',
   `created_at` BIGINT NOT NULL
 ) ENGINE=InnoDB CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
 CREATE INDEX ng_words_word ON ng_words(`word`);
+CREATE INDEX ng_words_livestream_id ON ng_words(`livestream_id`, `id`);
 
 -- ライブ配信に対するリアクション
 CREATE TABLE `reactions` (
@@ -0,0 +1,26 @@
+-- 既存のDBを 10_schema.sql に追いつかせる。何度実行してもよい
+-- 追加した列は既存の行にもデフォルト値が入る
+
+DROP PROCEDURE IF EXISTS isupipe_add_column;
+DELIMITER //
+CREATE PROCEDURE isupipe_add_column(IN tbl VARCHAR(64), IN col VARCHAR(64), IN definition TEXT)
+BEGIN
+  IF NOT EXISTS (
+    SELECT 1 FROM information_schema.COLUMNS
+    WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = tbl AND COLUMN_NAME = col
+  ) THEN
+    SET @isupipe_ddl = CONCAT('ALTER TABLE `', tbl, '` ADD COLUMN `', col, '` ', definition);
+    PREPARE stmt FROM @isupipe_ddl;
+    EXECUTE stmt;
+    DEALLOCATE PREPARE stmt;
+  END IF;
+END//
+DELIMITER ;
+
+-- ユーザごとのカスタムテーマ
+CALL isupipe_add_column('themes', 'accent_color', "VARCHAR(7) NOT NULL DEFAULT '#ff9900'");
+CALL isupipe_add_column('themes', 'background_image_url', 'VARCHAR(2048) DEFAULT NULL');
+CALL isupipe_add_column('themes', 'font', "VARCHAR(32) NOT NULL DEFAULT 'default'");
+CALL isupipe_add_column('themes', 'chat_color_scheme', "VARCHAR(32) NOT NULL DEFAULT 'default'");
+
+DROP PROCEDURE isupipe_add_column;